@@ -1,6 +1,11 @@
 //! A simple demo of how to use the binsider as a library.
 
-use std::{env, fs, path::PathBuf, sync::mpsc, time::Duration};
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::{mpsc, Arc},
+    time::Duration,
+};
 
 use binsider::{prelude::*, tui::ui::Tab};
 
@@ -16,12 +21,12 @@ fn main() -> Result<()> {
     let file_info = FileInfo::new(
         path.to_str().unwrap_or_default(),
         None,
-        file_data.as_slice(),
+        Arc::new(FileData::from(file_data)),
     )?;
-    let analyzer = Analyzer::new(file_info, 15, vec![])?;
+    let analyzer = Analyzer::new(file_info, 15, vec![], None)?;
     let mut state = State::new(analyzer, None)?;
     let (sender, receiver) = mpsc::channel();
-    state.analyzer.extract_strings(sender.clone());
+    state.view.analyzer.extract_strings(sender.clone());
 
     let mut terminal = ratatui::init();
     loop {
@@ -42,10 +47,10 @@ fn main() -> Result<()> {
         }
 
         // Handle binsider events.
-        if let Ok(Event::FileStrings(strings)) = receiver.try_recv() {
-            state.strings_loaded = true;
-            state.analyzer.strings = Some(strings?.into_iter().map(|(v, l)| (l, v)).collect());
-            if state.tab == Tab::Strings {
+        if let Ok(Event::FileStrings(_, strings)) = receiver.try_recv() {
+            state.view.strings_loaded = true;
+            state.view.analyzer.set_strings(Some(strings?));
+            if state.view.tab == Tab::Strings {
                 state.handle_tab()?;
             }
         }
@@ -0,0 +1,352 @@
+use crate::{diff::Change, elf::Elf};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Compatibility verdict of an ABI change.
+///
+/// The variants are ordered by severity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    /// Existing binaries keep working.
+    #[default]
+    Compatible,
+    /// Existing binaries might break.
+    Suspect,
+    /// Existing binaries break.
+    Breaking,
+}
+
+impl Verdict {
+    /// Returns the exit code for the command-line interface.
+    ///
+    /// The codes do not clash with the errors (1) and the usage errors (2).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Verdict::Compatible => 0,
+            Verdict::Breaking => 3,
+            Verdict::Suspect => 4,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Verdict::Compatible => "compatible",
+            Verdict::Suspect => "suspect",
+            Verdict::Breaking => "breaking",
+        })
+    }
+}
+
+/// ABI change between two builds of a shared library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbiChange {
+    /// Verdict of the change.
+    pub verdict: Verdict,
+    /// Kind of the change.
+    pub kind: &'static str,
+    /// Changed symbol, version node or SONAME.
+    pub subject: String,
+    /// Details of the change.
+    pub details: String,
+}
+
+/// Exported dynamic symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Export {
+    /// Symbol type.
+    symtype: u8,
+    /// Symbol binding.
+    bind: u8,
+    /// Symbol size.
+    size: u64,
+}
+
+/// ABI compatibility report.
+#[derive(Clone, Debug, Default)]
+pub struct AbiReport {
+    /// SONAME of the old file.
+    pub old_soname: Option<String>,
+    /// SONAME of the new file.
+    pub new_soname: Option<String>,
+    /// List of changes (the most severe first).
+    pub changes: Vec<AbiChange>,
+}
+
+impl AbiReport {
+    /// Compares the exported dynamic symbols, version nodes and SONAMEs of the given files.
+    pub fn new(old: &Elf, new: &Elf) -> Self {
+        let mut changes = Vec::new();
+        let old_soname = old.dynamic.soname().map(String::from);
+        let new_soname = new.dynamic.soname().map(String::from);
+        match (&old_soname, &new_soname) {
+            (Some(old_soname), Some(new_soname)) if old_soname != new_soname => {
+                changes.push(AbiChange {
+                    verdict: Verdict::Breaking,
+                    kind: "SONAME changed",
+                    subject: new_soname.to_string(),
+                    details: format!("{old_soname} → {new_soname}"),
+                })
+            }
+            (Some(old_soname), None) => changes.push(AbiChange {
+                verdict: Verdict::Suspect,
+                kind: "SONAME removed",
+                subject: old_soname.to_string(),
+                details: String::new(),
+            }),
+            (None, Some(new_soname)) => changes.push(AbiChange {
+                verdict: Verdict::Suspect,
+                kind: "SONAME added",
+                subject: new_soname.to_string(),
+                details: String::new(),
+            }),
+            _ => {}
+        }
+
+        let new_definitions = new
+            .dynamic_symbols
+            .definitions()
+            .iter()
+            .collect::<HashSet<_>>();
+        for definition in old.dynamic_symbols.definitions() {
+            if !new_definitions.contains(definition) {
+                changes.push(AbiChange {
+                    verdict: Verdict::Breaking,
+                    kind: "Version removed",
+                    subject: definition.to_string(),
+                    details: String::new(),
+                });
+            }
+        }
+        let old_definitions = old
+            .dynamic_symbols
+            .definitions()
+            .iter()
+            .collect::<HashSet<_>>();
+        for definition in new.dynamic_symbols.definitions() {
+            if !old_definitions.contains(definition) {
+                changes.push(AbiChange {
+                    verdict: Verdict::Compatible,
+                    kind: "Version added",
+                    subject: definition.to_string(),
+                    details: String::new(),
+                });
+            }
+        }
+
+        let old_exports = exports(old);
+        let new_exports = exports(new);
+        let new_names = new_exports
+            .iter()
+            .map(|((name, _), _)| name.to_string())
+            .collect::<HashSet<_>>();
+        let new_exports = new_exports.into_iter().collect::<HashMap<_, _>>();
+        let old_keys = old_exports
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<HashSet<_>>();
+        for (key, old_export) in &old_exports {
+            let subject = symbol_name(key);
+            let Some(new_export) = new_exports.get(key) else {
+                let (verdict, details) = if key.1.is_none() && new_names.contains(&key.0) {
+                    (Verdict::Suspect, String::from("now versioned"))
+                } else if new_names.contains(&key.0) {
+                    (Verdict::Breaking, String::from("version changed"))
+                } else {
+                    (Verdict::Breaking, String::new())
+                };
+                changes.push(AbiChange {
+                    verdict,
+                    kind: "Symbol removed",
+                    subject,
+                    details,
+                });
+                continue;
+            };
+            if old_export.symtype != new_export.symtype {
+                changes.push(AbiChange {
+                    verdict: Verdict::Breaking,
+                    kind: "Type changed",
+                    subject: subject.to_string(),
+                    details: format!(
+                        "{} → {}",
+                        symtype_to_string(old_export.symtype),
+                        symtype_to_string(new_export.symtype)
+                    ),
+                });
+            }
+            // Sizes of functions do not matter for the callers, but the sizes of
+            // objects are baked into the binaries via copy relocations.
+            if old_export.symtype != elf::abi::STT_FUNC
+                && old_export.symtype == new_export.symtype
+                && old_export.size != new_export.size
+            {
+                changes.push(AbiChange {
+                    verdict: Verdict::Breaking,
+                    kind: "Size changed",
+                    subject: subject.to_string(),
+                    details: format!("{} → {}", old_export.size, new_export.size),
+                });
+            }
+            if old_export.bind != new_export.bind {
+                changes.push(AbiChange {
+                    verdict: Verdict::Suspect,
+                    kind: "Binding changed",
+                    subject,
+                    details: format!(
+                        "{} → {}",
+                        bind_to_string(old_export.bind),
+                        bind_to_string(new_export.bind)
+                    ),
+                });
+            }
+        }
+        for (key, _) in exports(new) {
+            if !old_keys.contains(&key) {
+                changes.push(AbiChange {
+                    verdict: Verdict::Compatible,
+                    kind: "Symbol added",
+                    subject: symbol_name(&key),
+                    details: String::new(),
+                });
+            }
+        }
+        changes.sort_by_key(|change| std::cmp::Reverse(change.verdict));
+        Self {
+            old_soname,
+            new_soname,
+            changes,
+        }
+    }
+
+    /// Returns the rows of the report.
+    ///
+    /// The first column of each row is the [`Change`] marker of the verdict.
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        self.changes
+            .iter()
+            .map(|change| {
+                vec![
+                    match change.verdict {
+                        Verdict::Compatible => Change::Added,
+                        Verdict::Suspect => Change::Modified,
+                        Verdict::Breaking => Change::Removed,
+                    }
+                    .marker()
+                    .to_string(),
+                    change.verdict.to_string(),
+                    change.kind.to_string(),
+                    change.subject.to_string(),
+                    change.details.to_string(),
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns the overall verdict.
+    pub fn verdict(&self) -> Verdict {
+        self.changes
+            .iter()
+            .map(|change| change.verdict)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Returns the exported dynamic symbols keyed by their name and version.
+///
+/// Only the defined global/weak symbols with default/protected visibility are exported.
+fn exports(elf: &Elf) -> Vec<((String, Option<String>), Export)> {
+    elf.dynamic_symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, (name, symbol))| {
+            !name.is_empty()
+                && !symbol.is_undefined()
+                && matches!(
+                    symbol.st_bind(),
+                    elf::abi::STB_GLOBAL | elf::abi::STB_WEAK | elf::abi::STB_GNU_UNIQUE
+                )
+                && matches!(
+                    symbol.st_vis(),
+                    elf::abi::STV_DEFAULT | elf::abi::STV_PROTECTED
+                )
+        })
+        .map(|(i, (name, symbol))| {
+            (
+                (
+                    name.to_string(),
+                    elf.dynamic_symbols.version(i).map(|v| v.name.to_string()),
+                ),
+                Export {
+                    symtype: symbol.st_symtype(),
+                    bind: symbol.st_bind(),
+                    size: symbol.st_size,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Returns the symbol name along with its version.
+fn symbol_name(key: &(String, Option<String>)) -> String {
+    match &key.1 {
+        Some(version) => format!("{}@{}", key.0, version),
+        None => key.0.to_string(),
+    }
+}
+
+/// Returns the human readable symbol type.
+fn symtype_to_string(symtype: u8) -> String {
+    elf::to_str::st_symtype_to_string(symtype)
+        .trim_start_matches("STT_")
+        .to_string()
+}
+
+/// Returns the human readable symbol binding.
+fn bind_to_string(bind: u8) -> String {
+    elf::to_str::st_bind_to_string(bind)
+        .trim_start_matches("STB_")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_verdict() {
+        assert!(Verdict::Breaking > Verdict::Suspect);
+        assert!(Verdict::Suspect > Verdict::Compatible);
+        assert_eq!(
+            vec![0, 4, 3],
+            [Verdict::Compatible, Verdict::Suspect, Verdict::Breaking]
+                .map(|v| v.exit_code())
+                .to_vec()
+        );
+        assert_eq!(Verdict::Compatible, AbiReport::default().verdict());
+        assert_eq!(
+            Verdict::Breaking,
+            AbiReport {
+                changes: vec![
+                    AbiChange {
+                        verdict: Verdict::Compatible,
+                        kind: "Symbol added",
+                        subject: String::from("foo"),
+                        details: String::new(),
+                    },
+                    AbiChange {
+                        verdict: Verdict::Breaking,
+                        kind: "Symbol removed",
+                        subject: String::from("bar"),
+                        details: String::new(),
+                    },
+                ],
+                ..Default::default()
+            }
+            .verdict()
+        );
+    }
+}
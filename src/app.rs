@@ -1,20 +1,31 @@
 use crate::{
-    elf::Elf,
+    cache::{self, Cache},
+    deps::{DependencyTree, Provider},
+    elf::{Elf, Info},
     error::{Error, Result},
-    file::FileInfo,
-    tui::event::Event,
+    file::{FileData, FileInfo},
+    hashes::FileHashes,
+    insights::{
+        capabilities::Capabilities,
+        crypto::{self, CryptoConstant},
+        rules::{RuleMatch, RuleSet},
+    },
+    job::{JobKind, Jobs},
+    process::Process,
+    size::SizeReport,
+    strings::{self, FileString, StringEncoding},
+    tui::{event::Event, widgets::search::SearchIndex},
+    xrefs::Xrefs,
 };
 use elf::{endian::AnyEndian, ElfBytes};
 use heh::app::Application as Heh;
 use heh::decoder::Encoding;
-use lddtree::DependencyAnalyzer;
 use ratatui::text::Line;
-use rust_strings::BytesConfig;
 use std::{
+    collections::HashMap,
     fmt::{self, Debug, Formatter},
-    path::PathBuf,
-    sync::mpsc,
-    thread,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, OnceLock},
 };
 
 /// Tracer data.
@@ -28,24 +39,60 @@ pub struct TraceData {
 
 /// Binary analyzer.
 pub struct Analyzer<'a> {
+    /// Identifier of the file among the open files.
+    pub id: usize,
     /// List of files that are being analyzed.
     pub files: Vec<PathBuf>,
     /// Current file information.
     pub file: FileInfo<'a>,
     /// Elf properties.
     pub elf: Elf,
+    /// Rows of the ELF information tables.
+    ///
+    /// The rows are built and indexed on the first visit of the table.
+    pub info_rows: HashMap<Info, SearchIndex>,
     /// Strings.
-    pub strings: Option<Vec<(u64, String)>>,
+    ///
+    /// Use [`Analyzer::set_strings`] for updating the strings and their rows.
+    pub strings: Option<Vec<FileString>>,
+    /// Rows of the strings table.
+    pub strings_rows: SearchIndex,
     /// Min length of the strings.
     pub strings_len: usize,
+    /// Encodings of the strings.
+    pub strings_encodings: Vec<StringEncoding>,
     /// Heh application.
     pub heh: Heh,
     /// Tracer data.
     pub tracer: TraceData,
     /// System calls.
     pub system_calls: Vec<Line<'a>>,
+    /// Root directory for resolving the library dependencies.
+    root: PathBuf,
     /// Library dependencies.
-    pub dependencies: Vec<(String, String)>,
+    dependencies: OnceLock<DependencyTree>,
+    /// Providers of the dynamic symbols.
+    symbol_providers: OnceLock<Vec<Option<Provider>>>,
+    /// Size breakdown.
+    size: OnceLock<SizeReport>,
+    /// Cross-references.
+    pub xrefs: Option<Xrefs>,
+    /// Pattern matching rules.
+    pub rules: RuleSet,
+    /// Rule matches.
+    pub rule_matches: Option<Vec<RuleMatch>>,
+    /// Cryptographic constants.
+    pub crypto_constants: Option<Vec<CryptoConstant>>,
+    /// Capabilities inferred from the imports.
+    capabilities: OnceLock<Capabilities>,
+    /// File hashes.
+    pub hashes: Option<FileHashes>,
+    /// Running process of the file.
+    pub process: Option<Process>,
+    /// Background jobs.
+    pub jobs: Jobs,
+    /// Cache of the analysis results (if enabled).
+    pub cache: Option<Arc<Cache>>,
 }
 
 impl Debug for Analyzer<'_> {
@@ -58,66 +105,236 @@ impl Debug for Analyzer<'_> {
 
 impl<'a> Analyzer<'a> {
     /// Constructs a new instance.
+    ///
+    /// The library dependencies are resolved inside the given root directory
+    /// (defaults to `/`) when they are first needed.
     pub fn new(
         mut file_info: FileInfo<'a>,
         strings_len: usize,
         files: Vec<PathBuf>,
+        root: Option<&Path>,
     ) -> Result<Self> {
-        let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(file_info.bytes)?;
+        let elf_bytes = ElfBytes::<AnyEndian>::minimal_parse(&file_info.bytes)?;
         let elf = Elf::try_from(elf_bytes)?;
         let heh = Heh::new(file_info.open_file()?, Encoding::Ascii, 0)
             .map_err(|e| Error::HexdumpError(e.to_string()))?;
         Ok(Self {
-            dependencies: Self::extract_libs(&file_info)?,
+            id: 0,
+            root: root.unwrap_or(Path::new("/")).to_path_buf(),
+            dependencies: OnceLock::new(),
+            symbol_providers: OnceLock::new(),
             files,
             file: file_info,
             elf,
+            info_rows: HashMap::new(),
             strings: None,
+            strings_rows: SearchIndex::default(),
             strings_len,
+            strings_encodings: vec![StringEncoding::default()],
             heh,
             tracer: TraceData::default(),
             system_calls: Vec::new(),
+            size: OnceLock::new(),
+            xrefs: None,
+            rules: RuleSet::default(),
+            rule_matches: None,
+            crypto_constants: None,
+            capabilities: OnceLock::new(),
+            hashes: None,
+            process: None,
+            jobs: Jobs::default(),
+            cache: None,
         })
     }
 
     /// Extracts the library dependencies.
-    pub fn extract_libs(file_info: &FileInfo<'a>) -> Result<Vec<(String, String)>> {
-        let mut dependencies = DependencyAnalyzer::default()
-            .analyze(file_info.path)?
-            .libraries
-            .clone()
-            .into_iter()
-            .map(|(name, lib)| {
-                (
-                    name.to_string(),
-                    lib.realpath
-                        .unwrap_or(lib.path)
-                        .to_string_lossy()
-                        .to_string(),
-                )
-            })
-            .collect::<Vec<(String, String)>>();
-        dependencies.sort_by(|a, b| {
-            let lib_condition1 = a.0.starts_with("lib");
-            let lib_condition2 = b.0.starts_with("lib");
-            match (lib_condition1, lib_condition2) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.0.cmp(&b.0),
+    pub fn extract_libs(file_info: &FileInfo<'a>, root: Option<&Path>) -> Result<DependencyTree> {
+        DependencyTree::new(
+            Path::new(&file_info.path),
+            &file_info.bytes,
+            root.unwrap_or(Path::new("/")),
+        )
+    }
+
+    /// Returns the library dependencies.
+    ///
+    /// The dependencies are resolved on the first call. The tree is empty if the
+    /// dynamic information of the file cannot be parsed.
+    pub fn dependencies(&self) -> &DependencyTree {
+        self.dependencies
+            .get_or_init(|| Self::extract_libs(&self.file, Some(&self.root)).unwrap_or_default())
+    }
+
+    /// Returns the providers of the dynamic symbols.
+    pub fn symbol_providers(&self) -> &[Option<Provider>] {
+        self.symbol_providers
+            .get_or_init(|| self.dependencies().resolve(&self.elf.dynamic_symbols))
+    }
+
+    /// Returns the size breakdown.
+    pub fn size(&self) -> &SizeReport {
+        self.size
+            .get_or_init(|| SizeReport::new(&self.elf, self.file.bytes.len() as u64))
+    }
+
+    /// Returns the capabilities inferred from the imports.
+    pub fn capabilities(&self) -> &Capabilities {
+        self.capabilities
+            .get_or_init(|| Capabilities::new(&self.elf))
+    }
+
+    /// Returns the rows of the given ELF information table.
+    pub fn info_rows(&mut self, info: Info) -> &mut SearchIndex {
+        if !self.info_rows.contains_key(&info) {
+            let property = self.elf.info(&info);
+            let (mut rows, values) = (property.items(), property.values());
+            if let Info::DynamicSymbols = info {
+                let dependencies = self.dependencies();
+                let providers = self.symbol_providers();
+                for (i, row) in rows.iter_mut().enumerate() {
+                    row.push(dependencies.provider_name(providers.get(i).copied().flatten()));
+                }
             }
-        });
-        Ok(dependencies)
+            self.info_rows.insert(info, SearchIndex::new(rows, values));
+        }
+        self.info_rows.entry(info).or_default()
+    }
+
+    /// Sets the extracted strings.
+    ///
+    /// The rows are built and indexed once for each extraction.
+    pub fn set_strings(&mut self, strings: Option<Vec<FileString>>) {
+        self.strings_rows = SearchIndex::new(
+            strings.iter().flatten().map(FileString::row).collect(),
+            strings.iter().flatten().map(FileString::values).collect(),
+        );
+        self.strings = strings;
+    }
+
+    /// Returns the contents of the file for the background jobs.
+    fn shared_data(&self) -> Arc<FileData> {
+        Arc::clone(&self.file.bytes)
+    }
+
+    /// Restarts the background jobs that are cancelled (e.g. when the file was not active).
+    pub fn resume_jobs(&mut self, event_sender: mpsc::Sender<Event>) {
+        for kind in self.jobs.take_cancelled() {
+            match kind {
+                JobKind::Strings => self.extract_strings(event_sender.clone()),
+                JobKind::Xrefs => self.extract_xrefs(event_sender.clone()),
+                JobKind::Rules => self.match_rules(event_sender.clone()),
+                JobKind::CryptoConstants => self.extract_crypto_constants(event_sender.clone()),
+                JobKind::Hashes => self.extract_hashes(event_sender.clone()),
+            }
+        }
     }
 
     /// Returns the sequences of printable characters.
     pub fn extract_strings(&mut self, event_sender: mpsc::Sender<Event>) {
-        let config = BytesConfig::new(self.file.bytes.to_vec()).with_min_length(self.strings_len);
-        thread::spawn(move || {
-            event_sender
-                .send(Event::FileStrings(
-                    rust_strings::strings(&config).map_err(|e| Error::StringsError(e.to_string())),
-                ))
-                .expect("failed to send strings event");
-        });
+        let id = self.id;
+        let bytes = self.shared_data();
+        let min_length = self.strings_len;
+        let encodings = self.strings_encodings.clone();
+        let section_headers = self.elf.section_headers.clone();
+        let program_headers = self.elf.program_headers.clone();
+        let cache = self.cache.clone();
+        let entry = format!(
+            "strings-{min_length}-{}",
+            encodings
+                .iter()
+                .map(|v| format!("{v:?}").to_lowercase())
+                .collect::<Vec<_>>()
+                .join("-")
+        );
+        self.jobs.spawn(
+            JobKind::Strings,
+            event_sender,
+            move |job| {
+                cache::cached(cache.as_deref(), &bytes, &entry, job, || {
+                    strings::extract(&bytes, min_length, &encodings, job).map(|mut strings| {
+                        strings
+                            .iter_mut()
+                            .for_each(|string| string.locate(&section_headers, &program_headers));
+                        strings
+                    })
+                })
+            },
+            move |strings| Event::FileStrings(id, strings),
+        );
+    }
+
+    /// Finds the cross-references.
+    pub fn extract_xrefs(&mut self, event_sender: mpsc::Sender<Event>) {
+        let id = self.id;
+        let bytes = self.shared_data();
+        let cache = self.cache.clone();
+        self.jobs.spawn(
+            JobKind::Xrefs,
+            event_sender,
+            move |job| {
+                cache::cached(cache.as_deref(), &bytes, "xrefs", job, || {
+                    ElfBytes::<AnyEndian>::minimal_parse(&bytes)
+                        .and_then(Elf::try_from)
+                        .map(|elf| Xrefs::new(&elf, &bytes, job))
+                        .map_err(Error::from)
+                })
+            },
+            move |xrefs| Event::Xrefs(id, xrefs),
+        );
+    }
+
+    /// Matches the rules against the file.
+    pub fn match_rules(&mut self, event_sender: mpsc::Sender<Event>) {
+        let id = self.id;
+        let bytes = self.shared_data();
+        let rules = self.rules.clone();
+        self.jobs.spawn(
+            JobKind::Rules,
+            event_sender,
+            move |job| {
+                ElfBytes::<AnyEndian>::minimal_parse(&bytes)
+                    .and_then(Elf::try_from)
+                    .map(|elf| rules.scan(&bytes, &elf, job))
+                    .map_err(Error::from)
+            },
+            move |matches| Event::RuleMatches(id, matches),
+        );
+    }
+
+    /// Finds the cryptographic constants.
+    pub fn extract_crypto_constants(&mut self, event_sender: mpsc::Sender<Event>) {
+        let id = self.id;
+        let bytes = self.shared_data();
+        self.jobs.spawn(
+            JobKind::CryptoConstants,
+            event_sender,
+            move |job| {
+                ElfBytes::<AnyEndian>::minimal_parse(&bytes)
+                    .and_then(Elf::try_from)
+                    .map(|elf| crypto::scan(&bytes, &elf, job))
+                    .map_err(Error::from)
+            },
+            move |constants| Event::CryptoConstants(id, constants),
+        );
+    }
+
+    /// Computes the file hashes.
+    pub fn extract_hashes(&mut self, event_sender: mpsc::Sender<Event>) {
+        let id = self.id;
+        let bytes = self.shared_data();
+        let cache = self.cache.clone();
+        self.jobs.spawn(
+            JobKind::Hashes,
+            event_sender,
+            move |job| {
+                cache::cached(cache.as_deref(), &bytes, "hashes", job, || {
+                    ElfBytes::<AnyEndian>::minimal_parse(&bytes)
+                        .and_then(Elf::try_from)
+                        .map(|elf| FileHashes::new(&bytes, &elf, job))
+                        .map_err(Error::from)
+                })
+            },
+            move |hashes| Event::FileHashes(id, hashes),
+        );
     }
 }
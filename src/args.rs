@@ -1,7 +1,8 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use ratatui::style::Color;
 use std::path::PathBuf;
 
+use crate::strings::StringEncoding;
 use crate::tui::ui::Tab;
 
 /// Argument parser powered by [`clap`].
@@ -21,21 +22,101 @@ use crate::tui::ui::Tab;
 ",
 )]
 pub struct Args {
-    /// Binary / ELF object file.
+    /// Binary / ELF object file (or multiple files and directories to scan).
     #[arg(env, name = "FILE")]
     pub files: Vec<PathBuf>,
 
     /// Minimum length of strings.
-    #[arg(env, short = 'n', long = "min-len", default_value = "15")]
+    #[arg(
+        env,
+        short = 'n',
+        long = "min-len",
+        default_value = "15",
+        global = true
+    )]
     pub min_strings_len: usize,
 
+    /// Encodings of strings.
+    #[arg(
+        env,
+        short = 'e',
+        long = "encoding",
+        value_name = "ENCODING",
+        value_delimiter = ',',
+        default_value = "ascii",
+        global = true
+    )]
+    pub strings_encodings: Vec<StringEncoding>,
+
+    /// Rule files or directories to match against the file.
+    #[arg(env, long, value_name = "PATH", value_delimiter = ',', global = true)]
+    pub rules: Vec<PathBuf>,
+
+    /// Root directory for resolving the library dependencies.
+    #[arg(
+        env,
+        long,
+        visible_alias = "sysroot",
+        value_name = "PATH",
+        global = true
+    )]
+    pub root: Option<PathBuf>,
+
+    /// Analyze the running process with the given PID (Linux only).
+    #[arg(long, value_name = "PID", conflicts_with = "FILE")]
+    pub pid: Option<u32>,
+
+    /// Do not use the analysis cache.
+    #[arg(env, long, global = true)]
+    pub no_cache: bool,
+
+    /// Clear the analysis cache.
+    #[arg(env, long, global = true)]
+    pub clear_cache: bool,
+
+    /// Print the file hashes and exit.
+    #[arg(env, long, global = true)]
+    pub hashes: bool,
+
     /// The initial application tab to open.
-    #[arg(env, short = 't', long = "tab", default_value = "general")]
+    #[arg(
+        env,
+        short = 't',
+        long = "tab",
+        default_value = "general",
+        global = true
+    )]
     pub tab: Tab,
 
     /// Accent color of the application.
-    #[arg(env, long, value_name = "COLOR")]
+    #[arg(env, long, value_name = "COLOR", global = true)]
     pub accent_color: Option<Color>,
+
+    /// Subcommand to run.
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+/// Available subcommands.
+#[derive(Clone, Debug, Subcommand)]
+pub enum Commands {
+    /// Compare two ELF files.
+    Diff {
+        /// Old version of the file.
+        old: PathBuf,
+        /// New version of the file.
+        new: PathBuf,
+    },
+    /// Check the ABI compatibility of two builds of a shared library.
+    ///
+    /// Exits with 0 if compatible, 3 if breaking and 4 if suspect.
+    /// Errors exit with 1 and invalid arguments with 2.
+    Abi {
+        /// Old version of the library.
+        old: PathBuf,
+        /// New version of the library.
+        new: PathBuf,
+    },
 }
 
 #[cfg(test)]
@@ -0,0 +1,187 @@
+use crate::error::{Error, Result};
+use crate::hashes;
+use crate::job::JobHandle;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Persistent cache of the analysis results of a file.
+///
+/// The entries of a file are stored in a directory that is named after its GNU build ID,
+/// along with its size and modification time, or after the SHA-256 hash of its contents
+/// if it does not have a build ID.
+///
+/// The build ID alone is not enough since it is kept by `strip`, `objcopy` and patching.
+/// The contents of the files with a build ID are not hashed so that reopening a large file
+/// does not read it.
+#[derive(Debug)]
+pub struct Cache {
+    /// Cache directory.
+    root: PathBuf,
+    /// GNU build ID of the file.
+    build_id: Option<String>,
+    /// Modification time of the file.
+    modified: Option<SystemTime>,
+    /// Key of the file.
+    key: OnceLock<String>,
+}
+
+impl Cache {
+    /// Constructs a new instance in the default cache directory.
+    ///
+    /// Returns `None` if the cache directory cannot be determined.
+    pub fn new(build_id: Option<&str>, modified: Option<SystemTime>) -> Option<Self> {
+        Some(Self::with_root(dir()?, build_id, modified))
+    }
+
+    /// Constructs a new instance in the given cache directory.
+    pub fn with_root(root: PathBuf, build_id: Option<&str>, modified: Option<SystemTime>) -> Self {
+        Self {
+            root,
+            build_id: build_id.map(str::to_lowercase),
+            modified,
+            key: OnceLock::new(),
+        }
+    }
+
+    /// Returns the key of the file.
+    ///
+    /// The contents are only hashed once (if needed).
+    pub fn key(&self, bytes: &[u8]) -> &str {
+        self.key.get_or_init(|| {
+            let modified = self
+                .modified
+                .and_then(|v| v.duration_since(UNIX_EPOCH).ok());
+            match (&self.build_id, modified) {
+                (Some(build_id), Some(modified)) => {
+                    format!("{build_id}-{}-{}", bytes.len(), modified.as_nanos())
+                }
+                _ => format!("sha256-{}", hashes::hex(&Sha256::digest(bytes))),
+            }
+        })
+    }
+
+    /// Returns the path of the given entry.
+    ///
+    /// The entries are separated by version since their format might change.
+    fn path(&self, bytes: &[u8], name: &str) -> PathBuf {
+        self.root
+            .join(env!("CARGO_PKG_VERSION"))
+            .join(self.key(bytes))
+            .join(format!("{name}.bin"))
+    }
+
+    /// Loads the given entry of the file.
+    pub fn load<T: DeserializeOwned>(&self, bytes: &[u8], name: &str) -> Option<T> {
+        let data = fs::read(self.path(bytes, name)).ok()?;
+        bincode::deserialize(&data).ok()
+    }
+
+    /// Stores the given entry of the file.
+    pub fn store<T: Serialize>(&self, bytes: &[u8], name: &str, value: &T) -> Result<()> {
+        let path = self.path(bytes, name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let data = bincode::serialize(value).map_err(|e| Error::CacheError(e.to_string()))?;
+        // The entry is renamed after writing so that partially written entries are not loaded.
+        let temp_path = path.with_extension(format!("{}.tmp", process::id()));
+        fs::write(&temp_path, data)?;
+        fs::rename(temp_path, path)?;
+        Ok(())
+    }
+}
+
+/// Returns the cached result of the analysis or runs it and stores the result.
+pub fn cached<T, F>(
+    cache: Option<&Cache>,
+    bytes: &[u8],
+    name: &str,
+    job: &JobHandle,
+    analyze: F,
+) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Result<T>,
+{
+    let Some(cache) = cache else {
+        return analyze();
+    };
+    if let Some(value) = cache.load(bytes, name) {
+        return Ok(value);
+    }
+    let value = analyze()?;
+    // The results of the cancelled jobs might be incomplete. Also, the analysis
+    // is still usable if the cache is not writable.
+    if !job.is_cancelled() {
+        let _ = cache.store(bytes, name, &value);
+    }
+    Ok(value)
+}
+
+/// Returns the default cache directory.
+///
+/// It is `$XDG_CACHE_HOME/binsider` or `$HOME/.cache/binsider`.
+pub fn dir() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+}
+
+/// Removes the given cache directory.
+pub fn clear(root: &Path) -> Result<()> {
+    match fs::remove_dir_all(root) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cache() -> Result<()> {
+        let root = env::temp_dir().join(format!("binsider-cache-{}", process::id()));
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let cache = Cache::with_root(root.clone(), Some("15DFFF32"), Some(modified));
+        assert_eq!("15dfff32-4-1700000000000000000", cache.key(b"data"));
+        assert_eq!(None, cache.load::<Vec<String>>(b"data", "strings"));
+        cache.store(b"data", "strings", &vec![String::from("test")])?;
+        assert_eq!(
+            Some(vec![String::from("test")]),
+            cache.load(b"data", "strings")
+        );
+
+        // e.g. the stripped version of the file
+        let cache = Cache::with_root(root.clone(), Some("15DFFF32"), Some(modified));
+        assert_eq!(None, cache.load::<Vec<String>>(b"stripped", "strings"));
+
+        // e.g. the patched version of the file
+        let cache = Cache::with_root(
+            root.clone(),
+            Some("15DFFF32"),
+            Some(modified + Duration::from_secs(1)),
+        );
+        assert_eq!(None, cache.load::<Vec<String>>(b"data", "strings"));
+
+        let cache = Cache::with_root(root.clone(), None, Some(modified));
+        assert!(cache.key(b"data").starts_with("sha256-3a6eb079"));
+        assert_eq!(None, cache.load::<Vec<String>>(b"data", "strings"));
+
+        clear(&root)?;
+        assert!(!root.exists());
+        clear(&root)?;
+        Ok(())
+    }
+}
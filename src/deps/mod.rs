@@ -0,0 +1,551 @@
+/// Library resolver of the dynamic loader.
+mod resolver;
+
+use crate::elf::symbols::DynamicSymbols;
+use crate::error::Result;
+use elf::abi;
+use resolver::{Object, Resolver};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+/// Reason of skipping a searched path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// File does not exist.
+    NotFound,
+    /// File is not a valid ELF file.
+    Invalid,
+    /// File is built for another architecture.
+    Incompatible,
+}
+
+/// Entry of a library search path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchEntry {
+    /// Entry as it is specified (e.g. `$ORIGIN/../lib`).
+    pub value: String,
+    /// Expanded `$ORIGIN` (if the entry contains it).
+    pub origin: Option<PathBuf>,
+}
+
+/// Source of a searched path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchSource {
+    /// Path in the `DT_NEEDED` entry.
+    Needed,
+    /// Path in the `PT_INTERP` segment.
+    Interpreter,
+    /// `DT_RPATH` entry of a library (`None` for the file itself).
+    Rpath(Option<usize>, SearchEntry),
+    /// `LD_LIBRARY_PATH` entry.
+    LdLibraryPath(SearchEntry),
+    /// `DT_RUNPATH` entry.
+    Runpath(SearchEntry),
+    /// Entry of `/etc/ld.so.cache`.
+    Cache,
+    /// Directory in `/etc/ld.so.conf` (used if there is no cache).
+    Config,
+    /// Default directory.
+    Default,
+}
+
+impl SearchSource {
+    /// Returns the search path entry.
+    pub fn entry(&self) -> Option<&SearchEntry> {
+        match self {
+            SearchSource::Rpath(_, entry)
+            | SearchSource::LdLibraryPath(entry)
+            | SearchSource::Runpath(entry) => Some(entry),
+            _ => None,
+        }
+    }
+}
+
+/// Path that was searched for a library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchedPath {
+    /// Path of the library.
+    pub path: PathBuf,
+    /// Reason of skipping the path.
+    pub reason: SkipReason,
+    /// Source of the path.
+    pub source: SearchSource,
+}
+
+/// Symbol that is exported by a library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    /// Name of the symbol.
+    pub name: String,
+    /// Version of the symbol.
+    pub version: Option<String>,
+    /// Whether if the version is hidden (i.e. not the default version).
+    pub hidden: bool,
+}
+
+/// Provider of an imported symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Provider {
+    /// Library that exports the symbol (index in the load order).
+    Library(usize),
+    /// Program interpreter.
+    Interpreter,
+    /// Weak symbol that is not provided by any library.
+    Weak,
+    /// Symbol that is not provided by any library.
+    Missing,
+}
+
+/// Library dependency.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dependency {
+    /// Name of the library (e.g. `libc.so.6`).
+    pub name: String,
+    /// Path of the library.
+    pub path: Option<PathBuf>,
+    /// Canonical path of the library.
+    pub realpath: Option<PathBuf>,
+    /// Source of the path.
+    pub source: Option<SearchSource>,
+    /// Paths that were searched before finding the library.
+    pub searched: Vec<SearchedPath>,
+    /// Index of the library that loaded this library (`None` for the file itself).
+    pub parent: Option<usize>,
+    /// Needed libraries (`DT_NEEDED`).
+    pub needed: Vec<String>,
+    /// Library search paths (`DT_RPATH`).
+    pub rpath: Vec<String>,
+    /// Library search paths (`DT_RUNPATH`).
+    pub runpath: Vec<String>,
+    /// Exported symbols (sorted by name).
+    pub exports: Vec<Export>,
+}
+
+impl Dependency {
+    /// Returns the path to show (canonical path if possible).
+    pub fn display_path(&self) -> Option<&Path> {
+        self.realpath.as_deref().or(self.path.as_deref())
+    }
+
+    /// Returns whether if the library exports the symbol with the given version.
+    ///
+    /// Unversioned references bind to the default versions, and unversioned
+    /// definitions satisfy any reference.
+    pub fn exports(&self, name: &str, version: Option<&str>) -> bool {
+        let start = self.exports.partition_point(|v| v.name.as_str() < name);
+        self.exports[start..]
+            .iter()
+            .take_while(|export| export.name == name)
+            .any(|export| match (version, &export.version) {
+                (_, None) => true,
+                (Some(version), Some(v)) => v == version,
+                (None, Some(_)) => !export.hidden,
+            })
+    }
+}
+
+/// Kind of a row in the dependency tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowKind {
+    /// Resolved library.
+    Found,
+    /// Library that is already loaded by another library.
+    Duplicate,
+    /// Unresolved library.
+    Missing,
+    /// Path that was searched for an unresolved library.
+    Searched,
+}
+
+/// Library dependency tree.
+#[derive(Clone, Debug, Default)]
+pub struct DependencyTree {
+    /// Program interpreter (e.g. `/lib64/ld-linux-x86-64.so.2`).
+    pub interpreter: Option<Dependency>,
+    /// Needed libraries of the file (`DT_NEEDED`).
+    pub needed: Vec<String>,
+    /// Library search paths of the file (`DT_RPATH`).
+    pub rpath: Vec<String>,
+    /// Library search paths of the file (`DT_RUNPATH`).
+    pub runpath: Vec<String>,
+    /// Libraries in the load order.
+    pub libraries: Vec<Dependency>,
+}
+
+impl DependencyTree {
+    /// Resolves the dependencies of the given file.
+    ///
+    /// The libraries are searched inside the given root directory (e.g. `/`
+    /// or the root file system of an embedded device). `LD_LIBRARY_PATH` is
+    /// only taken into account for the host root.
+    pub fn new(path: &Path, bytes: &[u8], root: &Path) -> Result<Self> {
+        let object = Object::parse(bytes)?;
+        let resolver = Resolver::new(path, root, object.target);
+        let mut tree = Self {
+            interpreter: object
+                .interpreter
+                .map(|interpreter| resolver.interpreter(&interpreter)),
+            needed: object.needed,
+            rpath: object.rpath,
+            runpath: object.runpath,
+            libraries: Vec::new(),
+        };
+        // The libraries are loaded in breadth-first order and only once.
+        let mut queue = tree
+            .needed
+            .iter()
+            .map(|name| (name.to_string(), None))
+            .collect::<VecDeque<_>>();
+        while let Some((name, parent)) = queue.pop_front() {
+            if tree.libraries.iter().any(|library| library.name == name) {
+                continue;
+            }
+            let candidates = resolver.candidates(&tree, parent, &name);
+            let mut library = resolver.find(&name, candidates);
+            library.parent = parent;
+            let index = tree.libraries.len();
+            queue.extend(
+                library
+                    .needed
+                    .iter()
+                    .map(|name| (name.to_string(), Some(index))),
+            );
+            tree.libraries.push(library);
+        }
+        Ok(tree)
+    }
+
+    /// Returns the unresolved libraries.
+    pub fn missing(&self) -> impl Iterator<Item = &Dependency> {
+        self.libraries
+            .iter()
+            .filter(|library| library.path.is_none())
+    }
+
+    /// Finds the providers of the imported symbols.
+    ///
+    /// The libraries are searched in the load order (followed by the program
+    /// interpreter) and the version requirements are taken into account.
+    /// `None` is returned for the symbols that are not imported.
+    pub fn resolve(&self, symbols: &DynamicSymbols) -> Vec<Option<Provider>> {
+        symbols
+            .iter()
+            .enumerate()
+            .map(|(i, (name, symbol))| {
+                if name.is_empty() || !symbol.is_undefined() {
+                    return None;
+                }
+                let version = symbols.requirement(i).filter(|v| *v != "unknown");
+                Some(
+                    if let Some(index) = self
+                        .libraries
+                        .iter()
+                        .position(|library| library.exports(name, version))
+                    {
+                        Provider::Library(index)
+                    } else if self
+                        .interpreter
+                        .as_ref()
+                        .is_some_and(|interpreter| interpreter.exports(name, version))
+                    {
+                        Provider::Interpreter
+                    } else if symbol.st_bind() == abi::STB_WEAK {
+                        Provider::Weak
+                    } else {
+                        Provider::Missing
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the name of the provider.
+    pub fn provider_name(&self, provider: Option<Provider>) -> String {
+        match provider {
+            Some(Provider::Library(index)) => self.libraries[index].name.to_string(),
+            Some(Provider::Interpreter) => self
+                .interpreter
+                .as_ref()
+                .map(|v| v.name.to_string())
+                .unwrap_or_default(),
+            Some(Provider::Weak) => String::from("(weak)"),
+            Some(Provider::Missing) => String::from("not found"),
+            None => String::from("-"),
+        }
+    }
+
+    /// Returns the needed libraries of the file that do not provide any of the imports.
+    pub fn unused<'a>(
+        &'a self,
+        providers: &'a [Option<Provider>],
+    ) -> impl Iterator<Item = &'a Dependency> {
+        self.libraries
+            .iter()
+            .enumerate()
+            .filter(move |(index, library)| {
+                library.parent.is_none()
+                    && library.path.is_some()
+                    && !providers.contains(&Some(Provider::Library(*index)))
+            })
+            .map(|(_, library)| library)
+    }
+
+    /// Returns the dependency of a row of the tree.
+    pub fn dependency(&self, row: &[String]) -> Option<&Dependency> {
+        let name = row
+            .first()?
+            .trim_start_matches(|c: char| c.is_whitespace() || "├└│─".contains(c));
+        self.libraries
+            .iter()
+            .find(|library| library.name == name)
+            .or(self.interpreter.as_ref().filter(|v| v.name == name))
+    }
+
+    /// Returns the name of the source of a searched path.
+    pub fn source_name(&self, source: &SearchSource) -> String {
+        let object = |index: &Option<usize>| match index {
+            Some(index) => self.libraries[*index].name.to_string(),
+            None => String::from("the file"),
+        };
+        match source {
+            SearchSource::Needed => String::from("DT_NEEDED path"),
+            SearchSource::Interpreter => String::from("PT_INTERP"),
+            SearchSource::Rpath(index, _) => format!("DT_RPATH of {}", object(index)),
+            SearchSource::LdLibraryPath(_) => String::from("LD_LIBRARY_PATH"),
+            SearchSource::Runpath(_) => String::from("DT_RUNPATH"),
+            SearchSource::Cache => String::from("ld.so.cache"),
+            SearchSource::Config => String::from("ld.so.conf"),
+            SearchSource::Default => String::from("default directory"),
+        }
+    }
+
+    /// Explains how the dependency is resolved (name and value pairs).
+    pub fn explain(&self, dependency: &Dependency) -> Vec<(String, String)> {
+        let mut lines = vec![(String::from("Library"), dependency.name.to_string())];
+        let is_interpreter = self.interpreter.as_ref() == Some(dependency);
+        lines.push((
+            String::from("Loaded by"),
+            match dependency.parent {
+                _ if is_interpreter => String::from("the kernel"),
+                Some(index) => self.libraries[index].name.to_string(),
+                None => String::from("the file"),
+            },
+        ));
+        match (&dependency.path, &dependency.source) {
+            (Some(path), Some(source)) => {
+                lines.push((String::from("Path"), path.display().to_string()));
+                if let Some(realpath) = dependency.realpath.as_ref().filter(|v| *v != path) {
+                    lines.push((String::from("Real path"), realpath.display().to_string()));
+                }
+                lines.push((String::from("Found via"), self.source_name(source)));
+                if let Some(entry) = source.entry() {
+                    lines.push((String::from("Entry"), entry.value.to_string()));
+                    if let Some(origin) = &entry.origin {
+                        lines.push((String::from("$ORIGIN"), origin.display().to_string()));
+                    }
+                }
+            }
+            _ => lines.push((String::from("Path"), String::from("not found"))),
+        }
+        if !dependency.searched.is_empty() {
+            lines.push((
+                String::from("Skipped"),
+                dependency.searched.len().to_string(),
+            ));
+            lines.extend(dependency.searched.iter().map(|searched| {
+                (
+                    match searched.source.entry() {
+                        Some(entry) => {
+                            format!("- {} ({})", self.source_name(&searched.source), entry.value)
+                        }
+                        None => format!("- {}", self.source_name(&searched.source)),
+                    },
+                    match searched.reason {
+                        SkipReason::NotFound => searched.path.display().to_string(),
+                        SkipReason::Invalid => format!("{} (invalid)", searched.path.display()),
+                        SkipReason::Incompatible => {
+                            format!("{} (incompatible)", searched.path.display())
+                        }
+                    },
+                )
+            }));
+        }
+        lines
+    }
+
+    /// Returns the rows of the tree (library and path).
+    pub fn rows(&self) -> Vec<(RowKind, Vec<String>)> {
+        let mut rows = Vec::new();
+        self.push_rows(None, &self.needed, "", &mut rows);
+        if let Some(interpreter) = &self.interpreter {
+            rows.push(match interpreter.display_path() {
+                Some(path) => (
+                    RowKind::Found,
+                    vec![interpreter.name.to_string(), path.display().to_string()],
+                ),
+                None => (
+                    RowKind::Missing,
+                    vec![interpreter.name.to_string(), String::from("not found")],
+                ),
+            });
+        }
+        rows
+    }
+
+    /// Appends the rows of the needed libraries of a library.
+    fn push_rows(
+        &self,
+        parent: Option<usize>,
+        needed: &[String],
+        prefix: &str,
+        rows: &mut Vec<(RowKind, Vec<String>)>,
+    ) {
+        let branch = |last: bool| match (parent.is_some(), last) {
+            (false, _) => ("", ""),
+            (true, false) => ("├─ ", "│  "),
+            (true, true) => ("└─ ", "   "),
+        };
+        for (i, name) in needed.iter().enumerate() {
+            let Some(index) = self.libraries.iter().position(|v| v.name == *name) else {
+                continue;
+            };
+            let library = &self.libraries[index];
+            let (branch, indent) = branch(i == needed.len() - 1);
+            let label = format!("{prefix}{branch}{name}");
+            let path = library
+                .display_path()
+                .map(|v| v.display().to_string())
+                .unwrap_or_else(|| String::from("not found"));
+            if library.parent != parent {
+                rows.push((RowKind::Duplicate, vec![label, path]));
+            } else if library.path.is_none() {
+                rows.push((RowKind::Missing, vec![label, path]));
+                let prefix = format!("{prefix}{indent}");
+                for (j, searched) in library.searched.iter().enumerate() {
+                    let branch = if j == library.searched.len() - 1 {
+                        "└─ "
+                    } else {
+                        "├─ "
+                    };
+                    rows.push((
+                        RowKind::Searched,
+                        vec![
+                            format!("{prefix}{branch}✗"),
+                            match searched.reason {
+                                SkipReason::NotFound => searched.path.display().to_string(),
+                                SkipReason::Invalid => {
+                                    format!("{} (invalid)", searched.path.display())
+                                }
+                                SkipReason::Incompatible => {
+                                    format!("{} (incompatible)", searched.path.display())
+                                }
+                            },
+                        ],
+                    ));
+                }
+            } else {
+                rows.push((RowKind::Found, vec![label, path]));
+                self.push_rows(
+                    Some(index),
+                    &library.needed,
+                    &format!("{prefix}{indent}"),
+                    rows,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_exports() {
+        let export = |name: &str, version: Option<&str>, hidden| Export {
+            name: name.to_string(),
+            version: version.map(String::from),
+            hidden,
+        };
+        let library = Dependency {
+            exports: vec![
+                export("foo", None, false),
+                export("memcpy", Some("GLIBC_2.14"), false),
+                export("memcpy", Some("GLIBC_2.2.5"), true),
+            ],
+            ..Default::default()
+        };
+        assert!(library.exports("foo", None));
+        assert!(library.exports("foo", Some("V1")));
+        assert!(library.exports("memcpy", None));
+        assert!(library.exports("memcpy", Some("GLIBC_2.2.5")));
+        assert!(library.exports("memcpy", Some("GLIBC_2.14")));
+        assert!(!library.exports("memcpy", Some("GLIBC_2.3")));
+        assert!(!library.exports("bar", None));
+    }
+
+    #[test]
+    fn test_explain() {
+        let runpath = SearchSource::Runpath(SearchEntry {
+            value: String::from("$ORIGIN/../lib"),
+            origin: Some(PathBuf::from("/opt/app/bin")),
+        });
+        let tree = DependencyTree {
+            needed: vec![String::from("libfoo.so")],
+            libraries: vec![
+                Dependency {
+                    name: String::from("libfoo.so"),
+                    path: Some(PathBuf::from("/opt/app/bin/../lib/libfoo.so")),
+                    realpath: Some(PathBuf::from("/opt/app/lib/libfoo.so")),
+                    source: Some(runpath.clone()),
+                    needed: vec![String::from("libbar.so")],
+                    ..Default::default()
+                },
+                Dependency {
+                    name: String::from("libbar.so"),
+                    parent: Some(0),
+                    searched: vec![SearchedPath {
+                        path: PathBuf::from("/usr/lib/libbar.so"),
+                        reason: SkipReason::Incompatible,
+                        source: SearchSource::Cache,
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let explain = |row: &[&str]| {
+            let row = row.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            tree.explain(tree.dependency(&row).expect("dependency is not found"))
+                .into_iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![
+                "Library: libfoo.so",
+                "Loaded by: the file",
+                "Path: /opt/app/bin/../lib/libfoo.so",
+                "Real path: /opt/app/lib/libfoo.so",
+                "Found via: DT_RUNPATH",
+                "Entry: $ORIGIN/../lib",
+                "$ORIGIN: /opt/app/bin",
+            ],
+            explain(&["libfoo.so", ""])
+        );
+        assert_eq!(
+            vec![
+                "Library: libbar.so",
+                "Loaded by: libfoo.so",
+                "Path: not found",
+                "Skipped: 1",
+                "- ld.so.cache: /usr/lib/libbar.so (incompatible)",
+            ],
+            explain(&["└─ libbar.so", "not found"])
+        );
+        assert!(tree.dependency(&[String::from("└─ ✗")]).is_none());
+    }
+}
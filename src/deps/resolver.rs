@@ -0,0 +1,700 @@
+use super::{
+    Dependency, DependencyTree, Export, SearchEntry, SearchSource, SearchedPath, SkipReason,
+};
+use crate::elf::{dynamic::Dynamic, symbols::DynamicSymbols};
+use crate::error::Result;
+use crate::file::FileData;
+use elf::{abi, endian::AnyEndian, file::Class, ElfBytes};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    env,
+    ffi::OsString,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Default library directories of the dynamic loader.
+const DEFAULT_PATHS: &[&str] = &["/lib", "/lib64", "/usr/lib", "/usr/lib64"];
+
+/// Configuration file of the dynamic loader.
+const LD_SO_CONF: &str = "/etc/ld.so.conf";
+
+/// Library cache of the dynamic loader.
+const LD_SO_CACHE: &str = "/etc/ld.so.cache";
+
+/// Magic of the library cache.
+const CACHE_MAGIC: &[u8] = b"glibc-ld.so.cache1.1";
+
+/// Magic of the old library cache format.
+const OLD_CACHE_MAGIC: &[u8] = b"ld.so-1.7.0";
+
+/// Maximum number of symbolic links to follow while resolving a path.
+const MAX_SYMLINKS: usize = 40;
+
+/// Header values that need to match for loading a library.
+pub(crate) type Target = (Class, AnyEndian, u16);
+
+/// Dynamic information of an ELF object.
+pub(crate) struct Object {
+    /// Header values.
+    pub target: Target,
+    /// Program interpreter.
+    pub interpreter: Option<String>,
+    /// Needed libraries.
+    pub needed: Vec<String>,
+    /// Library search paths (`DT_RPATH`).
+    pub rpath: Vec<String>,
+    /// Library search paths (`DT_RUNPATH`).
+    pub runpath: Vec<String>,
+    /// Exported symbols.
+    pub exports: Vec<Export>,
+}
+
+impl Object {
+    /// Parses the object.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let elf = ElfBytes::<AnyEndian>::minimal_parse(bytes)?;
+        let dynamic = Dynamic::try_from(&elf).unwrap_or_default();
+        let paths = |tag| {
+            dynamic
+                .strings(tag)
+                .flat_map(|v| v.split(':'))
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        let interpreter = elf
+            .segments()
+            .and_then(|segments| segments.iter().find(|v| v.p_type == abi::PT_INTERP))
+            .and_then(|segment| elf.segment_data(&segment).ok())
+            .map(|data| {
+                String::from_utf8_lossy(data)
+                    .trim_end_matches('\0')
+                    .to_string()
+            });
+        let symbols = DynamicSymbols::try_from(&elf).unwrap_or_default();
+        let mut exports = symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, symbol))| {
+                !name.is_empty()
+                    && !symbol.is_undefined()
+                    && symbol.st_bind() != abi::STB_LOCAL
+                    && matches!(symbol.st_vis(), abi::STV_DEFAULT | abi::STV_PROTECTED)
+            })
+            .map(|(i, (name, _))| {
+                let version = symbols.version(i);
+                Export {
+                    name: name.to_string(),
+                    version: version.map(|v| v.name.to_string()),
+                    hidden: version.is_some_and(|v| v.hidden),
+                }
+            })
+            .collect::<Vec<_>>();
+        exports.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self {
+            target: (elf.ehdr.class, elf.ehdr.endianness, elf.ehdr.e_machine),
+            interpreter,
+            needed: dynamic.strings(abi::DT_NEEDED).map(String::from).collect(),
+            rpath: paths(abi::DT_RPATH),
+            runpath: paths(abi::DT_RUNPATH),
+            exports,
+        })
+    }
+}
+
+/// Library resolver that follows the search order of the dynamic loader.
+pub(crate) struct Resolver {
+    /// Root directory of the target file system.
+    root: PathBuf,
+    /// Header values of the file.
+    target: Target,
+    /// Directory of the file.
+    origin: PathBuf,
+    /// Library search paths from `LD_LIBRARY_PATH`.
+    env_paths: Vec<String>,
+    /// Library paths from `ld.so.cache`.
+    cache: Option<HashMap<String, Vec<PathBuf>>>,
+    /// Library search paths from `ld.so.conf`.
+    conf_paths: Vec<PathBuf>,
+}
+
+impl Resolver {
+    /// Constructs a new instance for the given file.
+    ///
+    /// The libraries are searched inside the given root directory and
+    /// `LD_LIBRARY_PATH` is only taken into account for the host root.
+    pub fn new(path: &Path, root: &Path, target: Target) -> Self {
+        let host = root == Path::new("/");
+        Self {
+            root: root.to_path_buf(),
+            target,
+            origin: canonicalize(root, path)
+                .or_else(|| fs::canonicalize(path).ok())
+                .and_then(|path| path.parent().map(Path::to_path_buf))
+                .unwrap_or_default(),
+            env_paths: env::var("LD_LIBRARY_PATH")
+                .ok()
+                .filter(|_| host)
+                .map(|v| v.split(':').map(String::from).collect())
+                .unwrap_or_default(),
+            cache: fs::read(rooted(root, Path::new(LD_SO_CACHE)))
+                .ok()
+                .and_then(|bytes| parse_ld_so_cache(&bytes)),
+            conf_paths: parse_ld_so_conf(root, &rooted(root, Path::new(LD_SO_CONF))),
+        }
+    }
+
+    /// Returns the program interpreter with the given path.
+    pub fn interpreter(&self, path: &str) -> Dependency {
+        let realpath = canonicalize(&self.root, &rooted(&self.root, Path::new(path)));
+        Dependency {
+            name: path.to_string(),
+            exports: realpath
+                .as_ref()
+                .and_then(|path| FileData::open(path).ok())
+                .and_then(|bytes| Object::parse(&bytes).ok())
+                .map(|object| object.exports)
+                .unwrap_or_default(),
+            source: realpath.is_some().then_some(SearchSource::Interpreter),
+            path: realpath.clone(),
+            realpath,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the library search paths of the object.
+    fn object_paths<'a>(
+        &'a self,
+        tree: &'a DependencyTree,
+        index: Option<usize>,
+    ) -> (&'a [String], &'a [String], PathBuf) {
+        match index.map(|i| &tree.libraries[i]) {
+            Some(library) => (
+                &library.rpath,
+                &library.runpath,
+                library
+                    .path
+                    .as_deref()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+            ),
+            None => (&tree.rpath, &tree.runpath, self.origin.clone()),
+        }
+    }
+
+    /// Returns the paths to search in the order of the dynamic loader.
+    ///
+    /// The `DT_RPATH` entries of the loading object and its ancestors are
+    /// used only if the loading object does not have `DT_RUNPATH`.
+    pub fn candidates(
+        &self,
+        tree: &DependencyTree,
+        parent: Option<usize>,
+        name: &str,
+    ) -> Vec<(PathBuf, SearchSource)> {
+        if name.contains('/') {
+            return vec![(rooted(&self.root, Path::new(name)), SearchSource::Needed)];
+        }
+        let mut candidates = Vec::new();
+        let (_, runpath, origin) = self.object_paths(tree, parent);
+        if runpath.is_empty() {
+            let mut index = parent;
+            loop {
+                let (rpath, _, origin) = self.object_paths(tree, index);
+                candidates.extend(rpath.iter().map(|path| {
+                    let (dir, entry) = self.expand(path, &origin);
+                    (dir.join(name), SearchSource::Rpath(index, entry))
+                }));
+                match index {
+                    Some(i) => index = tree.libraries[i].parent,
+                    None => break,
+                }
+            }
+        }
+        candidates.extend(self.env_paths.iter().map(|path| {
+            let (dir, entry) = self.expand(path, &origin);
+            (dir.join(name), SearchSource::LdLibraryPath(entry))
+        }));
+        candidates.extend(runpath.iter().map(|path| {
+            let (dir, entry) = self.expand(path, &origin);
+            (dir.join(name), SearchSource::Runpath(entry))
+        }));
+        match &self.cache {
+            Some(cache) => candidates.extend(
+                cache
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .map(|path| (rooted(&self.root, path), SearchSource::Cache)),
+            ),
+            None => candidates.extend(
+                self.conf_paths
+                    .iter()
+                    .map(|dir| (dir.join(name), SearchSource::Config)),
+            ),
+        }
+        let multiarch_paths = multiarch_triplets(self.target)
+            .iter()
+            .flat_map(|triplet| ["/lib", "/usr/lib"].map(|dir| Path::new(dir).join(triplet)));
+        candidates.extend(
+            multiarch_paths
+                .chain(DEFAULT_PATHS.iter().map(PathBuf::from))
+                .map(|dir| (rooted(&self.root, &dir).join(name), SearchSource::Default)),
+        );
+        let mut unique: Vec<(PathBuf, SearchSource)> = Vec::new();
+        for candidate in candidates {
+            if !unique.iter().any(|(path, _)| *path == candidate.0) {
+                unique.push(candidate);
+            }
+        }
+        unique
+    }
+
+    /// Expands the library search path for the target.
+    fn expand(&self, path: &str, origin: &Path) -> (PathBuf, SearchEntry) {
+        (
+            expand(path, origin, &self.root, self.target),
+            SearchEntry {
+                value: path.to_string(),
+                origin: (path.contains("$ORIGIN") || path.contains("${ORIGIN}"))
+                    .then(|| origin.to_path_buf()),
+            },
+        )
+    }
+
+    /// Finds the library in the given paths.
+    pub fn find(&self, name: &str, candidates: Vec<(PathBuf, SearchSource)>) -> Dependency {
+        let mut dependency = Dependency {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        for (path, source) in candidates {
+            let realpath = canonicalize(&self.root, &path);
+            let object = match realpath
+                .as_ref()
+                .and_then(|path| FileData::open(path).ok())
+                .map(|bytes| Object::parse(&bytes))
+            {
+                Some(Ok(object)) if object.target == self.target => Ok(object),
+                Some(Ok(_)) => Err(SkipReason::Incompatible),
+                Some(Err(_)) => Err(SkipReason::Invalid),
+                None => Err(SkipReason::NotFound),
+            };
+            match object {
+                Ok(object) => {
+                    dependency.realpath = realpath;
+                    dependency.path = Some(path);
+                    dependency.source = Some(source);
+                    dependency.needed = object.needed;
+                    dependency.rpath = object.rpath;
+                    dependency.runpath = object.runpath;
+                    dependency.exports = object.exports;
+                    break;
+                }
+                Err(reason) => dependency.searched.push(SearchedPath {
+                    path,
+                    reason,
+                    source,
+                }),
+            }
+        }
+        dependency
+    }
+}
+
+/// Returns the multiarch triplets of the target (e.g. `aarch64-linux-gnu` on Debian).
+fn multiarch_triplets((class, endian, machine): Target) -> &'static [&'static str] {
+    let little_endian = endian == AnyEndian::Little;
+    match machine {
+        abi::EM_X86_64 => &["x86_64-linux-gnu"],
+        abi::EM_386 => &["i386-linux-gnu"],
+        abi::EM_AARCH64 if little_endian => &["aarch64-linux-gnu"],
+        abi::EM_AARCH64 => &["aarch64_be-linux-gnu"],
+        abi::EM_ARM => &["arm-linux-gnueabihf", "arm-linux-gnueabi"],
+        abi::EM_RISCV if class == Class::ELF64 => &["riscv64-linux-gnu"],
+        abi::EM_PPC64 if little_endian => &["powerpc64le-linux-gnu"],
+        abi::EM_S390 => &["s390x-linux-gnu"],
+        _ => &[],
+    }
+}
+
+/// Returns the value of `$LIB` for the target.
+///
+/// It depends on the layout of the root directory: the multiarch directory
+/// (e.g. `lib/aarch64-linux-gnu`) is used if it exists, then `lib64` for the
+/// 64-bit targets (e.g. on Fedora) and `lib` otherwise.
+fn lib_dir(root: &Path, target: Target) -> String {
+    let exists = |dir: &str| {
+        ["/", "/usr"]
+            .iter()
+            .any(|prefix| rooted(root, &Path::new(prefix).join(dir)).is_dir())
+    };
+    if let Some(triplet) = multiarch_triplets(target)
+        .iter()
+        .find(|triplet| exists(&format!("lib/{triplet}")))
+    {
+        return format!("lib/{triplet}");
+    }
+    match target.0 {
+        Class::ELF64 if exists("lib64") => String::from("lib64"),
+        _ => String::from("lib"),
+    }
+}
+
+/// Returns the value of `$PLATFORM` for the target.
+fn platform((class, endian, machine): Target) -> Option<&'static str> {
+    let little_endian = endian == AnyEndian::Little;
+    match machine {
+        abi::EM_X86_64 => Some("x86_64"),
+        abi::EM_386 => Some("i686"),
+        abi::EM_AARCH64 if little_endian => Some("aarch64"),
+        abi::EM_AARCH64 => Some("aarch64_be"),
+        abi::EM_ARM if little_endian => Some("v7l"),
+        abi::EM_ARM => Some("v7b"),
+        abi::EM_RISCV if class == Class::ELF64 => Some("riscv64"),
+        abi::EM_RISCV => Some("riscv32"),
+        _ => None,
+    }
+}
+
+/// Joins the absolute path to the root directory.
+fn rooted(root: &Path, path: &Path) -> PathBuf {
+    match path.strip_prefix("/") {
+        Ok(relative) => root.join(relative),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Returns the canonical path inside the root directory.
+///
+/// Absolute symbolic links are resolved relative to the root directory, as
+/// they would be on the target system.
+fn canonicalize(root: &Path, path: &Path) -> Option<PathBuf> {
+    if root == Path::new("/") {
+        return fs::canonicalize(path).ok();
+    }
+    let mut pending = path
+        .strip_prefix(root)
+        .ok()?
+        .components()
+        .map(|v| v.as_os_str().to_os_string())
+        .collect::<VecDeque<_>>();
+    let mut resolved = root.to_path_buf();
+    let mut links = 0;
+    while let Some(name) = pending.pop_front() {
+        if name == ".." {
+            if resolved != root {
+                resolved.pop();
+            }
+            continue;
+        } else if name == "." {
+            continue;
+        }
+        let next = resolved.join(&name);
+        match fs::read_link(&next) {
+            Ok(target) => {
+                links += 1;
+                if links > MAX_SYMLINKS {
+                    return None;
+                }
+                if target.is_absolute() {
+                    resolved = root.to_path_buf();
+                }
+                for component in target.components().rev() {
+                    if let Component::Normal(_) | Component::ParentDir = component {
+                        pending.push_front(OsString::from(component.as_os_str()));
+                    }
+                }
+            }
+            Err(_) => resolved = next,
+        }
+    }
+    resolved.exists().then_some(resolved)
+}
+
+/// Expands the dynamic string tokens of a library search path.
+///
+/// `$ORIGIN` is replaced with the directory of the loading object, `$LIB` and
+/// `$PLATFORM` with the values for the target. Absolute paths that do not
+/// depend on `$ORIGIN` are joined to the root directory.
+fn expand(path: &str, origin: &Path, root: &Path, target: Target) -> PathBuf {
+    if path.is_empty() {
+        return PathBuf::from(".");
+    }
+    let mut expanded = String::new();
+    let mut has_origin = false;
+    let mut rest = path;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let token = &rest[start + 1..];
+        let (name, len) = match token.strip_prefix('{') {
+            Some(v) => match v.find('}') {
+                Some(end) => (&v[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = token
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(token.len());
+                (&token[..end], end)
+            }
+        };
+        let value = match name {
+            "ORIGIN" => {
+                has_origin = true;
+                Some(origin.to_string_lossy().to_string())
+            }
+            "LIB" => Some(lib_dir(root, target)),
+            "PLATFORM" => platform(target).map(String::from),
+            _ => None,
+        };
+        match value {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[start..start + 1 + len]),
+        }
+        rest = &token[len..];
+    }
+    expanded.push_str(rest);
+    if has_origin {
+        PathBuf::from(expanded)
+    } else {
+        rooted(root, Path::new(&expanded))
+    }
+}
+
+/// Parses the library directories from the configuration file of the dynamic loader.
+///
+/// The `include` directives are followed (wildcards are only supported in file names).
+/// Absolute paths are joined to the root directory.
+fn parse_ld_so_conf(root: &Path, path: &Path) -> Vec<PathBuf> {
+    parse_ld_so_conf_file(root, path, &mut HashSet::new())
+}
+
+/// Parses the library directories from the given configuration file.
+///
+/// The files that are already parsed are skipped so that cyclic includes terminate.
+fn parse_ld_so_conf_file(root: &Path, path: &Path, visited: &mut HashSet<PathBuf>) -> Vec<PathBuf> {
+    let Some(canonical_path) = canonicalize(root, path) else {
+        return Vec::new();
+    };
+    if !visited.insert(canonical_path) {
+        return Vec::new();
+    }
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut dirs = Vec::new();
+    for line in contents.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if let Some(pattern) = line.strip_prefix("include") {
+            let pattern = Path::new(pattern.trim());
+            let pattern = if pattern.is_absolute() {
+                rooted(root, pattern)
+            } else {
+                path.parent().unwrap_or(root).join(pattern)
+            };
+            let mut files = glob_files(&pattern);
+            files.sort();
+            for file in files {
+                dirs.extend(parse_ld_so_conf_file(root, &file, visited));
+            }
+        } else if !line.is_empty() && !line.starts_with("hwcap") {
+            dirs.push(rooted(root, Path::new(line)));
+        }
+    }
+    dirs
+}
+
+/// Parses the library paths from the cache of the dynamic loader.
+///
+/// The old format (`ld.so-1.7.0`) is skipped if the new format follows it.
+fn parse_ld_so_cache(bytes: &[u8]) -> Option<HashMap<String, Vec<PathBuf>>> {
+    let mut data = bytes;
+    if bytes.starts_with(OLD_CACHE_MAGIC) {
+        let count = u32::from_ne_bytes(bytes.get(12..16)?.try_into().ok()?) as usize;
+        data = bytes.get((16 + count * 12).next_multiple_of(8)..)?;
+    }
+    if !data.starts_with(CACHE_MAGIC) {
+        return None;
+    }
+    let big_endian = match data.get(28)? & 0b11 {
+        2 => false,
+        3 => true,
+        _ => cfg!(target_endian = "big"),
+    };
+    let read_u32 = |offset: usize| -> Option<usize> {
+        let bytes = data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        } as usize)
+    };
+    let read_str = |offset: usize| -> Option<&str> {
+        let bytes = data.get(offset..)?;
+        let end = bytes.iter().position(|v| *v == 0)?;
+        std::str::from_utf8(&bytes[..end]).ok()
+    };
+    let mut libraries = HashMap::<String, Vec<PathBuf>>::new();
+    for i in 0..read_u32(20)? {
+        let entry = 48 + i * 24;
+        libraries
+            .entry(read_str(read_u32(entry + 4)?)?.to_string())
+            .or_default()
+            .push(PathBuf::from(read_str(read_u32(entry + 8)?)?));
+    }
+    Some(libraries)
+}
+
+/// Returns the files that match the pattern (e.g. `/etc/ld.so.conf.d/*.conf`).
+fn glob_files(pattern: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (pattern.parent(), pattern.file_name()) else {
+        return Vec::new();
+    };
+    let name = name.to_string_lossy();
+    let Some((start, end)) = name.split_once('*') else {
+        return vec![pattern.to_path_buf()];
+    };
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .map(|v| v.to_string_lossy())
+                        .is_some_and(|v| {
+                            v.len() >= start.len() + end.len()
+                                && v.starts_with(start)
+                                && v.ends_with(end)
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_expand() -> Result<()> {
+        let origin = Path::new("/opt/app/bin");
+        let host = Path::new("/");
+        let x86_64 = (Class::ELF64, AnyEndian::Little, abi::EM_X86_64);
+        let aarch64 = (Class::ELF64, AnyEndian::Little, abi::EM_AARCH64);
+        let arm = (Class::ELF32, AnyEndian::Little, abi::EM_ARM);
+        assert_eq!(
+            PathBuf::from("/opt/app/bin/../lib"),
+            expand("$ORIGIN/../lib", origin, host, x86_64)
+        );
+        assert_eq!(
+            PathBuf::from("/opt/app/bin/x"),
+            expand("${ORIGIN}/x", origin, host, x86_64)
+        );
+        assert_eq!(PathBuf::from("."), expand("", origin, host, x86_64));
+        let root = env::temp_dir().join(format!("binsider-lib-{}", std::process::id()));
+        fs::create_dir_all(root.join("usr/lib64"))?;
+        assert_eq!(
+            root.join("usr/lib64/x86_64"),
+            expand("/usr/$LIB/${PLATFORM}", origin, &root, x86_64)
+        );
+        assert_eq!(
+            root.join("usr/lib64"),
+            expand("/usr/$LIB", origin, &root, aarch64)
+        );
+        fs::create_dir_all(root.join("lib/aarch64-linux-gnu"))?;
+        assert_eq!(
+            root.join("usr/lib/aarch64-linux-gnu"),
+            expand("/usr/$LIB", origin, &root, aarch64)
+        );
+        fs::remove_dir_all(&root)?;
+        assert_eq!(
+            PathBuf::from("/rootfs/usr/lib/v7l"),
+            expand("/usr/${LIB}/$PLATFORM", origin, Path::new("/rootfs"), arm)
+        );
+        assert_eq!(
+            PathBuf::from("/opt/app/bin/lib"),
+            expand("$ORIGIN/$LIB", origin, Path::new("/rootfs"), arm)
+        );
+        assert_eq!(
+            PathBuf::from("/rootfs/$LIBDIR/${FOO}/$"),
+            expand("/$LIBDIR/${FOO}/$", origin, Path::new("/rootfs"), arm)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonicalize() -> Result<()> {
+        let root = env::temp_dir().join(format!("binsider-sysroot-{}", std::process::id()));
+        fs::create_dir_all(root.join("lib"))?;
+        fs::create_dir_all(root.join("usr"))?;
+        fs::write(root.join("lib/libc.so.1"), "")?;
+        std::os::unix::fs::symlink("/lib/libc.so.1", root.join("lib/libc.so"))?;
+        std::os::unix::fs::symlink("../lib", root.join("usr/lib"))?;
+        let resolved = canonicalize(&root, &root.join("usr/lib/libc.so"));
+        let missing = canonicalize(&root, &root.join("usr/lib/libm.so"));
+        fs::remove_dir_all(&root)?;
+        assert_eq!(Some(root.join("lib/libc.so.1")), resolved);
+        assert_eq!(None, missing);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ld_so_cache() {
+        let mut bytes = CACHE_MAGIC.to_vec();
+        bytes.extend(2_u32.to_le_bytes());
+        bytes.extend(0_u32.to_le_bytes());
+        bytes.push(2);
+        bytes.resize(48, 0);
+        let strings = 48 + 2 * 24_u32;
+        for (key, value) in [(0, 10), (0, 29)] {
+            bytes.extend(0x0303_i32.to_le_bytes());
+            bytes.extend((strings + key).to_le_bytes());
+            bytes.extend((strings + value).to_le_bytes());
+            bytes.extend([0; 12]);
+        }
+        bytes.extend(b"libz.so.1\0/usr/lib/libz.so.1\0/usr/lib32/libz.so.1\0");
+        let cache = parse_ld_so_cache(&bytes).expect("failed to parse cache");
+        assert_eq!(
+            Some(&vec![
+                PathBuf::from("/usr/lib/libz.so.1"),
+                PathBuf::from("/usr/lib32/libz.so.1")
+            ]),
+            cache.get("libz.so.1")
+        );
+        assert_eq!(None, parse_ld_so_cache(b"ld.so-1.7.0"));
+    }
+
+    #[test]
+    fn test_ld_so_conf() -> Result<()> {
+        let root = env::temp_dir().join(format!("binsider-ld-so-conf-{}", std::process::id()));
+        let dir = root.join("etc");
+        fs::create_dir_all(dir.join("ld.so.conf.d"))?;
+        fs::write(
+            dir.join("ld.so.conf"),
+            "# comment\ninclude /etc/ld.so.conf.d/*.conf\n/usr/local/lib # local\n",
+        )?;
+        fs::write(dir.join("ld.so.conf.d/b.conf"), "/opt/b\n")?;
+        fs::write(dir.join("ld.so.conf.d/a.conf"), "/opt/a\n\n")?;
+        fs::write(dir.join("ld.so.conf.d/c.txt"), "/opt/c\n")?;
+        fs::write(
+            dir.join("ld.so.conf.d/d.conf"),
+            "include /etc/ld.so.conf\ninclude d.conf\n",
+        )?;
+        let dirs = parse_ld_so_conf(&root, &dir.join("ld.so.conf"));
+        fs::remove_dir_all(&root)?;
+        assert_eq!(
+            vec![
+                root.join("opt/a"),
+                root.join("opt/b"),
+                root.join("usr/local/lib")
+            ],
+            dirs
+        );
+        Ok(())
+    }
+}
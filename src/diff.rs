@@ -0,0 +1,486 @@
+use crate::{abi::AbiReport, app::Analyzer, elf::Property, error::Result, strings};
+use bytesize::ByteSize;
+use std::collections::HashMap;
+
+/// Kind of a change between two files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Only exists in the new file.
+    Added,
+    /// Only exists in the old file.
+    Removed,
+    /// Exists in both files with different values.
+    Modified,
+    /// Exists in both files with the same values.
+    Unchanged,
+}
+
+impl Change {
+    /// Returns the marker that is shown in the first column of a diff row.
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Change::Added => "+",
+            Change::Removed => "-",
+            Change::Modified => "~",
+            Change::Unchanged => " ",
+        }
+    }
+
+    /// Parses the change from the marker of a diff row.
+    pub fn from_marker(marker: &str) -> Self {
+        match marker {
+            "+" => Change::Added,
+            "-" => Change::Removed,
+            "~" => Change::Modified,
+            _ => Change::Unchanged,
+        }
+    }
+}
+
+/// Diff information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffInfo {
+    /// File headers.
+    FileHeaders,
+    /// Program headers (segments).
+    ProgramHeaders,
+    /// Section headers.
+    SectionHeaders,
+    /// Symbols.
+    Symbols,
+    /// Library dependencies.
+    Dependencies,
+    /// Notes.
+    Notes,
+    /// Strings.
+    Strings,
+    /// ABI compatibility.
+    Abi,
+}
+
+impl DiffInfo {
+    /// Returns the title.
+    pub fn title(&self) -> &str {
+        match self {
+            DiffInfo::FileHeaders => "Headers",
+            DiffInfo::ProgramHeaders => "Segments",
+            DiffInfo::SectionHeaders => "Sections",
+            DiffInfo::Symbols => "Symbols",
+            DiffInfo::Dependencies => "Dependencies",
+            DiffInfo::Notes => "Notes",
+            DiffInfo::Strings => "Strings",
+            DiffInfo::Abi => "ABI",
+        }
+    }
+
+    /// Returns the headers.
+    pub fn headers(&self) -> &[&str] {
+        match self {
+            DiffInfo::FileHeaders => &["", "Field", "Value"],
+            DiffInfo::ProgramHeaders => &["", "Type", "Flags", "FileSiz", "MemSiz", "Delta"],
+            DiffInfo::SectionHeaders => &["", "Name", "Type", "Size", "Delta"],
+            DiffInfo::Symbols => &["", "Name", "Type", "Bind", "Size", "Delta"],
+            DiffInfo::Dependencies => &["", "Library", "Path"],
+            DiffInfo::Notes => &["", "Note", "Value"],
+            DiffInfo::Strings => &["", "String"],
+            DiffInfo::Abi => &["", "Verdict", "Change", "Symbol", "Details"],
+        }
+    }
+}
+
+/// Comparable entry of a diff category.
+#[derive(Clone, Debug, Default)]
+struct Entry {
+    /// Name that is used for matching the entries.
+    name: String,
+    /// Values that are compared.
+    values: Vec<String>,
+    /// Size in bytes (if applicable).
+    size: Option<u64>,
+}
+
+/// Differences between two ELF files.
+#[derive(Clone, Debug, Default)]
+pub struct Diff {
+    /// Path of the old file.
+    pub old: String,
+    /// Path of the new file.
+    pub new: String,
+    /// File header rows.
+    file_headers: Vec<Vec<String>>,
+    /// Program header rows.
+    program_headers: Vec<Vec<String>>,
+    /// Section header rows.
+    section_headers: Vec<Vec<String>>,
+    /// Symbol rows.
+    symbols: Vec<Vec<String>>,
+    /// Dependency rows.
+    dependencies: Vec<Vec<String>>,
+    /// Note rows.
+    notes: Vec<Vec<String>>,
+    /// String rows.
+    strings: Vec<Vec<String>>,
+    /// ABI compatibility report.
+    pub abi: AbiReport,
+    /// ABI compatibility rows.
+    abi_rows: Vec<Vec<String>>,
+}
+
+impl Diff {
+    /// Compares the given files.
+    ///
+    /// Strings are extracted with the given minimum length.
+    pub fn new(old: &Analyzer, new: &Analyzer, strings_len: usize) -> Result<Self> {
+        let abi = AbiReport::new(&old.elf, &new.elf);
+        Ok(Self {
+            old: old.file.path.to_string(),
+            new: new.file.path.to_string(),
+            file_headers: diff_entries(&file_headers(old), &file_headers(new), false),
+            program_headers: diff_entries(&program_headers(old), &program_headers(new), false),
+            section_headers: diff_entries(&section_headers(old), &section_headers(new), false),
+            symbols: diff_entries(&symbols(old), &symbols(new), true),
+            dependencies: diff_entries(&dependencies(old), &dependencies(new), false),
+            notes: diff_entries(&notes(old), &notes(new), false),
+            strings: diff_entries(
+                &strings(old, strings_len)?,
+                &strings(new, strings_len)?,
+                true,
+            ),
+            abi_rows: abi.rows(),
+            abi,
+        })
+    }
+
+    /// Returns the rows of the given category.
+    ///
+    /// The first column of each row is the [`Change`] marker.
+    pub fn rows(&self, info: &DiffInfo) -> &[Vec<String>] {
+        match info {
+            DiffInfo::FileHeaders => &self.file_headers,
+            DiffInfo::ProgramHeaders => &self.program_headers,
+            DiffInfo::SectionHeaders => &self.section_headers,
+            DiffInfo::Symbols => &self.symbols,
+            DiffInfo::Dependencies => &self.dependencies,
+            DiffInfo::Notes => &self.notes,
+            DiffInfo::Strings => &self.strings,
+            DiffInfo::Abi => &self.abi_rows,
+        }
+    }
+
+    /// Returns the number of added, removed and modified rows of the given category.
+    pub fn summary(&self, info: &DiffInfo) -> (usize, usize, usize) {
+        self.rows(info)
+            .iter()
+            .fold(
+                (0, 0, 0),
+                |(added, removed, modified), row| match Change::from_marker(&row[0]) {
+                    Change::Added => (added + 1, removed, modified),
+                    Change::Removed => (added, removed + 1, modified),
+                    Change::Modified => (added, removed, modified + 1),
+                    Change::Unchanged => (added, removed, modified),
+                },
+            )
+    }
+}
+
+/// Returns the file header entries.
+fn file_headers(analyzer: &Analyzer) -> Vec<Entry> {
+    analyzer
+        .elf
+        .file_headers
+        .items()
+        .into_iter()
+        .map(|item| Entry {
+            name: item[0].to_string(),
+            values: vec![item[1].to_string()],
+            size: None,
+        })
+        .collect()
+}
+
+/// Returns the program header entries.
+///
+/// Addresses are not compared since they shift with every change in the layout.
+fn program_headers(analyzer: &Analyzer) -> Vec<Entry> {
+    analyzer
+        .elf
+        .program_headers
+        .inner()
+        .iter()
+        .map(|header| Entry {
+            name: elf::to_str::p_type_to_string(header.p_type)
+                .trim_start_matches("PT_")
+                .to_string(),
+            values: vec![
+                elf::to_str::p_flags_to_string(header.p_flags),
+                ByteSize(header.p_filesz).to_string(),
+                ByteSize(header.p_memsz).to_string(),
+            ],
+            size: Some(header.p_filesz),
+        })
+        .collect()
+}
+
+/// Returns the section header entries.
+fn section_headers(analyzer: &Analyzer) -> Vec<Entry> {
+    analyzer
+        .elf
+        .section_headers
+        .iter()
+        .map(|(name, header)| Entry {
+            name: name.to_string(),
+            values: vec![
+                elf::to_str::sh_type_to_string(header.sh_type)
+                    .trim_start_matches("SHT_")
+                    .to_string(),
+                ByteSize(header.sh_size).to_string(),
+            ],
+            size: Some(header.sh_size),
+        })
+        .collect()
+}
+
+/// Returns the symbol entries.
+///
+/// Dynamic symbols are used if the symbol table is stripped.
+fn symbols(analyzer: &Analyzer) -> Vec<Entry> {
+    let symbols = analyzer.elf.symbols.iter().collect::<Vec<_>>();
+    let symbols = if symbols.is_empty() {
+        analyzer.elf.dynamic_symbols.iter().collect()
+    } else {
+        symbols
+    };
+    symbols
+        .into_iter()
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, symbol)| Entry {
+            name: name.to_string(),
+            values: vec![
+                elf::to_str::st_symtype_to_string(symbol.st_symtype())
+                    .trim_start_matches("STT_")
+                    .to_string(),
+                elf::to_str::st_bind_to_string(symbol.st_bind())
+                    .trim_start_matches("STB_")
+                    .to_string(),
+                symbol.st_size.to_string(),
+            ],
+            size: Some(symbol.st_size),
+        })
+        .collect()
+}
+
+/// Returns the dependency entries.
+fn dependencies(analyzer: &Analyzer) -> Vec<Entry> {
+    analyzer
+        .dependencies()
+        .libraries
+        .iter()
+        .map(|library| Entry {
+            name: library.name.to_string(),
+            values: vec![library
+                .display_path()
+                .map(|v| v.display().to_string())
+                .unwrap_or_else(|| String::from("not found"))],
+            size: None,
+        })
+        .collect()
+}
+
+/// Returns the note entries.
+fn notes(analyzer: &Analyzer) -> Vec<Entry> {
+    analyzer
+        .elf
+        .notes
+        .inner
+        .iter()
+        .flat_map(|note| {
+            note.header
+                .iter()
+                .zip(note.text.iter())
+                .map(|(header, text)| Entry {
+                    name: format!("{} ({})", header, note.name),
+                    values: vec![text.to_string()],
+                    size: None,
+                })
+        })
+        .collect()
+}
+
+/// Returns the string entries.
+fn strings(analyzer: &Analyzer, strings_len: usize) -> Result<Vec<Entry>> {
+    Ok(strings::rust_encoding_strings(
+        &analyzer.file.bytes,
+        strings_len,
+        rust_strings::Encoding::ASCII,
+        &|_| true,
+    )?
+    .into_iter()
+    .map(|(value, _)| Entry {
+        name: value,
+        ..Default::default()
+    })
+    .collect())
+}
+
+/// Returns the signed size difference in a human readable format.
+fn size_delta(old: u64, new: u64) -> String {
+    if new >= old {
+        format!("+{}", ByteSize(new - old))
+    } else {
+        format!("-{}", ByteSize(old - new))
+    }
+}
+
+/// Returns the keys for matching the entries.
+///
+/// Entries with the same name are matched in the order of their occurrence.
+fn entry_keys(entries: &[Entry]) -> Vec<(&str, usize)> {
+    let mut occurrences = HashMap::new();
+    entries
+        .iter()
+        .map(|entry| {
+            let count = occurrences.entry(entry.name.as_str()).or_insert(0);
+            *count += 1;
+            (entry.name.as_str(), *count)
+        })
+        .collect()
+}
+
+/// Compares the entries and returns the diff rows.
+///
+/// The rows follow the order of the new entries and the removed entries are
+/// placed where they were in the old entries. If `changes_only` is set, the
+/// unchanged rows are omitted.
+fn diff_entries(old: &[Entry], new: &[Entry], changes_only: bool) -> Vec<Vec<String>> {
+    let old_keys = entry_keys(old);
+    let new_keys = entry_keys(new);
+    let old_index = old_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (*key, i))
+        .collect::<HashMap<_, _>>();
+    let new_index = new_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (*key, i))
+        .collect::<HashMap<_, _>>();
+    let sized = old.iter().chain(new.iter()).any(|v| v.size.is_some());
+    let row = |change: Change, entry: &Entry, values: Vec<String>, delta: String| {
+        let mut row = vec![change.marker().to_string(), entry.name.to_string()];
+        row.extend(values);
+        if sized {
+            row.push(delta);
+        }
+        row
+    };
+    let removed_row = |entry: &Entry| {
+        row(
+            Change::Removed,
+            entry,
+            entry.values.clone(),
+            size_delta(entry.size.unwrap_or_default(), 0),
+        )
+    };
+
+    let mut rows = Vec::new();
+    let mut old_position = 0;
+    for (new_entry, key) in new.iter().zip(new_keys.iter()) {
+        let Some(&old_position_of_key) = old_index.get(key) else {
+            rows.push(row(
+                Change::Added,
+                new_entry,
+                new_entry.values.clone(),
+                size_delta(0, new_entry.size.unwrap_or_default()),
+            ));
+            continue;
+        };
+        while old_position < old_position_of_key {
+            if !new_index.contains_key(&old_keys[old_position]) {
+                rows.push(removed_row(&old[old_position]));
+            }
+            old_position += 1;
+        }
+        old_position = old_position.max(old_position_of_key + 1);
+        let old_entry = &old[old_position_of_key];
+        if old_entry.values == new_entry.values && old_entry.size == new_entry.size {
+            if !changes_only {
+                rows.push(row(
+                    Change::Unchanged,
+                    new_entry,
+                    new_entry.values.clone(),
+                    String::new(),
+                ));
+            }
+            continue;
+        }
+        rows.push(row(
+            Change::Modified,
+            new_entry,
+            old_entry
+                .values
+                .iter()
+                .zip(new_entry.values.iter())
+                .map(|(old_value, new_value)| {
+                    if old_value == new_value {
+                        new_value.to_string()
+                    } else {
+                        format!("{old_value} → {new_value}")
+                    }
+                })
+                .collect(),
+            size_delta(
+                old_entry.size.unwrap_or_default(),
+                new_entry.size.unwrap_or_default(),
+            ),
+        ));
+    }
+    for (old_entry, key) in old.iter().zip(old_keys.iter()).skip(old_position) {
+        if !new_index.contains_key(key) {
+            rows.push(removed_row(old_entry));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(name: &str, size: u64) -> Entry {
+        Entry {
+            name: name.to_string(),
+            values: vec![size.to_string()],
+            size: Some(size),
+        }
+    }
+
+    #[test]
+    fn test_diff_entries() {
+        let old = vec![entry(".text", 10), entry(".data", 5), entry(".bss", 1)];
+        let new = vec![entry(".text", 12), entry(".rodata", 3), entry(".bss", 1)];
+        assert_eq!(
+            vec![
+                vec!["~", ".text", "10 → 12", "+2 B"],
+                vec!["+", ".rodata", "3", "+3 B"],
+                vec!["-", ".data", "5", "-5 B"],
+                vec![" ", ".bss", "1", ""],
+            ],
+            diff_entries(&old, &new, false)
+        );
+        assert_eq!(
+            vec![vec!["~", ".text", "10 → 12", "+2 B"]],
+            diff_entries(&old[..1], &new[..1], true)
+        );
+    }
+
+    #[test]
+    fn test_diff_duplicate_entries() {
+        let old = vec![entry("a", 1), entry("a", 1), entry("a", 1)];
+        let new = vec![entry("a", 1), entry("a", 1)];
+        assert_eq!(
+            vec![vec!["-", "a", "1", "-1 B"]],
+            diff_entries(&old, &new, true)
+        );
+    }
+}
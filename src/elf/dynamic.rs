@@ -1,5 +1,5 @@
 use crate::elf::Property;
-use elf::{dynamic::Dyn, endian::AnyEndian, parse::ParsingTable, ParseError};
+use elf::{dynamic::Dyn, endian::AnyEndian, ElfBytes, ParseError};
 use std::io::Error as IoError;
 
 /// ELF dynamic section wrapper.
@@ -7,19 +7,65 @@ use std::io::Error as IoError;
 pub struct Dynamic {
     /// Dynamics.
     dynamics: Vec<Dyn>,
+    /// Values of the string entries (e.g. `DT_NEEDED`).
+    strings: Vec<Option<String>>,
 }
 
-impl<'a> TryFrom<Option<ParsingTable<'a, AnyEndian, Dyn>>> for Dynamic {
+impl<'a> TryFrom<&'a ElfBytes<'a, AnyEndian>> for Dynamic {
     type Error = ParseError;
-    fn try_from(value: Option<ParsingTable<'a, AnyEndian, Dyn>>) -> Result<Self, Self::Error> {
-        let parsing_table = value
+    fn try_from(elf: &'a ElfBytes<'a, AnyEndian>) -> Result<Self, Self::Error> {
+        let parsing_table = elf
+            .dynamic()?
             .ok_or_else(|| ParseError::IOError(IoError::other("parsing table does not exist")))?;
+        let string_table = elf
+            .dynamic_symbol_table()
+            .ok()
+            .flatten()
+            .map(|(_, string_table)| string_table);
         Ok(Self {
             dynamics: parsing_table.iter().collect(),
+            strings: parsing_table
+                .iter()
+                .map(|dynamic| match dynamic.d_tag {
+                    elf::abi::DT_NEEDED
+                    | elf::abi::DT_SONAME
+                    | elf::abi::DT_RPATH
+                    | elf::abi::DT_RUNPATH => string_table
+                        .as_ref()?
+                        .get(dynamic.d_val() as usize)
+                        .ok()
+                        .map(|v| v.to_string()),
+                    _ => None,
+                })
+                .collect(),
         })
     }
 }
 
+impl Dynamic {
+    /// Returns the string values of the entries with the given tag.
+    pub fn strings(&self, tag: i64) -> impl Iterator<Item = &str> {
+        self.dynamics
+            .iter()
+            .zip(self.strings.iter())
+            .filter(move |(dynamic, _)| dynamic.d_tag == tag)
+            .filter_map(|(_, value)| value.as_deref())
+    }
+
+    /// Returns the value of the first entry with the given tag.
+    pub fn value(&self, tag: i64) -> Option<u64> {
+        self.dynamics
+            .iter()
+            .find(|dynamic| dynamic.d_tag == tag)
+            .map(|dynamic| dynamic.d_val())
+    }
+
+    /// Returns the shared object name (`DT_SONAME`).
+    pub fn soname(&self) -> Option<&str> {
+        self.strings(elf::abi::DT_SONAME).next()
+    }
+}
+
 impl Property<'_> for Dynamic {
     fn items(&self) -> Vec<Vec<String>> {
         self.dynamics
@@ -34,4 +80,11 @@ impl Property<'_> for Dynamic {
             })
             .collect()
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        self.dynamics
+            .iter()
+            .map(|dynamic| vec![None, Some(dynamic.clone().d_val().into())])
+            .collect()
+    }
 }
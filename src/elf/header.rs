@@ -15,6 +15,13 @@ pub struct FileHeaders {
     inner: ElfFileHeader<AnyEndian>,
 }
 
+impl FileHeaders {
+    /// Returns the inner type.
+    pub fn inner(&self) -> &ElfFileHeader<AnyEndian> {
+        &self.inner
+    }
+}
+
 impl From<ElfFileHeader<AnyEndian>> for FileHeaders {
     fn from(inner: ElfFileHeader<AnyEndian>) -> Self {
         Self { inner }
@@ -105,6 +112,11 @@ impl Property<'_> for FileHeaders {
         ]);
         headers
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        // The file headers are not shown in a table.
+        Vec::new()
+    }
 }
 
 /// ELF program header wrapper.
@@ -121,6 +133,37 @@ impl ProgramHeaders {
     pub fn toggle_readability(&mut self) {
         self.human_readable = !self.human_readable;
     }
+
+    /// Returns the program headers.
+    pub fn inner(&self) -> &[ProgramHeader] {
+        &self.inner
+    }
+
+    /// Returns the virtual address of the given file offset.
+    ///
+    /// Only the loadable segments are taken into account.
+    pub fn virtual_address(&self, offset: u64) -> Option<u64> {
+        self.inner
+            .iter()
+            .find(|segment| {
+                segment.p_type == elf::abi::PT_LOAD
+                    && range_contains(segment.p_offset, segment.p_filesz, offset)
+            })
+            .and_then(|segment| segment.p_vaddr.checked_add(offset - segment.p_offset))
+    }
+
+    /// Returns the file offset of the given virtual address.
+    ///
+    /// Only the loadable segments are taken into account.
+    pub fn file_offset(&self, address: u64) -> Option<u64> {
+        self.inner
+            .iter()
+            .find(|segment| {
+                segment.p_type == elf::abi::PT_LOAD
+                    && range_contains(segment.p_vaddr, segment.p_filesz, address)
+            })
+            .and_then(|segment| segment.p_offset.checked_add(address - segment.p_vaddr))
+    }
 }
 
 impl From<Vec<ProgramHeader>> for ProgramHeaders {
@@ -160,6 +203,24 @@ impl Property<'_> for ProgramHeaders {
             })
             .collect()
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        self.inner
+            .iter()
+            .map(|item| {
+                vec![
+                    None,
+                    Some(item.p_offset.into()),
+                    Some(item.p_vaddr.into()),
+                    Some(item.p_paddr.into()),
+                    Some(item.p_filesz.into()),
+                    Some(item.p_memsz.into()),
+                    None,
+                    Some(item.p_align.into()),
+                ]
+            })
+            .collect()
+    }
 }
 
 /// ELF file section header wrapper.
@@ -178,6 +239,45 @@ impl SectionHeaders {
     pub fn toggle_readability(&mut self) {
         self.human_readable = !self.human_readable;
     }
+
+    /// Returns an iterator over the section names and headers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SectionHeader)> {
+        self.names.iter().map(String::as_str).zip(self.inner.iter())
+    }
+
+    /// Returns the file offset of the given virtual address.
+    ///
+    /// Only the allocated sections with file contents are taken into account.
+    pub fn file_offset(&self, address: u64) -> Option<u64> {
+        self.iter()
+            .find(|(_, header)| {
+                header.sh_type != elf::abi::SHT_NOBITS
+                    && header.sh_flags & u64::from(elf::abi::SHF_ALLOC) != 0
+                    && header.sh_addr != 0
+                    && range_contains(header.sh_addr, header.sh_size, address)
+            })
+            .and_then(|(_, header)| header.sh_offset.checked_add(address - header.sh_addr))
+    }
+
+    /// Returns the virtual address of the given file offset.
+    ///
+    /// Only the allocated sections are taken into account.
+    pub fn virtual_address(&self, offset: u64) -> Option<u64> {
+        self.section_at(offset)
+            .filter(|(_, header)| {
+                header.sh_flags & u64::from(elf::abi::SHF_ALLOC) != 0 && header.sh_addr != 0
+            })
+            .and_then(|(_, header)| header.sh_addr.checked_add(offset - header.sh_offset))
+    }
+
+    /// Returns the section that contains the given file offset.
+    pub fn section_at(&self, offset: u64) -> Option<(&str, &SectionHeader)> {
+        self.iter().find(|(_, header)| {
+            header.sh_type != elf::abi::SHT_NULL
+                && header.sh_type != elf::abi::SHT_NOBITS
+                && range_contains(header.sh_offset, header.sh_size, offset)
+        })
+    }
 }
 
 impl<'a>
@@ -244,4 +344,97 @@ impl Property<'_> for SectionHeaders {
             })
             .collect()
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        self.inner
+            .iter()
+            .map(|header| {
+                vec![
+                    None,
+                    None,
+                    Some(header.sh_addr.into()),
+                    Some(header.sh_offset.into()),
+                    Some(header.sh_size.into()),
+                    Some(header.sh_entsize.into()),
+                    Some(header.sh_flags.into()),
+                    Some(header.sh_link.into()),
+                    Some(header.sh_info.into()),
+                    Some(header.sh_addralign.into()),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Returns `true` if the range with the given start and size contains the value.
+///
+/// Ranges that overflow (e.g. in crafted headers) do not contain anything.
+fn range_contains(start: u64, size: u64, value: u64) -> bool {
+    start
+        .checked_add(size)
+        .is_some_and(|end| (start..end).contains(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_crafted_headers() {
+        let segment = ProgramHeader {
+            p_type: elf::abi::PT_LOAD,
+            p_offset: u64::MAX - 1,
+            p_vaddr: u64::MAX - 1,
+            p_paddr: 0,
+            p_filesz: 0x10,
+            p_memsz: 0x10,
+            p_flags: 0,
+            p_align: 0,
+        };
+        let program_headers = ProgramHeaders::from(vec![
+            segment,
+            ProgramHeader {
+                p_offset: 0x1000,
+                p_vaddr: u64::MAX - 0x10,
+                ..segment
+            },
+        ]);
+        assert_eq!(None, program_headers.virtual_address(u64::MAX - 1));
+        assert_eq!(None, program_headers.file_offset(u64::MAX));
+        assert_eq!(Some(0x100f), program_headers.file_offset(u64::MAX - 1));
+        assert_eq!(
+            Some(u64::MAX - 0x10),
+            program_headers.virtual_address(0x1000)
+        );
+
+        let section = SectionHeader {
+            sh_name: 0,
+            sh_type: elf::abi::SHT_PROGBITS,
+            sh_flags: u64::from(elf::abi::SHF_ALLOC),
+            sh_addr: u64::MAX - 1,
+            sh_offset: u64::MAX - 1,
+            sh_size: 0x10,
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 0,
+            sh_entsize: 0,
+        };
+        let section_headers = SectionHeaders {
+            inner: vec![
+                section,
+                SectionHeader {
+                    sh_addr: u64::MAX,
+                    sh_offset: 0x1000,
+                    ..section
+                },
+            ],
+            names: vec![String::from(".crafted"), String::from(".text")],
+            human_readable: false,
+        };
+        assert_eq!(None, section_headers.file_offset(u64::MAX - 1));
+        assert_eq!(None, section_headers.section_at(u64::MAX - 1));
+        assert_eq!(Some(u64::MAX), section_headers.virtual_address(0x1000));
+        assert_eq!(None, section_headers.virtual_address(0x1001));
+    }
 }
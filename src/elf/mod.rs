@@ -20,10 +20,15 @@ use symbols::{DynamicSymbols, Symbols};
 pub trait Property<'a> {
     /// Returns the items.
     fn items(&self) -> Vec<Vec<String>>;
+
+    /// Returns the numeric values of the items (e.g. addresses and sizes) for sorting.
+    ///
+    /// The values are in the same order as the items, with `None` for the text.
+    fn values(&self) -> Vec<Vec<Option<i128>>>;
 }
 
 /// ELF information.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Info {
     /// File headers.
     FileHeaders,
@@ -70,7 +75,9 @@ impl Info {
                 "Align",
             ],
             Info::Symbols => &["Name", "Type", "Value", "Siz", "Bind", "Vis", "Ndx"],
-            Info::DynamicSymbols => &["Name", "Reqs", "Type", "Value", "Siz", "Bind", "Vis", "Ndx"],
+            Info::DynamicSymbols => &[
+                "Name", "Reqs", "Type", "Value", "Siz", "Bind", "Vis", "Ndx", "Provider",
+            ],
             Info::Dynamics => &["Tag", "Value"],
             Info::Relocations => &["Type", "Symbol", "Offset", "Addend"],
             Info::Notes => todo!(),
@@ -111,12 +118,8 @@ impl<'a> TryFrom<ElfBytes<'a, AnyEndian>> for Elf {
             section_headers: SectionHeaders::try_from(elf_bytes.section_headers_with_strtab()?)
                 .unwrap_or_default(),
             symbols: Symbols::try_from(elf_bytes.symbol_table()?).unwrap_or_default(),
-            dynamic_symbols: DynamicSymbols::try_from((
-                elf_bytes.dynamic_symbol_table()?,
-                elf_bytes.symbol_version_table()?,
-            ))
-            .unwrap_or_default(),
-            dynamic: Dynamic::try_from(elf_bytes.dynamic()?).unwrap_or_default(),
+            dynamic_symbols: DynamicSymbols::try_from(&elf_bytes).unwrap_or_default(),
+            dynamic: Dynamic::try_from(&elf_bytes).unwrap_or_default(),
             relocations: Relocations::try_from(&elf_bytes).unwrap_or_default(),
             notes: Notes::try_from(&elf_bytes).unwrap_or_default(),
         })
@@ -124,6 +127,24 @@ impl<'a> TryFrom<ElfBytes<'a, AnyEndian>> for Elf {
 }
 
 impl Elf {
+    /// Returns the file offset of the given virtual address.
+    ///
+    /// The loadable segments are used if they exist, otherwise the sections are used.
+    pub fn file_offset(&self, address: u64) -> Option<u64> {
+        self.program_headers
+            .file_offset(address)
+            .or_else(|| self.section_headers.file_offset(address))
+    }
+
+    /// Returns the virtual address of the given file offset.
+    ///
+    /// The loadable segments are used if they exist, otherwise the sections are used.
+    pub fn virtual_address(&self, offset: u64) -> Option<u64> {
+        self.program_headers
+            .virtual_address(offset)
+            .or_else(|| self.section_headers.virtual_address(offset))
+    }
+
     /// Returns the information about the ELF file.
     pub fn info<'a>(&self, info: &Info) -> Box<dyn Property<'a>> {
         match info {
@@ -90,3 +90,13 @@ impl<'a> TryFrom<&'a ElfBytes<'a, AnyEndian>> for Notes {
         Ok(Self { inner: notes })
     }
 }
+
+impl Notes {
+    /// Returns the GNU build ID (in hexadecimal).
+    pub fn build_id(&self) -> Option<&str> {
+        self.inner.iter().find_map(|note| {
+            let index = note.header.iter().position(|v| v == "Build ID")?;
+            note.text.get(index).map(String::as_str)
+        })
+    }
+}
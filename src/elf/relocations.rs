@@ -44,6 +44,18 @@ impl<'a> TryFrom<&'a ElfBytes<'a, AnyEndian>> for Relocations {
     }
 }
 
+impl Relocations {
+    /// Returns the relocations without addend.
+    pub fn rels(&self) -> &[Rel] {
+        &self.rels
+    }
+
+    /// Returns the relocations with addend.
+    pub fn relas(&self) -> &[Rela] {
+        &self.relas
+    }
+}
+
 impl Property<'_> for Relocations {
     fn items(&self) -> Vec<Vec<String>> {
         let mut relocations = Vec::new();
@@ -65,4 +77,26 @@ impl Property<'_> for Relocations {
         });
         relocations
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        self.rels
+            .iter()
+            .map(|v| {
+                vec![
+                    Some(v.r_type.into()),
+                    Some(v.r_sym.into()),
+                    Some(v.r_offset.into()),
+                    None,
+                ]
+            })
+            .chain(self.relas.iter().map(|v| {
+                vec![
+                    Some(v.r_type.into()),
+                    Some(v.r_sym.into()),
+                    Some(v.r_offset.into()),
+                    Some(v.r_addend.into()),
+                ]
+            }))
+            .collect()
+    }
 }
@@ -1,7 +1,7 @@
 use crate::elf::Property;
 use elf::{
-    endian::AnyEndian, gnu_symver::SymbolVersionTable, parse::ParsingTable,
-    string_table::StringTable, symbol::Symbol, ParseError,
+    endian::AnyEndian, gnu_symver::VerDefIterator, parse::ParsingTable, string_table::StringTable,
+    symbol::Symbol, ElfBytes, ParseError,
 };
 use std::io::Error as IoError;
 
@@ -36,6 +36,16 @@ impl<'a> TryFrom<Option<(ParsingTable<'a, AnyEndian, Symbol>, StringTable<'a>)>>
     }
 }
 
+impl Symbols {
+    /// Returns an iterator over the symbol names and symbols.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Symbol)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.symbols.iter())
+    }
+}
+
 impl Property<'_> for Symbols {
     fn items(&self) -> Vec<Vec<String>> {
         self.symbols
@@ -61,6 +71,23 @@ impl Property<'_> for Symbols {
             })
             .collect()
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        self.symbols
+            .iter()
+            .map(|symbol| {
+                vec![
+                    None,
+                    None,
+                    Some(symbol.st_value.into()),
+                    Some(symbol.st_size.into()),
+                    None,
+                    None,
+                    Some(symbol.st_shndx.into()),
+                ]
+            })
+            .collect()
+    }
 }
 
 /// ELF dynamic symbols wrapper.
@@ -72,27 +99,28 @@ pub struct DynamicSymbols {
     names: Vec<String>,
     /// Requirements.
     requirements: Vec<String>,
+    /// Versions of the defined symbols.
+    versions: Vec<Option<SymbolVersion>>,
+    /// Version definitions (except the base version).
+    definitions: Vec<String>,
 }
 
-impl<'a>
-    TryFrom<(
-        Option<(ParsingTable<'a, AnyEndian, Symbol>, StringTable<'a>)>,
-        Option<SymbolVersionTable<'a, AnyEndian>>,
-    )> for DynamicSymbols
-{
+/// Version of a defined dynamic symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolVersion {
+    /// Name of the version node.
+    pub name: String,
+    /// Whether if the version is hidden (i.e. not the default version).
+    pub hidden: bool,
+}
+
+impl<'a> TryFrom<&'a ElfBytes<'a, AnyEndian>> for DynamicSymbols {
     type Error = ParseError;
-    fn try_from(
-        value: (
-            Option<(ParsingTable<'a, AnyEndian, Symbol>, StringTable<'a>)>,
-            Option<SymbolVersionTable<'a, AnyEndian>>,
-        ),
-    ) -> Result<Self, Self::Error> {
-        let (parsing_table, string_table) = value
-            .0
+    fn try_from(elf: &'a ElfBytes<'a, AnyEndian>) -> Result<Self, Self::Error> {
+        let (parsing_table, string_table) = elf
+            .dynamic_symbol_table()?
             .ok_or_else(|| ParseError::IOError(IoError::other("symbol table does not exist")))?;
-        let version_table = value.1.ok_or_else(|| {
-            ParseError::IOError(IoError::other("symbol version table does not exist"))
-        })?;
+        let version_table = elf.symbol_version_table()?;
         Ok(Self {
             symbols: parsing_table.iter().collect(),
             names: parsing_table
@@ -108,23 +136,100 @@ impl<'a>
                 .iter()
                 .enumerate()
                 .map(|(i, v)| {
-                    if v.is_undefined() {
-                        version_table
+                    match &version_table {
+                        Some(version_table) if v.is_undefined() => version_table
                             .get_requirement(i)
                             .ok()
                             .flatten()
                             .map(|v| v.name)
-                            .unwrap_or_else(|| "unknown")
-                    } else {
-                        "-"
+                            .unwrap_or_else(|| "unknown"),
+                        _ => "-",
                     }
                     .to_string()
                 })
                 .collect(),
+            versions: parsing_table
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let version_table = version_table.as_ref()?;
+                    if v.is_undefined() {
+                        return None;
+                    }
+                    let mut definition = version_table.get_definition(i).ok().flatten()?;
+                    Some(SymbolVersion {
+                        name: definition.names.next()?.ok()?.to_string(),
+                        hidden: definition.hidden,
+                    })
+                })
+                .collect(),
+            definitions: Self::parse_definitions(elf).unwrap_or_default(),
         })
     }
 }
 
+impl DynamicSymbols {
+    /// Returns an iterator over the symbol names and symbols.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Symbol)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.symbols.iter())
+    }
+
+    /// Returns the version requirement of the undefined symbol at the given index.
+    pub fn requirement(&self, index: usize) -> Option<&str> {
+        self.requirements
+            .get(index)
+            .map(String::as_str)
+            .filter(|v| *v != "-")
+    }
+
+    /// Returns the version of the defined symbol at the given index.
+    pub fn version(&self, index: usize) -> Option<&SymbolVersion> {
+        self.versions.get(index)?.as_ref()
+    }
+
+    /// Returns the names of the version definitions.
+    ///
+    /// The base version (which is the name of the file) is not included.
+    pub fn definitions(&self) -> &[String] {
+        &self.definitions
+    }
+
+    /// Parses the version definitions from the `SHT_GNU_VERDEF` section.
+    fn parse_definitions(elf: &ElfBytes<'_, AnyEndian>) -> Result<Vec<String>, ParseError> {
+        let Some(section_headers) = elf.section_headers() else {
+            return Ok(Vec::new());
+        };
+        let Some(verdef_header) = section_headers
+            .iter()
+            .find(|shdr| shdr.sh_type == elf::abi::SHT_GNU_VERDEF)
+        else {
+            return Ok(Vec::new());
+        };
+        let string_table =
+            elf.section_data_as_strtab(&section_headers.get(verdef_header.sh_link as usize)?)?;
+        let (data, _) = elf.section_data(&verdef_header)?;
+        let mut definitions = Vec::new();
+        for (verdef, mut verdef_aux) in VerDefIterator::new(
+            elf.ehdr.endianness,
+            elf.ehdr.class,
+            verdef_header.sh_info.into(),
+            0,
+            data,
+        ) {
+            if verdef.vd_flags & elf::abi::VER_FLG_BASE != 0 {
+                continue;
+            }
+            if let Some(aux) = verdef_aux.next() {
+                definitions.push(string_table.get(aux.vda_name as usize)?.to_string());
+            }
+        }
+        Ok(definitions)
+    }
+}
+
 impl Property<'_> for DynamicSymbols {
     fn items(&self) -> Vec<Vec<String>> {
         self.symbols
@@ -150,4 +255,22 @@ impl Property<'_> for DynamicSymbols {
             })
             .collect()
     }
+
+    fn values(&self) -> Vec<Vec<Option<i128>>> {
+        self.symbols
+            .iter()
+            .map(|symbol| {
+                vec![
+                    None,
+                    None,
+                    None,
+                    Some(symbol.st_value.into()),
+                    Some(symbol.st_size.into()),
+                    None,
+                    None,
+                    Some(symbol.st_shndx.into()),
+                ]
+            })
+            .collect()
+    }
 }
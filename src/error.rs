@@ -30,9 +30,18 @@ pub enum Error {
     /// Error that may occur while parsing integers.
     #[error("Failed to parse integer: `{0}`")]
     IntParseError(#[from] std::num::TryFromIntError),
-    /// Error that may occur while analyzing library dependencies.
-    #[error("Dependency analysis error: `{0}`")]
-    LddError(#[from] lddtree::Error),
+    /// Error that may occur while parsing rules.
+    #[error("Rule error: `{0}`")]
+    RuleError(String),
+    /// Error that may occur while scanning directories.
+    #[error("Scan error: `{0}`")]
+    ScanError(String),
+    /// Error that may occur while inspecting a running process.
+    #[error("Process error: `{0}`")]
+    ProcessError(String),
+    /// Error that may occur while storing the analysis results in the cache.
+    #[error("Cache error: `{0}`")]
+    CacheError(String),
 }
 
 /// Type alias for the standard [`Result`] type.
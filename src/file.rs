@@ -2,9 +2,12 @@ use bytesize::ByteSize;
 use sysinfo::{Gid, Groups, Uid, Users};
 
 use crate::error::Result;
+use memmap2::Mmap;
 use std::{
     fs::{self, File, OpenOptions},
-    path::PathBuf,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -14,15 +17,62 @@ use std::os::unix::fs::{MetadataExt, PermissionsExt};
 #[cfg(target_os = "windows")]
 use std::os::windows::fs::MetadataExt;
 
+/// Contents of a file.
+#[derive(Debug)]
+pub enum FileData {
+    /// Memory-mapped file.
+    Mapped(Mmap),
+    /// File that is read into memory.
+    Owned(Vec<u8>),
+}
+
+impl FileData {
+    /// Opens the file.
+    ///
+    /// The file is memory-mapped so that only the accessed pages are loaded.
+    /// It falls back to reading the whole file if it cannot be mapped (e.g. empty files).
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() != 0 {
+            // SAFETY: The mapping is read-only and the file is not written by this
+            // process while it is mapped, since the hexdump is opened read-only for
+            // the mapped files (see `FileInfo::open_file`). Other processes are not
+            // expected to modify or truncate the file while it is being analyzed.
+            if let Ok(mmap) = unsafe { Mmap::map(&file) } {
+                return Ok(Self::Mapped(mmap));
+            }
+        }
+        Ok(Self::Owned(fs::read(path)?))
+    }
+}
+
+impl From<Vec<u8>> for FileData {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Owned(bytes)
+    }
+}
+
+impl Deref for FileData {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
 /// General file information.
 #[derive(Debug)]
 pub struct FileInfo<'a> {
     /// Path of the file.
-    pub path: &'a str,
+    pub path: String,
     /// Arguments of the file.
     pub arguments: Option<Vec<&'a str>>,
     /// Bytes of the file.
-    pub bytes: &'a [u8],
+    ///
+    /// The contents are shared with the background jobs.
+    pub bytes: Arc<FileData>,
     /// Whether if the file is read only.
     pub is_read_only: bool,
     /// Name of the file.
@@ -72,14 +122,14 @@ pub struct FileDateInfo {
 impl<'a> FileInfo<'a> {
     /// Constructs a new instance.
     #[cfg(not(target_os = "windows"))]
-    pub fn new(path: &'a str, arguments: Option<Vec<&'a str>>, bytes: &'a [u8]) -> Result<Self> {
+    pub fn new(path: &str, arguments: Option<Vec<&'a str>>, bytes: Arc<FileData>) -> Result<Self> {
         let metadata = fs::metadata(path)?;
         let mode = metadata.permissions().mode();
 
         let users = Users::new_with_refreshed_list();
         let groups = Groups::new_with_refreshed_list();
         Ok(Self {
-            path,
+            path: path.to_string(),
             arguments,
             bytes,
             is_read_only: false,
@@ -153,13 +203,13 @@ impl<'a> FileInfo<'a> {
     }
 
     #[cfg(target_os = "windows")]
-    pub fn new(path: &'a str, arguments: Option<Vec<&'a str>>, bytes: &'a [u8]) -> Result<Self> {
+    pub fn new(path: &str, arguments: Option<Vec<&'a str>>, bytes: Arc<FileData>) -> Result<Self> {
         let metadata = fs::metadata(path)?;
 
         let users = Users::new_with_refreshed_list();
         let groups = Groups::new_with_refreshed_list();
         Ok(Self {
-            path,
+            path: path.to_string(),
             arguments,
             bytes,
             is_read_only: false,
@@ -198,13 +248,20 @@ impl<'a> FileInfo<'a> {
     }
 
     /// Opens the file (with R/W if possible) and returns it.
+    ///
+    /// Memory-mapped files are opened read-only since writing to them would change
+    /// the bytes that are borrowed by the analysis and the background jobs.
     pub fn open_file(&mut self) -> Result<File> {
+        if matches!(*self.bytes, FileData::Mapped(_)) {
+            self.is_read_only = true;
+            return Ok(File::open(&self.path)?);
+        }
         Ok(
-            match OpenOptions::new().write(true).read(true).open(self.path) {
+            match OpenOptions::new().write(true).read(true).open(&self.path) {
                 Ok(v) => v,
                 Err(_) => {
                     self.is_read_only = true;
-                    File::open(self.path)?
+                    File::open(&self.path)?
                 }
             },
         )
@@ -0,0 +1,380 @@
+use crate::elf::Elf;
+use crate::job::{JobHandle, PROGRESS_INTERVAL};
+use elf::abi;
+use md5::{Digest, Md5};
+use serde_derive::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::Sha256;
+
+/// Names of the symbols that are excluded from the symbol hash (as in telfhash).
+const SYMBOL_HASH_EXCLUSIONS: &[&str] = &[
+    "__libc_start_main",
+    "main",
+    "abort",
+    "cachectl",
+    "cacheflush",
+    "puts",
+    "atol",
+    "malloc_trim",
+];
+
+/// Hashes of a file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileHashes {
+    /// MD5 of the file.
+    pub md5: String,
+    /// SHA-1 of the file.
+    pub sha1: String,
+    /// SHA-256 of the file.
+    pub sha256: String,
+    /// MD5 of the sorted imported symbol names.
+    pub import_hash: Option<String>,
+    /// TLSH of the sorted function symbol names (telfhash-style).
+    pub symbol_hash: Option<String>,
+    /// TLSH of the file.
+    pub tlsh: Option<String>,
+    /// Context triggered piecewise hash (ssdeep) of the file.
+    pub ssdeep: String,
+}
+
+impl FileHashes {
+    /// Computes the hashes of the given file.
+    ///
+    /// The progress is reported to the given job. If it is cancelled, the rest of the
+    /// hashes are not computed.
+    pub fn new(bytes: &[u8], elf: &Elf, job: &JobHandle) -> Self {
+        let mut imports = elf
+            .dynamic_symbols
+            .iter()
+            .filter(|(name, symbol)| !name.is_empty() && symbol.is_undefined())
+            .map(|(name, _)| symbol_name(name).to_lowercase())
+            .collect::<Vec<_>>();
+        imports.sort();
+        imports.dedup();
+        let (mut md5, mut sha1, mut sha256) = (Md5::new(), Sha1::new(), Sha256::new());
+        // The digests are computed in chunks, followed by the fuzzy hashes.
+        for (i, chunk) in bytes.chunks(PROGRESS_INTERVAL).enumerate() {
+            job.set_progress(i * PROGRESS_INTERVAL, bytes.len() * 2);
+            if job.is_cancelled() {
+                return Self::default();
+            }
+            md5.update(chunk);
+            sha1.update(chunk);
+            sha256.update(chunk);
+        }
+        job.set_progress(2, 4);
+        let tlsh = tlsh(bytes);
+        job.set_progress(3, 4);
+        if job.is_cancelled() {
+            return Self::default();
+        }
+        Self {
+            md5: hex(&md5.finalize()),
+            sha1: hex(&sha1.finalize()),
+            sha256: hex(&sha256.finalize()),
+            import_hash: (!imports.is_empty())
+                .then(|| hex(&Md5::digest(imports.join(",").as_bytes()))),
+            symbol_hash: symbol_hash(elf),
+            tlsh,
+            ssdeep: ssdeep(bytes),
+        }
+    }
+
+    /// Returns the names and the values of the hashes.
+    pub fn items(&self) -> Vec<(&'static str, String)> {
+        let value = |v: &Option<String>| v.clone().unwrap_or_else(|| String::from("-"));
+        vec![
+            ("MD5", self.md5.to_string()),
+            ("SHA1", self.sha1.to_string()),
+            ("SHA256", self.sha256.to_string()),
+            ("Import hash", value(&self.import_hash)),
+            ("Symbol hash", value(&self.symbol_hash)),
+            ("TLSH", value(&self.tlsh)),
+            ("ssdeep", self.ssdeep.to_string()),
+        ]
+    }
+}
+
+/// Returns the lowercase hexadecimal representation of the bytes.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|v| format!("{v:02x}")).collect()
+}
+
+/// Returns the symbol name without the version (e.g. `@GLIBC_2.2.5`).
+fn symbol_name(name: &str) -> &str {
+    name.split('@').next().unwrap_or(name)
+}
+
+/// Returns the telfhash-style hash of the function symbols.
+///
+/// The global function symbols are taken from the symbol table (or the dynamic
+/// symbol table if the file is stripped), filtered, sorted and hashed with TLSH.
+fn symbol_hash(elf: &Elf) -> Option<String> {
+    let mut symbols = elf.symbols.iter().collect::<Vec<_>>();
+    if symbols.is_empty() {
+        symbols = elf.dynamic_symbols.iter().collect();
+    }
+    let mut names = symbols
+        .into_iter()
+        .filter(|(name, symbol)| {
+            symbol.st_symtype() == abi::STT_FUNC
+                && symbol.st_bind() == abi::STB_GLOBAL
+                && symbol.st_vis() == abi::STV_DEFAULT
+                && !name.is_empty()
+        })
+        .map(|(name, _)| symbol_name(name).to_lowercase())
+        .filter(|name| {
+            !name.starts_with(['_', '.'])
+                && !name.ends_with("64")
+                && !name.starts_with("str")
+                && !name.starts_with("mem")
+                && !SYMBOL_HASH_EXCLUSIONS.contains(&name.as_str())
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    tlsh(names.join(",").as_bytes())
+}
+
+/// Pearson hash table of TLSH.
+const TLSH_TABLE: [u8; 256] = [
+    1, 87, 49, 12, 176, 178, 102, 166, 121, 193, 6, 84, 249, 230, 44, 163, 14, 197, 213, 181, 161,
+    85, 218, 80, 64, 239, 24, 226, 236, 142, 38, 200, 110, 177, 104, 103, 141, 253, 255, 50, 77,
+    101, 81, 18, 45, 96, 31, 222, 25, 107, 190, 70, 86, 237, 240, 34, 72, 242, 20, 214, 244, 227,
+    149, 235, 97, 234, 57, 22, 60, 250, 82, 175, 208, 5, 127, 199, 111, 62, 135, 248, 174, 169,
+    211, 58, 66, 154, 106, 195, 245, 171, 17, 187, 182, 179, 0, 243, 132, 56, 148, 75, 128, 133,
+    158, 100, 130, 126, 91, 13, 153, 246, 216, 219, 119, 68, 223, 78, 83, 88, 201, 99, 122, 11, 92,
+    32, 136, 114, 52, 10, 138, 30, 48, 183, 156, 35, 61, 26, 143, 74, 251, 94, 129, 162, 63, 152,
+    170, 7, 115, 167, 241, 206, 3, 150, 55, 59, 151, 220, 90, 53, 23, 131, 125, 173, 15, 238, 79,
+    95, 89, 16, 105, 137, 225, 224, 217, 160, 37, 123, 118, 73, 2, 157, 46, 116, 9, 145, 134, 228,
+    207, 212, 202, 215, 69, 229, 27, 188, 67, 124, 168, 252, 42, 4, 29, 108, 21, 247, 19, 205, 39,
+    203, 233, 40, 186, 147, 198, 192, 155, 33, 164, 191, 98, 204, 165, 180, 117, 76, 140, 36, 210,
+    172, 41, 54, 159, 8, 185, 232, 113, 196, 231, 47, 146, 120, 51, 65, 28, 144, 254, 221, 93, 189,
+    194, 139, 112, 43, 71, 109, 184, 209,
+];
+
+/// Minimum number of bytes for computing TLSH.
+const TLSH_MIN_LENGTH: usize = 50;
+
+/// Number of buckets that are used in TLSH.
+const TLSH_BUCKETS: usize = 128;
+
+/// Maps the salt and the three bytes to a bucket (Pearson hashing).
+#[inline(always)]
+fn tlsh_mapping(salt: u8, i: u8, j: u8, k: u8) -> u8 {
+    let mut h = TLSH_TABLE[salt as usize];
+    h = TLSH_TABLE[(h ^ i) as usize];
+    h = TLSH_TABLE[(h ^ j) as usize];
+    TLSH_TABLE[(h ^ k) as usize]
+}
+
+/// Returns the length value of TLSH (logarithm of the length).
+fn tlsh_length(length: usize) -> u8 {
+    let log = (length as f64).ln();
+    let value = if length <= 656 {
+        log / 0.4054651
+    } else if length <= 3199 {
+        log / 0.26236426 - 8.72777
+    } else {
+        log / 0.095310180 - 62.5472
+    };
+    (value.floor() as u64 & 0xff) as u8
+}
+
+/// Computes the Trend Micro Locality Sensitive Hash (128 buckets, 1 byte checksum).
+///
+/// Returns `None` if the data is too short or does not have enough variation.
+fn tlsh(bytes: &[u8]) -> Option<String> {
+    if bytes.len() < TLSH_MIN_LENGTH {
+        return None;
+    }
+    let mut buckets = [0u32; 256];
+    let mut checksum = 0;
+    for window in bytes.windows(5) {
+        let &[e, d, c, b, a] = window else {
+            unreachable!()
+        };
+        checksum = tlsh_mapping(0, a, b, checksum);
+        buckets[tlsh_mapping(2, a, b, c) as usize] += 1;
+        buckets[tlsh_mapping(3, a, b, d) as usize] += 1;
+        buckets[tlsh_mapping(5, a, c, d) as usize] += 1;
+        buckets[tlsh_mapping(7, a, c, e) as usize] += 1;
+        buckets[tlsh_mapping(11, a, b, e) as usize] += 1;
+        buckets[tlsh_mapping(13, a, d, e) as usize] += 1;
+    }
+    let buckets = &buckets[..TLSH_BUCKETS];
+    if buckets.iter().filter(|v| **v > 0).count() <= TLSH_BUCKETS / 2 {
+        return None;
+    }
+    let mut sorted = buckets.to_vec();
+    sorted.sort_unstable();
+    let (q1, q2, q3) = (
+        sorted[TLSH_BUCKETS / 4 - 1],
+        sorted[TLSH_BUCKETS / 2 - 1],
+        sorted[TLSH_BUCKETS - TLSH_BUCKETS / 4 - 1],
+    );
+    if q3 == 0 {
+        return None;
+    }
+    let ratio = |q: u32| (q.wrapping_mul(100) as f32 / q3 as f32) as u32 as u8 % 16;
+    let swap = |v: u8| v.rotate_left(4);
+    let mut hash = vec![
+        swap(checksum),
+        swap(tlsh_length(bytes.len())),
+        (ratio(q1) << 4) | ratio(q2),
+    ];
+    hash.extend(buckets.chunks(4).rev().map(|chunk| {
+        chunk.iter().enumerate().fold(0u8, |code, (i, count)| {
+            let value = match *count {
+                v if v > q3 => 3,
+                v if v > q2 => 2,
+                v if v > q1 => 1,
+                _ => 0,
+            };
+            code | (value << (i * 2))
+        })
+    }));
+    Some(format!("T1{}", hex(&hash).to_uppercase()))
+}
+
+/// Size of the rolling window of ssdeep.
+const SSDEEP_WINDOW: usize = 7;
+
+/// Minimum block size of ssdeep.
+const SSDEEP_MIN_BLOCK_SIZE: u32 = 3;
+
+/// Maximum length of an ssdeep signature.
+const SSDEEP_LENGTH: usize = 64;
+
+/// Alphabet of the ssdeep signatures.
+const SSDEEP_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Rolling hash of ssdeep.
+#[derive(Default)]
+struct RollingHash {
+    /// Last bytes.
+    window: [u32; SSDEEP_WINDOW],
+    /// Sum of the bytes in the window.
+    h1: u32,
+    /// Sum of the weighted bytes in the window.
+    h2: u32,
+    /// Shift/xor hash.
+    h3: u32,
+    /// Number of bytes.
+    n: usize,
+}
+
+impl RollingHash {
+    /// Adds the byte and returns the hash.
+    fn update(&mut self, byte: u8) -> u32 {
+        let byte = u32::from(byte);
+        let index = self.n % SSDEEP_WINDOW;
+        self.h2 = self
+            .h2
+            .wrapping_sub(self.h1)
+            .wrapping_add(SSDEEP_WINDOW as u32 * byte);
+        self.h1 = self.h1.wrapping_add(byte).wrapping_sub(self.window[index]);
+        self.window[index] = byte;
+        self.n += 1;
+        self.h3 = (self.h3 << 5) ^ byte;
+        self.h1.wrapping_add(self.h2).wrapping_add(self.h3)
+    }
+}
+
+/// Computes the context triggered piecewise hash (ssdeep) in `blocksize:hash:hash` format.
+fn ssdeep(bytes: &[u8]) -> String {
+    const HASH_INIT: u32 = 0x28021967;
+    const HASH_PRIME: u32 = 0x01000193;
+    let sum_hash = |hash: u32, byte: u8| hash.wrapping_mul(HASH_PRIME) ^ u32::from(byte);
+    let mut block_size = SSDEEP_MIN_BLOCK_SIZE;
+    while (block_size as usize) * SSDEEP_LENGTH < bytes.len() {
+        block_size *= 2;
+    }
+    loop {
+        let mut rolling_hash = RollingHash::default();
+        let (mut h1, mut h2) = (HASH_INIT, HASH_INIT);
+        let mut signature1 = [0u8; SSDEEP_LENGTH];
+        let mut signature2 = [0u8; SSDEEP_LENGTH / 2];
+        let (mut i, mut j) = (0, 0);
+        let mut hash = 0;
+        for byte in bytes {
+            h1 = sum_hash(h1, *byte);
+            h2 = sum_hash(h2, *byte);
+            hash = rolling_hash.update(*byte);
+            if hash % block_size == block_size - 1 {
+                signature1[i] = SSDEEP_ALPHABET[(h1 % 64) as usize];
+                if i < SSDEEP_LENGTH - 1 {
+                    h1 = HASH_INIT;
+                    i += 1;
+                }
+            }
+            if hash % (block_size * 2) == block_size * 2 - 1 {
+                signature2[j] = SSDEEP_ALPHABET[(h2 % 64) as usize];
+                if j < SSDEEP_LENGTH / 2 - 1 {
+                    h2 = HASH_INIT;
+                    j += 1;
+                }
+            }
+        }
+        if hash != 0 {
+            signature1[i] = SSDEEP_ALPHABET[(h1 % 64) as usize];
+            signature2[j] = SSDEEP_ALPHABET[(h2 % 64) as usize];
+        }
+        // The block size guess might be too large for the data.
+        if block_size > SSDEEP_MIN_BLOCK_SIZE && i < SSDEEP_LENGTH / 2 {
+            block_size /= 2;
+            continue;
+        }
+        let signature = |v: &[u8]| {
+            String::from_utf8_lossy(&v[..v.iter().position(|c| *c == 0).unwrap_or(v.len())])
+                .to_string()
+        };
+        return format!(
+            "{block_size}:{}:{}",
+            signature(&signature1),
+            signature(&signature2)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_hashes() {
+        assert_eq!(
+            "d41d8cd98f00b204e9800998ecf8427e",
+            hex(&Md5::digest(b"")).as_str()
+        );
+        assert_eq!(None, tlsh(b"too short"));
+        assert_eq!(None, tlsh(&[0; 1024]));
+        assert_eq!("3::", ssdeep(b""));
+        let data = (0..4096u32)
+            .map(|v| (v.wrapping_mul(2654435761) >> 13) as u8)
+            .collect::<Vec<_>>();
+        let hash = tlsh(&data).expect("failed to compute TLSH");
+        assert_eq!(72, hash.len());
+        assert!(hash.starts_with("T1"));
+        let signature = ssdeep(&data);
+        let parts = signature.split(':').collect::<Vec<_>>();
+        assert_eq!(3, parts.len());
+        assert!(parts[1].len() <= SSDEEP_LENGTH && parts[2].len() <= SSDEEP_LENGTH / 2);
+        // Similar data has similar hashes.
+        let mut similar = data.clone();
+        similar[2048] ^= 0xff;
+        let similar_hash = tlsh(&similar).expect("failed to compute TLSH");
+        assert!(
+            hash.chars()
+                .zip(similar_hash.chars())
+                .filter(|(a, b)| a != b)
+                .count()
+                < 8
+        );
+        assert_eq!(
+            signature.split(':').next(),
+            ssdeep(&similar).split(':').next()
+        );
+    }
+}
@@ -0,0 +1,359 @@
+use crate::elf::Elf;
+
+/// Headers of the imports of a capability.
+pub const IMPORT_HEADERS: &[&str] = &["Function", "Capability", "Version"];
+
+/// Capability of a binary that is inferred from the imported functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Capability {
+    /// Network communication.
+    Network,
+    /// Process creation and command execution.
+    ProcessExecution,
+    /// File system access.
+    FileIo,
+    /// Memory mapping and protection changes.
+    MemoryProtection,
+    /// Cryptographic primitives.
+    Crypto,
+    /// Process tracing.
+    AntiDebugging,
+    /// Unbounded string and buffer functions.
+    UnsafeLibc,
+}
+
+impl Capability {
+    /// All capabilities.
+    pub const ALL: &[Capability] = &[
+        Capability::Network,
+        Capability::ProcessExecution,
+        Capability::FileIo,
+        Capability::MemoryProtection,
+        Capability::Crypto,
+        Capability::AntiDebugging,
+        Capability::UnsafeLibc,
+    ];
+
+    /// Returns the title.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Capability::Network => "Network",
+            Capability::ProcessExecution => "Process execution",
+            Capability::FileIo => "File I/O",
+            Capability::MemoryProtection => "Memory protection",
+            Capability::Crypto => "Crypto",
+            Capability::AntiDebugging => "Anti-debugging",
+            Capability::UnsafeLibc => "Unsafe libc",
+        }
+    }
+
+    /// Returns the description.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Capability::Network => "Creates sockets, resolves hosts and transfers data",
+            Capability::ProcessExecution => "Spawns processes and runs commands",
+            Capability::FileIo => "Opens, reads, writes and modifies files",
+            Capability::MemoryProtection => "Maps memory and changes its protection",
+            Capability::Crypto => "Uses cryptographic libraries",
+            Capability::AntiDebugging => "Traces processes or changes their dumpability",
+            Capability::UnsafeLibc => "Uses functions without bounds checking",
+        }
+    }
+
+    /// Returns the names of the functions.
+    fn functions(&self) -> &'static [&'static str] {
+        match self {
+            Capability::Network => &[
+                "socket",
+                "socketpair",
+                "connect",
+                "bind",
+                "listen",
+                "accept",
+                "accept4",
+                "send",
+                "sendto",
+                "sendmsg",
+                "sendmmsg",
+                "recv",
+                "recvfrom",
+                "recvmsg",
+                "recvmmsg",
+                "shutdown",
+                "setsockopt",
+                "getsockopt",
+                "getpeername",
+                "getsockname",
+                "getaddrinfo",
+                "getnameinfo",
+                "gethostbyname",
+                "gethostbyname2",
+                "gethostbyname_r",
+                "gethostbyaddr",
+                "inet_addr",
+                "inet_aton",
+                "inet_ntoa",
+                "inet_ntop",
+                "inet_pton",
+                "res_query",
+                "res_search",
+            ],
+            Capability::ProcessExecution => &[
+                "execve",
+                "execveat",
+                "fexecve",
+                "execv",
+                "execvp",
+                "execvpe",
+                "execl",
+                "execlp",
+                "execle",
+                "system",
+                "popen",
+                "posix_spawn",
+                "posix_spawnp",
+                "fork",
+                "vfork",
+                "clone",
+                "clone3",
+                "daemon",
+            ],
+            Capability::FileIo => &[
+                "open",
+                "open64",
+                "openat",
+                "openat64",
+                "creat",
+                "creat64",
+                "fopen",
+                "fopen64",
+                "freopen",
+                "freopen64",
+                "fdopen",
+                "read",
+                "pread",
+                "pread64",
+                "readv",
+                "write",
+                "pwrite",
+                "pwrite64",
+                "writev",
+                "fread",
+                "fwrite",
+                "sendfile",
+                "sendfile64",
+                "copy_file_range",
+                "truncate",
+                "truncate64",
+                "ftruncate",
+                "ftruncate64",
+                "unlink",
+                "unlinkat",
+                "remove",
+                "rename",
+                "renameat",
+                "renameat2",
+                "link",
+                "linkat",
+                "symlink",
+                "symlinkat",
+                "readlink",
+                "readlinkat",
+                "mkdir",
+                "mkdirat",
+                "rmdir",
+                "chmod",
+                "fchmod",
+                "fchmodat",
+                "chown",
+                "fchown",
+                "fchownat",
+                "lchown",
+                "opendir",
+                "fdopendir",
+                "readdir",
+                "readdir64",
+            ],
+            Capability::MemoryProtection => &[
+                "mprotect",
+                "pkey_mprotect",
+                "mmap",
+                "mmap64",
+                "mremap",
+                "memfd_create",
+            ],
+            Capability::Crypto => &["crypt", "crypt_r", "getrandom", "getentropy"],
+            Capability::AntiDebugging => &["ptrace", "prctl"],
+            Capability::UnsafeLibc => &[
+                "gets", "strcpy", "strcat", "stpcpy", "wcscpy", "wcscat", "sprintf", "vsprintf",
+                "scanf", "sscanf", "fscanf", "vscanf", "vsscanf", "vfscanf", "getwd", "mktemp",
+                "tmpnam", "tempnam",
+            ],
+        }
+    }
+
+    /// Returns the prefixes of the library functions.
+    fn prefixes(&self) -> &'static [&'static str] {
+        match self {
+            Capability::Network => &["curl_"],
+            Capability::Crypto => &[
+                "EVP_",
+                "SSL_",
+                "AES_",
+                "RSA_",
+                "DES_",
+                "EC_KEY_",
+                "HMAC",
+                "MD5_",
+                "SHA1_",
+                "SHA256_",
+                "SHA512_",
+                "RAND_bytes",
+                "gcry_",
+                "gnutls_",
+                "mbedtls_",
+                "crypto_",
+                "sodium_",
+                "PK11_",
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Classifies the imported function.
+    ///
+    /// The symbol version, the `__isoc99_` prefix and the suffixes of the
+    /// fortified functions (e.g. `__read_chk`) are ignored. Fortified functions
+    /// are not considered unsafe.
+    pub fn classify(name: &str) -> Option<Self> {
+        let name = name.split('@').next().unwrap_or(name);
+        let name = name
+            .strip_prefix("__isoc99_")
+            .or_else(|| name.strip_prefix("__isoc23_"))
+            .unwrap_or(name);
+        let (name, fortified) = match name.strip_prefix("__").and_then(|v| {
+            v.strip_suffix("_chk")
+                .or_else(|| v.strip_suffix("_2"))
+                .filter(|v| !v.is_empty())
+        }) {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        Self::ALL.iter().copied().find(|capability| {
+            !(fortified && *capability == Capability::UnsafeLibc)
+                && (capability.functions().contains(&name)
+                    || capability
+                        .prefixes()
+                        .iter()
+                        .any(|prefix| name.starts_with(prefix)))
+        })
+    }
+}
+
+/// Imported function with a capability.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Import {
+    /// Name of the function.
+    pub name: String,
+    /// Required version (e.g. `GLIBC_2.2.5`).
+    pub version: Option<String>,
+    /// Capability.
+    pub capability: Capability,
+}
+
+/// Capabilities of a binary.
+#[derive(Clone, Debug, Default)]
+pub struct Capabilities {
+    /// Classified imports.
+    pub imports: Vec<Import>,
+}
+
+impl Capabilities {
+    /// Classifies the imported functions of the dynamic symbol table.
+    pub fn new(elf: &Elf) -> Self {
+        let mut imports = elf
+            .dynamic_symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, symbol))| !name.is_empty() && symbol.is_undefined())
+            .filter_map(|(i, (name, _))| {
+                Some(Import {
+                    capability: Capability::classify(name)?,
+                    name: name.to_string(),
+                    version: elf.dynamic_symbols.requirement(i).map(String::from),
+                })
+            })
+            .collect::<Vec<_>>();
+        imports.sort_by(|a, b| (a.capability, &a.name).cmp(&(b.capability, &b.name)));
+        imports.dedup();
+        Self { imports }
+    }
+
+    /// Returns the imports of the capability.
+    pub fn imports(&self, capability: Capability) -> impl Iterator<Item = &Import> {
+        self.imports
+            .iter()
+            .filter(move |import| import.capability == capability)
+    }
+
+    /// Returns the summary rows (capability, number of imports and description).
+    pub fn summary(&self) -> Vec<Vec<String>> {
+        Capability::ALL
+            .iter()
+            .filter_map(|capability| {
+                let count = self.imports(*capability).count();
+                (count != 0).then(|| {
+                    vec![
+                        capability.title().to_string(),
+                        count.to_string(),
+                        capability.description().to_string(),
+                    ]
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the rows of the imports of the capability.
+    pub fn rows(&self, capability: Capability) -> Vec<Vec<String>> {
+        self.imports(capability)
+            .map(|import| {
+                vec![
+                    import.name.to_string(),
+                    import.capability.title().to_string(),
+                    import.version.clone().unwrap_or_else(|| String::from("-")),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_classify() {
+        for (name, capability) in [
+            ("socket", Some(Capability::Network)),
+            ("connect@GLIBC_2.2.5", Some(Capability::Network)),
+            ("curl_easy_perform", Some(Capability::Network)),
+            ("execve", Some(Capability::ProcessExecution)),
+            ("posix_spawnp", Some(Capability::ProcessExecution)),
+            ("open64", Some(Capability::FileIo)),
+            ("__open64_2", Some(Capability::FileIo)),
+            ("__read_chk", Some(Capability::FileIo)),
+            ("mprotect", Some(Capability::MemoryProtection)),
+            ("EVP_EncryptInit_ex", Some(Capability::Crypto)),
+            ("ptrace", Some(Capability::AntiDebugging)),
+            ("gets", Some(Capability::UnsafeLibc)),
+            ("__isoc99_sscanf", Some(Capability::UnsafeLibc)),
+            ("__strcpy_chk", None),
+            ("__sprintf_chk", None),
+            ("printf", None),
+            ("__2", None),
+        ] {
+            assert_eq!(capability, Capability::classify(name), "{name}");
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use crate::elf::Elf;
+use crate::job::{JobHandle, PROGRESS_INTERVAL};
+use elf::abi;
+use regex::bytes::RegexBuilder;
+
+/// Number of table entries to match for the generated word tables (e.g. CRC32).
+const TABLE_PREFIX: usize = 16;
+
+/// Well-known constant of a cryptographic (or encoding) algorithm.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Signature {
+    /// Name of the algorithm.
+    algorithm: &'static str,
+    /// Name of the constant.
+    name: String,
+    /// Bytes of the constant.
+    bytes: Vec<u8>,
+}
+
+impl Signature {
+    /// Constructs a new instance.
+    fn new(algorithm: &'static str, name: &str, bytes: Vec<u8>) -> Self {
+        Self {
+            algorithm,
+            name: name.to_string(),
+            bytes,
+        }
+    }
+
+    /// Constructs the little and big endian variants of a table of 32-bit words.
+    fn words32(algorithm: &'static str, name: &str, words: &[u32]) -> [Self; 2] {
+        [
+            Self::new(
+                algorithm,
+                &format!("{name} (LE)"),
+                words.iter().flat_map(|v| v.to_le_bytes()).collect(),
+            ),
+            Self::new(
+                algorithm,
+                &format!("{name} (BE)"),
+                words.iter().flat_map(|v| v.to_be_bytes()).collect(),
+            ),
+        ]
+    }
+
+    /// Constructs the little and big endian variants of a table of 64-bit words.
+    fn words64(algorithm: &'static str, name: &str, words: &[u64]) -> [Self; 2] {
+        [
+            Self::new(
+                algorithm,
+                &format!("{name} (LE)"),
+                words.iter().flat_map(|v| v.to_le_bytes()).collect(),
+            ),
+            Self::new(
+                algorithm,
+                &format!("{name} (BE)"),
+                words.iter().flat_map(|v| v.to_be_bytes()).collect(),
+            ),
+        ]
+    }
+}
+
+/// Returns the signatures of the known constants.
+///
+/// If a constant is a prefix of another one (e.g. MD5 and SHA-1 initial values),
+/// the longer one comes first so that it takes precedence.
+fn signatures() -> Vec<Signature> {
+    let (sbox, inverse_sbox) = aes_sboxes();
+    let te0 = sbox
+        .iter()
+        .take(TABLE_PREFIX)
+        .map(|v| u32::from_be_bytes([xtime(*v), *v, *v, xtime(*v) ^ v]))
+        .collect::<Vec<_>>();
+    let mut signatures = vec![
+        Signature::new("AES", "S-box", sbox.to_vec()),
+        Signature::new("AES", "Inverse S-box", inverse_sbox.to_vec()),
+    ];
+    signatures.extend(Signature::words32("AES", "T-table (Te0)", &te0));
+    signatures.extend(Signature::words32(
+        "SHA-1",
+        "Initial values",
+        &[0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0],
+    ));
+    signatures.extend(Signature::words32(
+        "MD5",
+        "Initial values",
+        &[0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+    ));
+    signatures.extend(Signature::words32(
+        "MD5",
+        "Round constants",
+        &[0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee],
+    ));
+    signatures.extend(Signature::words32(
+        "SHA-1",
+        "Round constants",
+        &[0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xca62c1d6],
+    ));
+    signatures.extend(Signature::words32(
+        "SHA-256",
+        "Initial values",
+        &[
+            0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+            0x5be0cd19,
+        ],
+    ));
+    signatures.extend(Signature::words32(
+        "SHA-224",
+        "Initial values",
+        &[
+            0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7,
+            0xbefa4fa4,
+        ],
+    ));
+    signatures.extend(Signature::words32(
+        "SHA-256",
+        "Round constants",
+        &[
+            0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+            0xab1c5ed5,
+        ],
+    ));
+    signatures.extend(Signature::words64(
+        "SHA-512",
+        "Initial values",
+        &[
+            0x6a09e667f3bcc908,
+            0xbb67ae8584caa73b,
+            0x3c6ef372fe94f82b,
+            0xa54ff53a5f1d36f1,
+        ],
+    ));
+    signatures.extend(Signature::words64(
+        "SHA-384",
+        "Initial values",
+        &[
+            0xcbbb9d5dc1059ed8,
+            0x629a292a367cd507,
+            0x9159015a3070dd17,
+            0x152fecd8f70e5939,
+        ],
+    ));
+    signatures.extend(Signature::words64(
+        "SHA-512",
+        "Round constants",
+        &[
+            0x428a2f98d728ae22,
+            0x7137449123ef65cd,
+            0xb5c0fbcfec4d3b2f,
+            0xe9b5dba58189dbbc,
+        ],
+    ));
+    signatures.extend(Signature::words32(
+        "CRC32",
+        "Table",
+        &crc32_table(0xedb88320),
+    ));
+    signatures.extend(Signature::words32(
+        "CRC32C",
+        "Table",
+        &crc32_table(0x82f63b78),
+    ));
+    signatures.extend([
+        Signature::new("ChaCha20", "Sigma", b"expand 32-byte k".to_vec()),
+        Signature::new("ChaCha20", "Tau", b"expand 16-byte k".to_vec()),
+        Signature::new(
+            "Base64",
+            "Alphabet",
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/".to_vec(),
+        ),
+        Signature::new(
+            "Base64",
+            "URL-safe alphabet",
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_".to_vec(),
+        ),
+    ]);
+    // 2^255 - 19 and the Ed25519 curve constant `d` in little endian.
+    let mut prime = vec![0xff; 32];
+    prime[0] = 0xed;
+    prime[31] = 0x7f;
+    signatures.extend([
+        Signature::new("Curve25519", "Field prime", prime),
+        Signature::new(
+            "Curve25519",
+            "Ed25519 constant d",
+            vec![
+                0xa3, 0x78, 0x59, 0x13, 0xca, 0x4d, 0xeb, 0x75, 0xab, 0xd8, 0x41, 0x41, 0x4d, 0x0a,
+                0x70, 0x00, 0x98, 0xe8, 0x79, 0x77, 0x79, 0x40, 0xc7, 0x8c, 0x73, 0xfe, 0x6f, 0x2b,
+                0xee, 0x6c, 0x03, 0x52,
+            ],
+        ),
+    ]);
+    signatures
+}
+
+/// Multiplies the value by 2 in the AES field.
+fn xtime(value: u8) -> u8 {
+    (value << 1) ^ if value & 0x80 != 0 { 0x1b } else { 0 }
+}
+
+/// Returns the AES S-box and the inverse S-box.
+fn aes_sboxes() -> ([u8; 256], [u8; 256]) {
+    let mut sbox = [0; 256];
+    let (mut p, mut q) = (1u8, 1u8);
+    loop {
+        // Multiply p by 3 and divide q by 3 so that q is the inverse of p.
+        p ^= xtime(p);
+        q ^= q << 1;
+        q ^= q << 2;
+        q ^= q << 4;
+        if q & 0x80 != 0 {
+            q ^= 0x09;
+        }
+        sbox[p as usize] =
+            0x63 ^ q ^ q.rotate_left(1) ^ q.rotate_left(2) ^ q.rotate_left(3) ^ q.rotate_left(4);
+        if p == 1 {
+            break;
+        }
+    }
+    // Zero has no inverse.
+    sbox[0] = 0x63;
+    let mut inverse_sbox = [0; 256];
+    for (i, value) in sbox.iter().enumerate() {
+        inverse_sbox[*value as usize] = i as u8;
+    }
+    (sbox, inverse_sbox)
+}
+
+/// Returns the first entries of the CRC32 lookup table for the given (reflected) polynomial.
+fn crc32_table(polynomial: u32) -> Vec<u32> {
+    (0..TABLE_PREFIX as u32)
+        .map(|mut crc| {
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    polynomial ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+            }
+            crc
+        })
+        .collect()
+}
+
+/// Found cryptographic constant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CryptoConstant {
+    /// Name of the algorithm.
+    pub algorithm: &'static str,
+    /// Name of the constant.
+    pub name: String,
+    /// File offset.
+    pub offset: u64,
+    /// Size in bytes.
+    pub size: u64,
+    /// Section that contains the constant.
+    pub section: Option<String>,
+    /// Virtual address.
+    pub address: Option<u64>,
+    /// Nearest preceding symbol in the same section and the distance to it.
+    pub symbol: Option<(String, u64)>,
+}
+
+impl CryptoConstant {
+    /// Returns the table row.
+    pub fn row(&self) -> Vec<String> {
+        vec![
+            self.algorithm.to_string(),
+            self.name.to_string(),
+            format!("{:#x}", self.offset),
+            self.size.to_string(),
+            self.address
+                .map(|v| format!("{v:#x}"))
+                .unwrap_or_else(|| String::from("-")),
+            self.section.clone().unwrap_or_else(|| String::from("-")),
+            match &self.symbol {
+                Some((name, 0)) => name.to_string(),
+                Some((name, distance)) => format!("{name}+{distance:#x}"),
+                None => String::from("-"),
+            },
+        ]
+    }
+}
+
+/// Scans the file for the known cryptographic constants.
+///
+/// The constants are sorted by their offsets. The progress is reported to the given job
+/// and the scan is stopped if it is cancelled.
+pub fn scan(bytes: &[u8], elf: &Elf, job: &JobHandle) -> Vec<CryptoConstant> {
+    let signatures = signatures();
+    let pattern = signatures
+        .iter()
+        .map(|signature| {
+            signature
+                .bytes
+                .iter()
+                .map(|v| format!("\\x{v:02x}"))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+    let Ok(regex) = RegexBuilder::new(&pattern)
+        .unicode(false)
+        .size_limit(1 << 24)
+        .build()
+    else {
+        return Vec::new();
+    };
+    // The file is searched in chunks that overlap by the length of the longest signature.
+    let overlap = signatures
+        .iter()
+        .map(|signature| signature.bytes.len())
+        .max()
+        .unwrap_or_default();
+    let mut matches = Vec::new();
+    for start in (0..bytes.len()).step_by(PROGRESS_INTERVAL) {
+        job.set_progress(start, bytes.len());
+        if job.is_cancelled() {
+            break;
+        }
+        let end = bytes.len().min(start + PROGRESS_INTERVAL + overlap);
+        matches.extend(
+            regex
+                .find_iter(&bytes[start..end])
+                .filter(|m| m.start() < PROGRESS_INTERVAL)
+                .map(|m| (start + m.start(), m.as_bytes())),
+        );
+    }
+    let symbols = symbols(elf);
+    matches
+        .into_iter()
+        .filter_map(|(offset, value)| {
+            let signature = signatures.iter().find(|v| v.bytes == value)?;
+            let offset = offset as u64;
+            let address = elf.virtual_address(offset);
+            let section = elf.section_headers.section_at(offset);
+            Some(CryptoConstant {
+                algorithm: signature.algorithm,
+                name: signature.name.to_string(),
+                offset,
+                size: signature.bytes.len() as u64,
+                section: section.map(|(name, _)| name.to_string()),
+                address,
+                // Only the symbols in the same section are considered.
+                symbol: address.and_then(|address| {
+                    let index = symbols.partition_point(|(start, _)| *start <= address);
+                    symbols
+                        .get(index.checked_sub(1)?)
+                        .filter(|(start, _)| {
+                            section.is_none_or(|(_, header)| *start >= header.sh_addr)
+                        })
+                        .map(|(start, name)| (name.to_string(), address - start))
+                }),
+            })
+        })
+        .collect()
+}
+
+/// Returns the addresses and the demangled names of the functions and objects.
+fn symbols(elf: &Elf) -> Vec<(u64, String)> {
+    let mut symbols = elf
+        .symbols
+        .iter()
+        .chain(elf.dynamic_symbols.iter())
+        .filter(|(name, symbol)| {
+            !name.is_empty()
+                && !symbol.is_undefined()
+                && symbol.st_value != 0
+                && matches!(symbol.st_symtype(), abi::STT_FUNC | abi::STT_OBJECT)
+        })
+        .map(|(name, symbol)| {
+            (
+                symbol.st_value,
+                rustc_demangle::try_demangle(name)
+                    .map(|name| format!("{name:#}"))
+                    .unwrap_or_else(|_| name.to_string()),
+            )
+        })
+        .collect::<Vec<_>>();
+    symbols.sort();
+    symbols.dedup_by_key(|(address, _)| *address);
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use elf::{endian::AnyEndian, ElfBytes};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tables() {
+        let (sbox, inverse_sbox) = aes_sboxes();
+        assert_eq!([0x63, 0x7c, 0x77, 0x7b], sbox[..4]);
+        assert_eq!(0x16, sbox[255]);
+        assert_eq!([0x52, 0x09, 0x6a, 0xd5], inverse_sbox[..4]);
+        assert_eq!(
+            vec![0x00000000, 0x77073096, 0xee0e612c, 0x990951ba],
+            crc32_table(0xedb88320)[..4]
+        );
+    }
+
+    #[test]
+    fn test_scan() -> Result<()> {
+        let bytes = std::fs::read("/proc/self/exe")?;
+        let elf = Elf::try_from(ElfBytes::<AnyEndian>::minimal_parse(&bytes)?)?;
+        let mut data = vec![0; 16];
+        data.extend(aes_sboxes().0);
+        [
+            0x67452301u32,
+            0xefcdab89,
+            0x98badcfe,
+            0x10325476,
+            0xc3d2e1f0,
+        ]
+        .iter()
+        .for_each(|v| data.extend(v.to_be_bytes()));
+        data.extend(b"..expand 32-byte k..");
+        assert_eq!(
+            vec![
+                ("AES", String::from("S-box"), 16),
+                ("SHA-1", String::from("Initial values (BE)"), 272),
+                ("ChaCha20", String::from("Sigma"), 294),
+            ],
+            scan(&data, &elf, &JobHandle::default())
+                .into_iter()
+                .map(|v| (v.algorithm, v.name, v.offset))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
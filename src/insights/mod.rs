@@ -0,0 +1,47 @@
+/// Capabilities inferred from the imports.
+pub mod capabilities;
+
+/// Cryptographic constant detection.
+pub mod crypto;
+
+/// YARA-style pattern matching rules.
+pub mod rules;
+
+/// Insight information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InsightInfo {
+    /// Rule matches.
+    Rules,
+    /// Cryptographic constants.
+    Crypto,
+    /// Capabilities.
+    Capabilities,
+}
+
+impl InsightInfo {
+    /// Returns the title.
+    pub fn title(&self) -> &str {
+        match self {
+            InsightInfo::Rules => "Rules",
+            InsightInfo::Crypto => "Crypto",
+            InsightInfo::Capabilities => "Capabilities",
+        }
+    }
+
+    /// Returns the headers.
+    pub fn headers(&self) -> &[&str] {
+        match self {
+            InsightInfo::Rules => &["Rule", "Tags", "String", "Offset", "Size", "Data"],
+            InsightInfo::Crypto => &[
+                "Algorithm",
+                "Constant",
+                "Offset",
+                "Size",
+                "Address",
+                "Section",
+                "Symbol",
+            ],
+            InsightInfo::Capabilities => &["Capability", "Imports", "Description"],
+        }
+    }
+}
@@ -0,0 +1,1174 @@
+use crate::{
+    elf::Elf,
+    error::{Error, Result},
+    job::JobHandle,
+};
+use elf::abi;
+use regex::bytes::{Regex, RegexBuilder};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Maximum number of matches to keep for a single string.
+///
+/// The match count (`#a`) in the conditions is also limited by this value.
+pub const MATCH_LIMIT: usize = 1000;
+
+/// Maximum number of bytes to show for a match.
+const PREVIEW_LIMIT: usize = 64;
+
+/// File extensions of the rule files in a directory.
+const RULE_EXTENSIONS: &[&str] = &["yar", "yara"];
+
+/// Comparison operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<=`
+    Le,
+    /// `>=`
+    Ge,
+    /// `<`
+    Lt,
+    /// `>`
+    Gt,
+}
+
+impl Operator {
+    /// Operators along with their symbols.
+    ///
+    /// Longer symbols come first so that `<=` is not parsed as `<`.
+    const ALL: &[(&str, Operator)] = &[
+        ("==", Operator::Eq),
+        ("!=", Operator::Ne),
+        ("<=", Operator::Le),
+        (">=", Operator::Ge),
+        ("<", Operator::Lt),
+        (">", Operator::Gt),
+    ];
+
+    /// Compares the given values.
+    fn apply(self, lhs: u64, rhs: u64) -> bool {
+        match self {
+            Operator::Eq => lhs == rhs,
+            Operator::Ne => lhs != rhs,
+            Operator::Le => lhs <= rhs,
+            Operator::Ge => lhs >= rhs,
+            Operator::Lt => lhs < rhs,
+            Operator::Gt => lhs > rhs,
+        }
+    }
+}
+
+/// Numeric value in a condition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    /// Size of the file.
+    FileSize,
+    /// Number of matches of the string at the given index (`#a`).
+    Count(usize),
+    /// Literal number.
+    Number(u64),
+}
+
+/// Quantifier of a string set (e.g. `any of them`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Quantifier {
+    /// At least one string matches.
+    Any,
+    /// All of the strings match.
+    All,
+    /// None of the strings match.
+    None,
+    /// At least the given number of strings match.
+    AtLeast(u64),
+}
+
+/// Condition of a rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Condition {
+    /// Constant value.
+    Bool(bool),
+    /// Negation.
+    Not(Box<Condition>),
+    /// Conjunction.
+    And(Box<Condition>, Box<Condition>),
+    /// Disjunction.
+    Or(Box<Condition>, Box<Condition>),
+    /// String at the given index matches (`$a`).
+    Matched(usize),
+    /// String at the given index matches at the offset (`$a at 0`).
+    MatchedAt(usize, u64),
+    /// Comparison of two values (e.g. `#a > 2`).
+    Compare(Value, Operator, Value),
+    /// Quantified string set (e.g. `2 of ($a*)`).
+    Of(Quantifier, Vec<usize>),
+    /// Previous rule at the given index matches.
+    Rule(usize),
+    /// Section with the given name exists.
+    Section(String),
+    /// Undefined dynamic symbol with the given name exists.
+    Import(String),
+    /// Defined dynamic symbol with the given name exists.
+    Export(String),
+    /// Library with the given name is needed (`DT_NEEDED`).
+    Library(String),
+}
+
+/// Values that are used while evaluating a condition.
+struct Scope<'a> {
+    /// Size of the file.
+    file_size: u64,
+    /// ELF properties.
+    elf: &'a Elf,
+    /// Matches of the strings of the rule.
+    matches: &'a [Vec<StringMatch>],
+    /// Results of the previous rules.
+    results: &'a [bool],
+}
+
+impl Condition {
+    /// Evaluates the condition.
+    fn evaluate(&self, scope: &Scope) -> bool {
+        match self {
+            Condition::Bool(value) => *value,
+            Condition::Not(condition) => !condition.evaluate(scope),
+            Condition::And(lhs, rhs) => lhs.evaluate(scope) && rhs.evaluate(scope),
+            Condition::Or(lhs, rhs) => lhs.evaluate(scope) || rhs.evaluate(scope),
+            Condition::Matched(index) => !scope.matches[*index].is_empty(),
+            Condition::MatchedAt(index, offset) => {
+                scope.matches[*index].iter().any(|v| v.offset == *offset)
+            }
+            Condition::Compare(lhs, operator, rhs) => {
+                let value = |value: &Value| match value {
+                    Value::FileSize => scope.file_size,
+                    Value::Count(index) => scope.matches[*index].len() as u64,
+                    Value::Number(number) => *number,
+                };
+                operator.apply(value(lhs), value(rhs))
+            }
+            Condition::Of(quantifier, indices) => {
+                let matched = indices
+                    .iter()
+                    .filter(|index| !scope.matches[**index].is_empty())
+                    .count();
+                match quantifier {
+                    Quantifier::Any => matched > 0,
+                    Quantifier::All => matched == indices.len(),
+                    Quantifier::None => matched == 0,
+                    Quantifier::AtLeast(count) => matched as u64 >= *count,
+                }
+            }
+            Condition::Rule(index) => scope.results[*index],
+            Condition::Section(name) => scope
+                .elf
+                .section_headers
+                .iter()
+                .any(|(section, _)| section == name),
+            Condition::Import(name) => scope
+                .elf
+                .dynamic_symbols
+                .iter()
+                .any(|(symbol, v)| v.is_undefined() && symbol_name(symbol) == name),
+            Condition::Export(name) => scope
+                .elf
+                .dynamic_symbols
+                .iter()
+                .any(|(symbol, v)| !v.is_undefined() && symbol_name(symbol) == name),
+            Condition::Library(name) => scope
+                .elf
+                .dynamic
+                .strings(abi::DT_NEEDED)
+                .any(|library| library == name),
+        }
+    }
+}
+
+/// Returns the symbol name without the version (e.g. `@GLIBC_2.2.5`).
+fn symbol_name(name: &str) -> &str {
+    name.split('@').next().unwrap_or(name)
+}
+
+/// String (pattern) of a rule.
+#[derive(Clone, Debug)]
+struct Pattern {
+    /// Identifier without the `$` prefix.
+    identifier: String,
+    /// Compiled pattern.
+    regex: Regex,
+    /// Whether the match should be delimited by non-alphanumeric characters.
+    fullword: bool,
+}
+
+impl Pattern {
+    /// Returns the matches in the given bytes.
+    fn find(&self, bytes: &[u8]) -> Vec<StringMatch> {
+        self.regex
+            .find_iter(bytes)
+            .filter(|m| {
+                !self.fullword
+                    || (!m
+                        .start()
+                        .checked_sub(1)
+                        .is_some_and(|i| bytes[i].is_ascii_alphanumeric())
+                        && !bytes.get(m.end()).is_some_and(u8::is_ascii_alphanumeric))
+            })
+            .take(MATCH_LIMIT)
+            .map(|m| StringMatch {
+                identifier: self.identifier.clone(),
+                offset: m.start() as u64,
+                data: m.as_bytes().to_vec(),
+            })
+            .collect()
+    }
+}
+
+/// Pattern matching rule.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    /// Name of the rule.
+    pub name: String,
+    /// Tags.
+    pub tags: Vec<String>,
+    /// Metadata entries.
+    pub meta: Vec<(String, String)>,
+    /// Whether the rule is only used by other rules.
+    private: bool,
+    /// Strings.
+    patterns: Vec<Pattern>,
+    /// Condition.
+    condition: Condition,
+}
+
+/// Match of a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StringMatch {
+    /// Identifier of the string without the `$` prefix.
+    pub identifier: String,
+    /// File offset.
+    pub offset: u64,
+    /// Matched bytes.
+    pub data: Vec<u8>,
+}
+
+/// Match of a rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleMatch {
+    /// Name of the rule.
+    pub rule: String,
+    /// Tags of the rule.
+    pub tags: Vec<String>,
+    /// Metadata entries of the rule.
+    pub meta: Vec<(String, String)>,
+    /// Matched strings sorted by their offsets.
+    pub strings: Vec<StringMatch>,
+}
+
+impl RuleMatch {
+    /// Returns the matched string of each table row.
+    ///
+    /// A row is returned for each matched string, or a single row without a string
+    /// if the rule matched without any strings (e.g. via imports).
+    pub fn row_strings(&self) -> impl Iterator<Item = Option<&StringMatch>> {
+        self.strings
+            .iter()
+            .map(Some)
+            .chain(self.strings.is_empty().then_some(None))
+    }
+
+    /// Returns the table rows.
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        let tags = self.tags.join(" ");
+        self.row_strings()
+            .map(|string| match string {
+                Some(string) => {
+                    let mut data = string.data[..string.data.len().min(PREVIEW_LIMIT)]
+                        .escape_ascii()
+                        .to_string();
+                    if string.data.len() > PREVIEW_LIMIT {
+                        data.push('…');
+                    }
+                    vec![
+                        self.rule.to_string(),
+                        tags.to_string(),
+                        format!("${}", string.identifier),
+                        format!("{:#x}", string.offset),
+                        string.data.len().to_string(),
+                        data,
+                    ]
+                }
+                None => vec![
+                    self.rule.to_string(),
+                    tags.to_string(),
+                    String::from("-"),
+                    String::from("-"),
+                    String::from("-"),
+                    String::from("-"),
+                ],
+            })
+            .collect()
+    }
+}
+
+/// Set of rules.
+#[derive(Clone, Debug, Default)]
+pub struct RuleSet {
+    /// Rules in the order of their definitions.
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Parses the rules from the given source.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut rules = Self::default();
+        Parser::new(source).parse(&mut rules.rules)?;
+        Ok(rules)
+    }
+
+    /// Loads the rules from the given files.
+    ///
+    /// The `.yar` and `.yara` files are loaded recursively from the directories.
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        let mut files = Vec::new();
+        for path in paths {
+            collect_files(path, &mut files)?;
+        }
+        let mut rules = Self::default();
+        for file in files {
+            let source = fs::read_to_string(&file)?;
+            Parser::new(&source)
+                .parse(&mut rules.rules)
+                .map_err(|e| Error::RuleError(format!("{}:{e}", file.display())))?;
+        }
+        Ok(rules)
+    }
+
+    /// Returns the rules.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns `true` if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Matches the rules against the given file.
+    ///
+    /// Private rules are not returned. The progress is reported to the given job
+    /// and the matching is stopped if it is cancelled.
+    pub fn scan(&self, bytes: &[u8], elf: &Elf, job: &JobHandle) -> Vec<RuleMatch> {
+        let mut results = Vec::with_capacity(self.rules.len());
+        let mut matches = Vec::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            job.set_progress(i, self.rules.len());
+            if job.is_cancelled() {
+                break;
+            }
+            let strings = rule
+                .patterns
+                .iter()
+                .map(|pattern| pattern.find(bytes))
+                .collect::<Vec<_>>();
+            let matched = rule.condition.evaluate(&Scope {
+                file_size: bytes.len() as u64,
+                elf,
+                matches: &strings,
+                results: &results,
+            });
+            results.push(matched);
+            if matched && !rule.private {
+                let mut strings = strings.into_iter().flatten().collect::<Vec<_>>();
+                strings.sort_by_key(|v| v.offset);
+                matches.push(RuleMatch {
+                    rule: rule.name.to_string(),
+                    tags: rule.tags.clone(),
+                    meta: rule.meta.clone(),
+                    strings,
+                });
+            }
+        }
+        matches
+    }
+}
+
+/// Collects the rule files from the given path.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|v| v.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, files)?;
+        } else if entry
+            .extension()
+            .is_some_and(|v| RULE_EXTENSIONS.iter().any(|ext| v == *ext))
+        {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the regex pattern that matches the given byte.
+fn escape_byte(byte: u8) -> String {
+    format!("\\x{byte:02x}")
+}
+
+/// Converts a hex string (e.g. `{ 4D 5A ?? [2-4] ( 01 | 02 ) }`) to a regex pattern.
+fn hex_pattern(hex: &str) -> std::result::Result<String, String> {
+    let mut pattern = String::new();
+    let mut chars = hex.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut bytes = 0;
+    let mut depth = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                let range = chars.by_ref().take_while(|c| *c != ']').collect::<String>();
+                let parse = |v: &str| {
+                    v.parse::<u64>()
+                        .map_err(|_| format!("invalid jump: `[{range}]`"))
+                };
+                pattern += &match range.split_once('-') {
+                    None => format!(".{{{}}}", parse(&range)?),
+                    Some(("", "")) => String::from(".*"),
+                    Some((min, "")) => format!(".{{{},}}", parse(min)?),
+                    Some((min, max)) => {
+                        let min = if min.is_empty() { 0 } else { parse(min)? };
+                        let max = parse(max)?;
+                        if min > max {
+                            return Err(format!("invalid jump: `[{range}]`"));
+                        }
+                        format!(".{{{min},{max}}}")
+                    }
+                };
+            }
+            '(' => {
+                depth += 1;
+                pattern += "(?:";
+            }
+            '|' if depth > 0 => pattern.push('|'),
+            ')' if depth > 0 => {
+                depth -= 1;
+                pattern.push(')');
+            }
+            _ => {
+                let negated = c == '~';
+                let high = if negated { chars.next() } else { Some(c) };
+                let (Some(high), Some(low)) = (high, chars.next()) else {
+                    return Err(String::from("incomplete byte"));
+                };
+                let nibble = |c: char| match c {
+                    '?' => Ok(None),
+                    _ => c
+                        .to_digit(16)
+                        .map(|v| Some(v as u8))
+                        .ok_or_else(|| format!("invalid character: `{c}`")),
+                };
+                let class = match (nibble(high)?, nibble(low)?) {
+                    (None, None) => String::from("."),
+                    (Some(high), Some(low)) if !negated => escape_byte(high << 4 | low),
+                    (Some(high), Some(low)) => format!("[{}]", escape_byte(high << 4 | low)),
+                    (Some(high), None) => format!(
+                        "[{}-{}]",
+                        escape_byte(high << 4),
+                        escape_byte(high << 4 | 0xf)
+                    ),
+                    (None, Some(low)) => format!(
+                        "[{}]",
+                        (0..16)
+                            .map(|high| escape_byte(high << 4 | low))
+                            .collect::<String>()
+                    ),
+                };
+                if negated && class == "." {
+                    return Err(String::from("invalid negation: `~??`"));
+                }
+                pattern += &if negated {
+                    class.replacen('[', "[^", 1)
+                } else {
+                    class
+                };
+                bytes += 1;
+            }
+        }
+    }
+    if depth != 0 {
+        return Err(String::from("unbalanced parentheses"));
+    }
+    if bytes == 0 {
+        return Err(String::from("empty hex string"));
+    }
+    Ok(format!("(?s-u:{pattern})"))
+}
+
+/// Converts a text string to a regex pattern.
+fn text_pattern(text: &[u8], nocase: bool, ascii: bool, wide: bool) -> String {
+    let mut alternatives = Vec::new();
+    if ascii || !wide {
+        alternatives.push(text.iter().map(|v| escape_byte(*v)).collect::<String>());
+    }
+    if wide {
+        alternatives.push(
+            text.iter()
+                .map(|v| escape_byte(*v) + &escape_byte(0))
+                .collect::<String>(),
+        );
+    }
+    format!(
+        "(?s{}-u:{})",
+        if nocase { "i" } else { "" },
+        alternatives.join("|")
+    )
+}
+
+/// Rule parser.
+struct Parser<'a> {
+    /// Source of the rules.
+    source: &'a str,
+    /// Current position in the source (in bytes).
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Constructs a new instance.
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            position: 0,
+        }
+    }
+
+    /// Returns an error at the current position.
+    fn error(&self, message: impl AsRef<str>) -> Error {
+        let consumed = &self.source[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.len() - consumed.rfind('\n').map(|v| v + 1).unwrap_or(0) + 1;
+        Error::RuleError(format!("{line}:{column}: {}", message.as_ref()))
+    }
+
+    /// Returns the remaining source.
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    /// Skips the whitespace and the comments.
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                self.position += comment.find("*/").map(|v| v + 4).unwrap_or(trimmed.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Returns the next character without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    /// Returns `true` if the next token is the given one.
+    fn check(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let rest = self.rest();
+        rest.starts_with(token)
+            && !(token.ends_with(is_identifier_char)
+                && rest[token.len()..].starts_with(is_identifier_char))
+    }
+
+    /// Consumes the given token if it is next.
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.check(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    /// Consumes the given token or returns an error.
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{token}`")))
+        }
+    }
+
+    /// Parses an identifier.
+    fn identifier(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !is_identifier_char(c))
+            .unwrap_or(rest.len());
+        if length == 0 || rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.error("expected identifier"));
+        }
+        self.position += length;
+        Ok(rest[..length].to_string())
+    }
+
+    /// Parses a number with an optional `KB` or `MB` suffix.
+    fn number(&mut self) -> Result<u64> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let token = &rest[..length];
+        let (digits, multiplier) = if let Some(v) = token.strip_suffix("KB") {
+            (v, 1024)
+        } else if let Some(v) = token.strip_suffix("MB") {
+            (v, 1024 * 1024)
+        } else {
+            (token, 1)
+        };
+        let value = match digits.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => digits.parse(),
+        }
+        .map_err(|_| self.error(format!("invalid number: `{token}`")))?;
+        let value = value
+            .checked_mul(multiplier)
+            .ok_or_else(|| self.error("number is too large"))?;
+        self.position += length;
+        Ok(value)
+    }
+
+    /// Parses a quoted text string.
+    fn text(&mut self) -> Result<Vec<u8>> {
+        self.expect("\"")?;
+        let mut text = Vec::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position += i + 1;
+                    return Ok(text);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => text.push(b'\n'),
+                    Some('r') => text.push(b'\r'),
+                    Some('t') => text.push(b'\t'),
+                    Some('x') => {
+                        let hex = chars.by_ref().take(2).map(|(_, c)| c).collect::<String>();
+                        text.push(u8::from_str_radix(&hex, 16).map_err(|_| {
+                            self.error(format!("invalid escape sequence: `\\x{hex}`"))
+                        })?);
+                    }
+                    Some(c @ ('"' | '\\')) => text.push(c as u8),
+                    _ => return Err(self.error("invalid escape sequence")),
+                },
+                '\n' => break,
+                _ => text.extend(c.to_string().as_bytes()),
+            }
+        }
+        self.position -= 1;
+        Err(self.error("unterminated string"))
+    }
+
+    /// Parses the rules.
+    fn parse(&mut self, rules: &mut Vec<Rule>) -> Result<()> {
+        while self.peek().is_some() {
+            // Modules are not supported, the ELF properties are built-in.
+            if self.eat("import") {
+                self.text()?;
+                continue;
+            }
+            let private = self.eat("private");
+            self.expect("rule")?;
+            self.skip_whitespace();
+            let start = self.position;
+            let name = self.identifier()?;
+            if rules.iter().any(|rule| rule.name == name) {
+                self.position = start;
+                return Err(self.error(format!("duplicate rule: `{name}`")));
+            }
+            let mut tags = Vec::new();
+            if self.eat(":") {
+                while self.peek() != Some('{') {
+                    tags.push(self.identifier()?);
+                }
+            }
+            self.expect("{")?;
+            let mut meta = Vec::new();
+            if self.eat("meta") {
+                self.expect(":")?;
+                while !self.check("strings") && !self.check("condition") {
+                    let key = self.identifier()?;
+                    self.expect("=")?;
+                    let value = match self.peek() {
+                        Some('"') => String::from_utf8_lossy(&self.text()?).to_string(),
+                        Some(c) if c.is_ascii_digit() => self.number()?.to_string(),
+                        _ => self.identifier()?,
+                    };
+                    meta.push((key, value));
+                }
+            }
+            let mut patterns = Vec::new();
+            if self.eat("strings") {
+                self.expect(":")?;
+                while self.peek() == Some('$') {
+                    let pattern = self.pattern()?;
+                    if !pattern.identifier.is_empty()
+                        && patterns
+                            .iter()
+                            .any(|v: &Pattern| v.identifier == pattern.identifier)
+                    {
+                        return Err(self.error(format!(
+                            "duplicate string identifier: `${}`",
+                            pattern.identifier
+                        )));
+                    }
+                    patterns.push(pattern);
+                }
+            }
+            self.expect("condition")?;
+            self.expect(":")?;
+            let condition = self.or(rules, &patterns)?;
+            self.expect("}")?;
+            rules.push(Rule {
+                name,
+                tags,
+                meta,
+                private,
+                patterns,
+                condition,
+            });
+        }
+        Ok(())
+    }
+
+    /// Parses a string definition (e.g. `$a = "text" nocase`).
+    fn pattern(&mut self) -> Result<Pattern> {
+        self.expect("$")?;
+        let identifier = if self.rest().starts_with(is_identifier_char) {
+            self.identifier()?
+        } else {
+            String::new()
+        };
+        self.expect("=")?;
+        let mut text = None;
+        let pattern = match self.peek() {
+            Some('"') => {
+                text = Some(self.text()?);
+                String::new()
+            }
+            Some('{') => {
+                let rest = self.rest();
+                let end = rest
+                    .find('}')
+                    .ok_or_else(|| self.error("unterminated hex string"))?;
+                let pattern = hex_pattern(&rest[1..end]).map_err(|e| self.error(e))?;
+                self.position += end + 1;
+                pattern
+            }
+            Some('/') => {
+                let rest = &self.rest()[1..];
+                let mut escaped = false;
+                let end = rest
+                    .find(|c: char| {
+                        let end = c == '/' && !escaped;
+                        escaped = c == '\\' && !escaped;
+                        end || c == '\n'
+                    })
+                    .filter(|v| rest[*v..].starts_with('/'))
+                    .ok_or_else(|| self.error("unterminated regular expression"))?;
+                let body = &rest[..end];
+                self.position += end + 2;
+                let flags = self
+                    .rest()
+                    .find(|c: char| !matches!(c, 'i' | 's'))
+                    .unwrap_or(self.rest().len());
+                let flags = &self.rest()[..flags];
+                self.position += flags.len();
+                if flags.is_empty() {
+                    body.to_string()
+                } else {
+                    format!("(?{flags}){body}")
+                }
+            }
+            _ => return Err(self.error("expected text, hex or regular expression")),
+        };
+        let (mut nocase, mut ascii, mut wide, mut fullword) = (false, false, false, false);
+        loop {
+            if self.eat("nocase") {
+                nocase = true;
+            } else if self.eat("ascii") {
+                ascii = true;
+            } else if self.eat("wide") {
+                wide = true;
+            } else if self.eat("fullword") {
+                fullword = true;
+            } else if self.eat("private") {
+            } else {
+                break;
+            }
+        }
+        let pattern = match text {
+            Some(text) => text_pattern(&text, nocase, ascii, wide),
+            None if wide => {
+                return Err(self.error("`wide` is only supported for text strings"));
+            }
+            None => pattern,
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(nocase)
+            .unicode(false)
+            .build()
+            .map_err(|e| self.error(format!("invalid pattern `${identifier}`: {e}")))?;
+        Ok(Pattern {
+            identifier,
+            regex,
+            fullword,
+        })
+    }
+
+    /// Parses a disjunction.
+    fn or(&mut self, rules: &[Rule], patterns: &[Pattern]) -> Result<Condition> {
+        let mut condition = self.and(rules, patterns)?;
+        while self.eat("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and(rules, patterns)?));
+        }
+        Ok(condition)
+    }
+
+    /// Parses a conjunction.
+    fn and(&mut self, rules: &[Rule], patterns: &[Pattern]) -> Result<Condition> {
+        let mut condition = self.not(rules, patterns)?;
+        while self.eat("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.not(rules, patterns)?));
+        }
+        Ok(condition)
+    }
+
+    /// Parses a negation.
+    fn not(&mut self, rules: &[Rule], patterns: &[Pattern]) -> Result<Condition> {
+        if self.eat("not") {
+            Ok(Condition::Not(Box::new(self.not(rules, patterns)?)))
+        } else {
+            self.primary(rules, patterns)
+        }
+    }
+
+    /// Parses a primary expression.
+    fn primary(&mut self, rules: &[Rule], patterns: &[Pattern]) -> Result<Condition> {
+        if self.eat("(") {
+            let condition = self.or(rules, patterns)?;
+            self.expect(")")?;
+            return Ok(condition);
+        }
+        match self.peek() {
+            Some('$') => {
+                let index = self.string_index(patterns)?;
+                if self.eat("at") {
+                    Ok(Condition::MatchedAt(index, self.number()?))
+                } else {
+                    Ok(Condition::Matched(index))
+                }
+            }
+            Some('#') => {
+                self.position += 1;
+                let index = self.string_index(patterns)?;
+                self.comparison(Value::Count(index), patterns)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let number = self.number()?;
+                if self.eat("of") {
+                    Ok(Condition::Of(
+                        Quantifier::AtLeast(number),
+                        self.string_set(patterns)?,
+                    ))
+                } else {
+                    self.comparison(Value::Number(number), patterns)
+                }
+            }
+            _ => {
+                let start = self.position;
+                let word = self.identifier()?;
+                match word.as_str() {
+                    "true" => Ok(Condition::Bool(true)),
+                    "false" => Ok(Condition::Bool(false)),
+                    "filesize" => self.comparison(Value::FileSize, patterns),
+                    "any" | "all" | "none" => {
+                        self.expect("of")?;
+                        let quantifier = match word.as_str() {
+                            "any" => Quantifier::Any,
+                            "all" => Quantifier::All,
+                            _ => Quantifier::None,
+                        };
+                        Ok(Condition::Of(quantifier, self.string_set(patterns)?))
+                    }
+                    "section" | "import" | "export" | "library" => {
+                        self.expect("(")?;
+                        let name = String::from_utf8_lossy(&self.text()?).to_string();
+                        self.expect(")")?;
+                        Ok(match word.as_str() {
+                            "section" => Condition::Section(name),
+                            "import" => Condition::Import(name),
+                            "export" => Condition::Export(name),
+                            _ => Condition::Library(name),
+                        })
+                    }
+                    _ => rules
+                        .iter()
+                        .position(|rule| rule.name == word)
+                        .map(Condition::Rule)
+                        .ok_or_else(|| {
+                            self.position = start;
+                            self.error(format!("unknown identifier: `{word}`"))
+                        }),
+                }
+            }
+        }
+    }
+
+    /// Parses the index of a string identifier (e.g. `$a`).
+    fn string_index(&mut self, patterns: &[Pattern]) -> Result<usize> {
+        let start = self.position;
+        self.eat("$");
+        let identifier = self.identifier()?;
+        patterns
+            .iter()
+            .position(|v| v.identifier == identifier)
+            .ok_or_else(|| {
+                self.position = start;
+                self.error(format!("undefined string identifier: `${identifier}`"))
+            })
+    }
+
+    /// Parses the rest of a comparison.
+    fn comparison(&mut self, lhs: Value, patterns: &[Pattern]) -> Result<Condition> {
+        let operator = Operator::ALL
+            .iter()
+            .find(|(symbol, _)| self.eat(symbol))
+            .map(|(_, operator)| *operator)
+            .ok_or_else(|| self.error("expected comparison operator"))?;
+        let rhs = match self.peek() {
+            Some('#') => {
+                self.position += 1;
+                Value::Count(self.string_index(patterns)?)
+            }
+            Some(c) if c.is_ascii_digit() => Value::Number(self.number()?),
+            _ => {
+                self.expect("filesize")?;
+                Value::FileSize
+            }
+        };
+        Ok(Condition::Compare(lhs, operator, rhs))
+    }
+
+    /// Parses a string set (e.g. `them` or `($a, $b*)`).
+    fn string_set(&mut self, patterns: &[Pattern]) -> Result<Vec<usize>> {
+        if self.eat("them") {
+            return Ok((0..patterns.len()).collect());
+        }
+        self.expect("(")?;
+        let mut indices = Vec::new();
+        loop {
+            self.expect("$")?;
+            let identifier = if self.rest().starts_with(is_identifier_char) {
+                self.identifier()?
+            } else {
+                String::new()
+            };
+            let found = if self.rest().starts_with('*') {
+                self.position += 1;
+                patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, v)| v.identifier.starts_with(&identifier))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>()
+            } else {
+                patterns
+                    .iter()
+                    .position(|v| v.identifier == identifier)
+                    .into_iter()
+                    .collect()
+            };
+            if found.is_empty() {
+                return Err(self.error(format!("undefined string identifier: `${identifier}`")));
+            }
+            indices.extend(
+                found
+                    .into_iter()
+                    .filter(|i| !indices.contains(i))
+                    .collect::<Vec<_>>(),
+            );
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.expect(")")?;
+        Ok(indices)
+    }
+}
+
+/// Returns `true` if the character can be used in identifiers.
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elf::{endian::AnyEndian, ElfBytes};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_hex_pattern() {
+        assert_eq!(
+            Ok(String::from(
+                r"(?s-u:\x4d\x5a.[\x00-\x0f][\x0a\x1a\x2a\x3a\x4a\x5a\x6a\x7a\x8a\x9a\xaa\xba\xca\xda\xea\xfa])"
+            )),
+            hex_pattern("4D 5A ?? 0? ?A")
+        );
+        assert_eq!(
+            Ok(String::from(r"(?s-u:\x01.{2,4}(?:\x02|[^\x03]).{1,}.*)")),
+            hex_pattern("01 [2-4] (02 | ~03) [1-] [-]")
+        );
+        assert!(hex_pattern("").is_err());
+        assert!(hex_pattern("0").is_err());
+        assert!(hex_pattern("01 [4-2]").is_err());
+        assert!(hex_pattern("(01 | 02").is_err());
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let rules = RuleSet::parse(
+            r#"
+            import "elf"
+
+            // Comment.
+            private rule Base { condition: filesize > 1KB }
+
+            rule Test : first second {
+                meta:
+                    author = "orhun"
+                    version = 2
+                /* Multi-line
+                   comment. */
+                strings:
+                    $a1 = "text\x00" nocase wide ascii fullword
+                    $a2 = { 7F 45 4C 46 [0-4] ?? }
+                    $ = /ab+c/is
+                condition:
+                    Base and (2 of ($a*) or #a1 >= 3) and not $a2 at 0x10
+                        and any of them and section(".text")
+            }
+            "#,
+        )?;
+        assert_eq!(2, rules.rules().len());
+        let rule = &rules.rules()[1];
+        assert!(rules.rules()[0].private);
+        assert_eq!("Test", rule.name);
+        assert_eq!(vec!["first", "second"], rule.tags);
+        assert_eq!(
+            vec![
+                (String::from("author"), String::from("orhun")),
+                (String::from("version"), String::from("2"))
+            ],
+            rule.meta
+        );
+        assert_eq!(3, rule.patterns.len());
+        assert!(rule.patterns[0].fullword);
+        assert_eq!("", rule.patterns[2].identifier);
+        for (source, error) in [
+            (
+                "rule A { condition: $a }",
+                "1:21: undefined string identifier: `$a`",
+            ),
+            ("rule A { condition: B }", "1:21: unknown identifier: `B`"),
+            ("rule A { strings: $a = \"x", "1:24: unterminated string"),
+            (
+                "rule A { strings: $a = { 0G } condition: $a }",
+                "1:24: invalid character: `G`",
+            ),
+            (
+                "rule A { condition: true } rule A { condition: true }",
+                "1:33: duplicate rule: `A`",
+            ),
+            (
+                "rule A { condition: filesize }",
+                "1:30: expected comparison operator",
+            ),
+            (
+                "rule A { condition: filesize < 99999999999999999MB }",
+                "1:32: number is too large",
+            ),
+        ] {
+            assert_eq!(
+                format!("Rule error: `{error}`"),
+                RuleSet::parse(source).expect_err(source).to_string()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan() -> Result<()> {
+        let bytes = fs::read("/proc/self/exe")?;
+        let elf = Elf::try_from(ElfBytes::<AnyEndian>::minimal_parse(&bytes)?)?;
+        let data = b"\x7fELF..Hello binsider..h\0e\0l\0l\0o\0..xhellox";
+        let rules = RuleSet::parse(
+            r#"
+            rule Elf { strings: $magic = { 7F 45 4C 46 } condition: $magic at 0 }
+            rule Text : greeting {
+                strings:
+                    $a = "hello" nocase ascii wide fullword
+                condition:
+                    #a == 2 and Elf
+            }
+            rule Regex { strings: $a = /b[a-z]+r\./ condition: $a and section(".text") }
+            rule Missing { condition: section(".missing") or import("missing") }
+            rule Size { condition: filesize < 1KB or none of them }
+            "#,
+        )?;
+        let matches = rules.scan(data, &elf, &JobHandle::default());
+        assert_eq!(
+            vec!["Elf", "Text", "Regex", "Size"],
+            matches.iter().map(|v| v.rule.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                String::from("Hello"),
+                String::from("h\\x00e\\x00l\\x00l\\x00o\\x00")
+            ],
+            matches[1]
+                .rows()
+                .into_iter()
+                .map(|row| row[5].to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec!["Size", "", "-", "-", "-", "-"]],
+            matches[3].rows()
+        );
+        assert_eq!(
+            vec![Some(6), Some(22)],
+            matches[1]
+                .row_strings()
+                .map(|string| string.map(|v| v.offset))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![None], matches[3].row_strings().collect::<Vec<_>>());
+        Ok(())
+    }
+}
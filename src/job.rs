@@ -0,0 +1,242 @@
+use crate::error::Result;
+use crate::tui::event::Event;
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    mpsc, Arc,
+};
+use std::{mem, thread};
+
+/// Number of bytes to process between the progress updates.
+pub const PROGRESS_INTERVAL: usize = 1 << 20;
+
+/// Kind of a background job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobKind {
+    /// Extracting the strings.
+    Strings,
+    /// Disassembling the code for the cross-references.
+    Xrefs,
+    /// Matching the rules.
+    Rules,
+    /// Finding the cryptographic constants.
+    CryptoConstants,
+    /// Computing the file hashes.
+    Hashes,
+}
+
+impl JobKind {
+    /// Returns the title.
+    pub fn title(&self) -> &str {
+        match self {
+            JobKind::Strings => "Strings",
+            JobKind::Xrefs => "Disassembly",
+            JobKind::Rules => "Rules",
+            JobKind::CryptoConstants => "Crypto",
+            JobKind::Hashes => "Hashes",
+        }
+    }
+}
+
+/// Handle that is shared with a job for reporting the progress and checking cancellation.
+#[derive(Clone, Debug, Default)]
+pub struct JobHandle {
+    /// Progress in percent.
+    progress: Arc<AtomicUsize>,
+    /// Whether if the job is cancelled.
+    cancelled: Arc<AtomicBool>,
+    /// Whether if the job is finished.
+    finished: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    /// Sets the progress by the amount of the processed work.
+    pub fn set_progress(&self, done: usize, total: usize) {
+        let progress = done.saturating_mul(100) / total.max(1);
+        self.progress.store(progress.min(100), Ordering::Relaxed);
+    }
+
+    /// Returns the progress in percent.
+    pub fn progress(&self) -> usize {
+        self.progress.load(Ordering::Relaxed)
+    }
+
+    /// Cancels the job.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the job is cancelled.
+    ///
+    /// Jobs should check it periodically and return early.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the job is finished.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+}
+
+/// Background job.
+#[derive(Clone, Debug)]
+pub struct Job {
+    /// Kind of the job.
+    pub kind: JobKind,
+    /// Handle of the job.
+    pub handle: JobHandle,
+}
+
+/// Background jobs of a file.
+#[derive(Debug, Default)]
+pub struct Jobs {
+    /// Started jobs.
+    jobs: Vec<Job>,
+    /// Kinds of the jobs that are cancelled before finishing.
+    cancelled: Vec<JobKind>,
+    /// Errors of the failed jobs.
+    errors: Vec<(JobKind, String)>,
+}
+
+impl Jobs {
+    /// Runs the given task in the background and sends its result as an event.
+    ///
+    /// The running job of the same kind is cancelled. The result of a cancelled job is not sent.
+    pub fn spawn<T, F, E>(
+        &mut self,
+        kind: JobKind,
+        event_sender: mpsc::Sender<Event>,
+        task: F,
+        event: E,
+    ) where
+        T: Send + 'static,
+        F: FnOnce(&JobHandle) -> T + Send + 'static,
+        E: FnOnce(T) -> Event + Send + 'static,
+    {
+        self.jobs.retain(|job| {
+            if job.kind == kind {
+                job.handle.cancel();
+            }
+            job.kind != kind && !job.handle.is_finished()
+        });
+        self.cancelled.retain(|v| *v != kind);
+        self.errors.retain(|(v, _)| *v != kind);
+        let handle = JobHandle::default();
+        self.jobs.push(Job {
+            kind,
+            handle: handle.clone(),
+        });
+        thread::spawn(move || {
+            let result = task(&handle);
+            handle.finished.store(true, Ordering::Relaxed);
+            if !handle.is_cancelled() {
+                event_sender
+                    .send(event(result))
+                    .expect("failed to send job event");
+            }
+        });
+    }
+
+    /// Returns the running jobs.
+    pub fn running(&self) -> impl Iterator<Item = &Job> {
+        self.jobs
+            .iter()
+            .filter(|job| !job.handle.is_finished() && !job.handle.is_cancelled())
+    }
+
+    /// Cancels the running jobs (e.g. when the file is no longer active).
+    pub fn cancel(&mut self) {
+        for job in mem::take(&mut self.jobs) {
+            if !job.handle.is_finished() {
+                job.handle.cancel();
+                self.cancelled.push(job.kind);
+            }
+        }
+    }
+
+    /// Returns the value of a finished job or records its error.
+    ///
+    /// A failed job should not stop the other analyses, so the error is only shown.
+    pub fn result<T>(&mut self, kind: JobKind, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.errors.push((kind, e.to_string()));
+                None
+            }
+        }
+    }
+
+    /// Returns the error of the failed job.
+    pub fn error(&self, kind: JobKind) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|(v, _)| *v == kind)
+            .map(|(_, e)| e.as_str())
+    }
+
+    /// Returns the failed jobs along with their errors.
+    pub fn failed(&self) -> impl Iterator<Item = &(JobKind, String)> {
+        self.errors.iter()
+    }
+
+    /// Returns the kinds of the cancelled jobs so that they can be restarted.
+    pub fn take_cancelled(&mut self) -> Vec<JobKind> {
+        mem::take(&mut self.cancelled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_jobs() {
+        let (sender, receiver) = mpsc::channel();
+        let mut jobs = Jobs::default();
+        jobs.spawn(
+            JobKind::Strings,
+            sender.clone(),
+            |job| {
+                while !job.is_cancelled() {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            },
+            |_| Event::Tick,
+        );
+        jobs.spawn(
+            JobKind::Hashes,
+            sender,
+            |job| job.set_progress(1, 4),
+            |_| Event::Trace,
+        );
+        assert!(matches!(receiver.recv(), Ok(Event::Trace)));
+        assert_eq!(
+            vec![JobKind::Strings],
+            jobs.running().map(|job| job.kind).collect::<Vec<_>>()
+        );
+        jobs.cancel();
+        assert_eq!(0, jobs.running().count());
+        assert_eq!(vec![JobKind::Strings], jobs.take_cancelled());
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        assert_eq!(Some(1), jobs.result(JobKind::Hashes, Ok(1)));
+        assert_eq!(
+            None,
+            jobs.result::<()>(
+                JobKind::Xrefs,
+                Err(crate::error::Error::StringsError(String::from("x")))
+            )
+        );
+        assert!(jobs.error(JobKind::Xrefs).is_some());
+        assert_eq!(1, jobs.failed().count());
+
+        let handle = JobHandle::default();
+        handle.set_progress(3, 4);
+        assert_eq!(75, handle.progress());
+        handle.set_progress(5, 0);
+        assert_eq!(100, handle.progress());
+    }
+}
@@ -26,22 +26,92 @@ pub mod tracer;
 /// File information.
 pub mod file;
 
+/// Binary diff.
+pub mod diff;
+
+/// ABI compatibility checker.
+pub mod abi;
+
+/// Size analysis.
+pub mod size;
+
+/// String analysis.
+pub mod strings;
+
+/// Background jobs.
+pub mod job;
+
+/// Persistent analysis cache.
+pub mod cache;
+
+/// Cross-references.
+pub mod xrefs;
+
+/// File hashes.
+pub mod hashes;
+
+/// Library dependencies.
+pub mod deps;
+
+/// Insights (e.g. rule matches).
+pub mod insights;
+
+/// Directory scan.
+pub mod scan;
+
+/// Running process inspection.
+pub mod process;
+
 /// Common types that can be glob-imported for convenience.
 pub mod prelude;
 
-use args::Args;
-use file::FileInfo;
+use ::elf::{endian::AnyEndian, ElfBytes};
+use abi::{AbiReport, Verdict};
+use args::{Args, Commands};
+use cache::Cache;
+use console::style;
+use diff::Diff;
+use elf::Elf;
+use file::{FileData, FileInfo};
+use hashes::FileHashes;
+use insights::rules::RuleSet;
+use job::{JobHandle, JobKind};
 use prelude::*;
+use process::Process;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::{env, fs, io, path::PathBuf};
+use scan::Scan;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+};
 use tui::{state::State, ui::Tab, Tui};
 
 /// Runs binsider.
 pub fn run(mut args: Args) -> Result<()> {
+    if args.clear_cache {
+        if let Some(dir) = cache::dir() {
+            cache::clear(&dir)?;
+        }
+        if args.files.is_empty() && args.pid.is_none() && args.command.is_none() {
+            return Ok(());
+        }
+    }
+    match args.command.clone() {
+        Some(Commands::Diff { old, new }) => return run_diff(old, new, args),
+        Some(Commands::Abi { old, new }) => return check_abi(&old, &new).map(|_| ()),
+        None => {}
+    }
+    if let Some(pid) = args.pid {
+        return run_process(pid, args);
+    }
     if args.files.is_empty() {
         args.files.push(env::current_exe()?);
     }
+    if args.files.len() > 1 || args.files.iter().any(|path| path.is_dir()) {
+        return run_scan(args);
+    }
     let mut path = args.files[args.files.len() - 1].clone();
     let mut arguments = None;
     let path_str = path.to_string_lossy().to_string();
@@ -57,26 +127,193 @@ pub fn run(mut args: Args) -> Result<()> {
         }
         path = resolved_path;
     }
-    let file_data = fs::read(&path)?;
-    let bytes = file_data.as_slice();
-    let file_info = FileInfo::new(path.to_str().unwrap_or_default(), arguments, bytes)?;
-    let analyzer = Analyzer::new(file_info, args.min_strings_len, args.files.clone())?;
-    start_tui(analyzer, args)
+    if args.hashes {
+        return print_hashes(&path);
+    }
+    let file_data = Arc::new(FileData::open(&path)?);
+    let file_info = FileInfo::new(path.to_str().unwrap_or_default(), arguments, file_data)?;
+    let analyzer = Analyzer::new(
+        file_info,
+        args.min_strings_len,
+        args.files.clone(),
+        args.root.as_deref(),
+    )?;
+    start_tui(analyzer, None, None, args)
+}
+
+/// Runs binsider for analyzing a running process.
+fn run_process(pid: u32, mut args: Args) -> Result<()> {
+    let process = Process::new(pid)?;
+    // The executable might be deleted or replaced after the process is started.
+    let exe = Process::proc_path(pid, "exe");
+    if args.hashes {
+        return print_hashes(&exe);
+    }
+    let path = if process.exe.exists() {
+        process.exe.clone()
+    } else {
+        exe.clone()
+    };
+    let file_data = Arc::new(FileData::open(&exe)?);
+    let path_str = path.to_string_lossy().to_string();
+    args.files = vec![path];
+    let mut analyzer = Analyzer::new(
+        FileInfo::new(&path_str, None, file_data)?,
+        args.min_strings_len,
+        args.files.clone(),
+        args.root.as_deref(),
+    )?;
+    analyzer.process = Some(process);
+    if args.tab == Tab::General {
+        args.tab = Tab::Process;
+    }
+    start_tui(analyzer, None, None, args)
+}
+
+/// Runs binsider for scanning multiple files and directories.
+///
+/// The first ELF file that is found is opened for analysis.
+fn run_scan(mut args: Args) -> Result<()> {
+    let scan = Scan::new(&args.files)?;
+    args.files = vec![scan.entries[0].path.clone()];
+    let analyzer = load_file(&args.files[0], args.files.clone(), &args)?;
+    if args.tab == Tab::General {
+        args.tab = Tab::Scan;
+    }
+    start_tui(analyzer, None, Some(scan), args)
+}
+
+/// Runs binsider for comparing two files.
+fn run_diff(old: PathBuf, new: PathBuf, mut args: Args) -> Result<()> {
+    let (old_data, new_data) = (FileData::open(&old)?, FileData::open(&new)?);
+    let (old_path, new_path) = (
+        old.to_string_lossy().to_string(),
+        new.to_string_lossy().to_string(),
+    );
+    let old_analyzer = Analyzer::new(
+        FileInfo::new(&old_path, None, Arc::new(old_data))?,
+        args.min_strings_len,
+        vec![old],
+        args.root.as_deref(),
+    )?;
+    args.files = vec![new];
+    let analyzer = Analyzer::new(
+        FileInfo::new(&new_path, None, Arc::new(new_data))?,
+        args.min_strings_len,
+        args.files.clone(),
+        args.root.as_deref(),
+    )?;
+    let diff = Diff::new(&old_analyzer, &analyzer, args.min_strings_len)?;
+    if args.tab == Tab::General {
+        args.tab = Tab::Diff;
+    }
+    start_tui(analyzer, Some(diff), None, args)
+}
+
+/// Checks the ABI compatibility of two files and prints the report.
+///
+/// Returns the overall verdict.
+pub fn check_abi(old: &Path, new: &Path) -> Result<Verdict> {
+    let (old_data, new_data) = (FileData::open(old)?, FileData::open(new)?);
+    let old_elf = Elf::try_from(ElfBytes::<AnyEndian>::minimal_parse(&old_data)?)?;
+    let new_elf = Elf::try_from(ElfBytes::<AnyEndian>::minimal_parse(&new_data)?)?;
+    let report = AbiReport::new(&old_elf, &new_elf);
+    for change in &report.changes {
+        let verdict = match change.verdict {
+            Verdict::Compatible => style(change.verdict).green(),
+            Verdict::Suspect => style(change.verdict).yellow(),
+            Verdict::Breaking => style(change.verdict).red(),
+        };
+        println!(
+            "{verdict:<10} {:<16} {} {}",
+            change.kind,
+            style(&change.subject).bold(),
+            change.details
+        );
+    }
+    let verdict = report.verdict();
+    println!(
+        "{} → {}: {}",
+        old.display(),
+        new.display(),
+        style(verdict).bold()
+    );
+    Ok(verdict)
+}
+
+/// Prints the hashes of the file.
+pub fn print_hashes(path: &Path) -> Result<()> {
+    let data = FileData::open(path)?;
+    let elf = Elf::try_from(ElfBytes::<AnyEndian>::minimal_parse(&data)?)?;
+    for (name, value) in FileHashes::new(&data, &elf, &JobHandle::default()).items() {
+        println!("{:<12} {value}", style(name).bold());
+    }
+    Ok(())
+}
+
+/// Loads a file for analysis.
+///
+/// The file stays mapped while its analyzer is open so that switching
+/// between the open files does not require reloading.
+fn load_file(path: &Path, files: Vec<PathBuf>, args: &Args) -> Result<Analyzer<'static>> {
+    Analyzer::new(
+        FileInfo::new(
+            &path.to_string_lossy(),
+            None,
+            Arc::new(FileData::open(path)?),
+        )?,
+        args.min_strings_len,
+        files,
+        args.root.as_deref(),
+    )
+}
+
+/// Starts the background analysis of the active file.
+///
+/// The rules are loaded once and shared by the open files.
+fn start_analysis(state: &mut State, args: &Args, rules: &RuleSet, sender: mpsc::Sender<Event>) {
+    if !args.strings_encodings.is_empty() {
+        state.view.analyzer.strings_encodings = args.strings_encodings.clone();
+    }
+    state.view.analyzer.rules = rules.clone();
+    if !args.no_cache {
+        let modified = fs::metadata(&state.view.analyzer.file.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        state.view.analyzer.cache =
+            Cache::new(state.view.analyzer.elf.notes.build_id(), modified).map(Arc::new);
+    }
+    state.view.analyzer.extract_strings(sender.clone());
+    state.view.analyzer.extract_xrefs(sender.clone());
+    state.view.analyzer.extract_crypto_constants(sender.clone());
+    state.view.analyzer.extract_hashes(sender.clone());
+    if !state.view.analyzer.rules.is_empty() {
+        state.view.analyzer.match_rules(sender);
+    }
 }
 
 /// Starts the terminal user interface.
-pub fn start_tui(analyzer: Analyzer, args: Args) -> Result<()> {
+pub fn start_tui(
+    analyzer: Analyzer,
+    diff: Option<Diff>,
+    scan: Option<Scan>,
+    args: Args,
+) -> Result<()> {
     // Create an application.
     let mut state = State::new(analyzer, args.accent_color)?;
+    state.view.diff = diff;
+    state.scan = scan;
 
     // Change tab depending on cli arguments.
     state.set_tab(args.tab);
+    state.handle_tab()?;
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stdout());
     let terminal = Terminal::new(backend)?;
     let events = EventHandler::new(250);
-    state.analyzer.extract_strings(events.sender.clone());
+    let rules = RuleSet::load(&args.rules)?;
+    start_analysis(&mut state, &args, &rules, events.sender.clone());
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
 
@@ -89,11 +326,11 @@ pub fn start_tui(analyzer: Analyzer, args: Args) -> Result<()> {
             Event::Tick => {}
             Event::Key(key_event) => {
                 let command = if state.input_mode {
-                    Command::Input(InputCommand::parse(key_event, &state.input))
+                    Command::Input(InputCommand::parse(key_event, &state.view.input))
                 } else if state.show_heh {
                     Command::Hexdump(HexdumpCommand::parse(
                         key_event,
-                        state.analyzer.file.is_read_only,
+                        state.view.analyzer.file.is_read_only,
                     ))
                 } else {
                     Command::from(key_event)
@@ -104,51 +341,94 @@ pub fn start_tui(analyzer: Analyzer, args: Args) -> Result<()> {
                 state.run_command(Command::from(mouse_event), tui.events.sender.clone())?;
             }
             Event::Resize(_, _) => {}
-            Event::FileStrings(strings) => {
-                state.strings_loaded = true;
-                state.analyzer.strings = Some(strings?.into_iter().map(|(v, l)| (l, v)).collect());
-                if state.tab == Tab::Strings {
+            Event::FileStrings(id, strings) => {
+                if let Some(view) = state.file_view(id) {
+                    view.strings_loaded = true;
+                    let strings = view.analyzer.jobs.result(JobKind::Strings, strings);
+                    view.analyzer.set_strings(strings);
+                }
+                if id == state.view.analyzer.id && state.view.tab == Tab::Strings {
                     state.handle_tab()?;
                 }
             }
+            Event::Xrefs(id, xrefs) => {
+                if let Some(view) = state.file_view(id) {
+                    view.analyzer.xrefs = view.analyzer.jobs.result(JobKind::Xrefs, xrefs);
+                }
+            }
+            Event::RuleMatches(id, matches) => {
+                if let Some(view) = state.file_view(id) {
+                    view.analyzer.rule_matches = view.analyzer.jobs.result(JobKind::Rules, matches);
+                }
+                if id == state.view.analyzer.id && state.view.tab == Tab::Insights {
+                    state.handle_tab()?;
+                }
+            }
+            Event::CryptoConstants(id, constants) => {
+                if let Some(view) = state.file_view(id) {
+                    view.analyzer.crypto_constants = view
+                        .analyzer
+                        .jobs
+                        .result(JobKind::CryptoConstants, constants);
+                }
+                if id == state.view.analyzer.id && state.view.tab == Tab::Insights {
+                    state.handle_tab()?;
+                }
+            }
+            Event::FileHashes(id, hashes) => {
+                if let Some(view) = state.file_view(id) {
+                    view.analyzer.hashes = view.analyzer.jobs.result(JobKind::Hashes, hashes);
+                }
+            }
             #[cfg(feature = "dynamic-analysis")]
             Event::Trace => {
-                state.system_calls_loaded = false;
+                state.view.system_calls_loaded = false;
                 tui.toggle_pause()?;
-                tracer::trace_syscalls(&state.analyzer.file, tui.events.sender.clone());
+                tracer::trace_syscalls(&state.view.analyzer.file, tui.events.sender.clone());
             }
             #[cfg(feature = "dynamic-analysis")]
             Event::TraceResult(syscalls) => {
-                state.analyzer.tracer = match syscalls {
+                state.view.analyzer.tracer = match syscalls {
                     Ok(v) => v,
                     Err(e) => TraceData {
                         syscalls: console::style(e).red().to_string().as_bytes().to_vec(),
                         ..Default::default()
                     },
                 };
-                state.system_calls_loaded = true;
-                state.dynamic_scroll_index = 0;
+                state.view.system_calls_loaded = true;
+                state.view.dynamic_scroll_index = 0;
                 tui.toggle_pause()?;
                 state.handle_tab()?;
             }
             #[cfg(not(feature = "dynamic-analysis"))]
             Event::Trace | Event::TraceResult(_) => {}
-            Event::Restart(path) => {
-                let mut args = args.clone();
-                match path {
-                    Some(path) => {
-                        args.files.push(path);
+            Event::OpenFile(path) => match state.find_file(&path) {
+                Some(id) => state.switch_file(id, tui.events.sender.clone())?,
+                None => {
+                    // Files that are opened from the scan do not have a parent.
+                    let mut files = if state.view.tab == Tab::Scan {
+                        Vec::new()
+                    } else {
+                        state.view.analyzer.files.clone()
+                    };
+                    files.push(path.clone());
+                    // A file that cannot be opened should not end the session.
+                    state.error = None;
+                    match load_file(&path, files, &args)
+                        .and_then(|analyzer| state.open_file(analyzer))
+                    {
+                        Ok(()) => {
+                            start_analysis(&mut state, &args, &rules, tui.events.sender.clone())
+                        }
+                        Err(e) => {
+                            state.error = Some(format!(
+                                "{} cannot be opened: {e}",
+                                path.file_name().unwrap_or_default().to_string_lossy()
+                            ))
+                        }
                     }
-                    None => {
-                        args.files.pop();
-                    }
-                }
-                if !args.files.is_empty() {
-                    tui.exit()?;
-                    state.running = false;
-                    run(args)?;
                 }
-            }
+            },
         }
     }
 
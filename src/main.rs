@@ -1,4 +1,4 @@
-use binsider::args::Args;
+use binsider::args::{Args, Commands};
 use binsider::error::Result;
 use clap::Parser;
 use ratatui::style::Color;
@@ -19,6 +19,15 @@ fn main() -> Result<()> {
             })
             .ok();
     }
+    if let Some(Commands::Abi { old, new }) = &args.command {
+        match binsider::check_abi(old, new) {
+            Ok(verdict) => process::exit(verdict.exit_code()),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1)
+            }
+        }
+    }
     match binsider::run(args) {
         Ok(_) => process::exit(0),
         Err(e) => {
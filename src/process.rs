@@ -0,0 +1,298 @@
+use crate::deps::DependencyTree;
+use crate::elf::Elf;
+use crate::error::{Error, Result};
+use crate::scan;
+use bytesize::ByteSize;
+use elf::abi;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Suffix of the paths of the deleted files in `/proc/<pid>/maps`.
+const DELETED_SUFFIX: &str = " (deleted)";
+
+/// Process info.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessInfo {
+    /// Memory mappings.
+    Maps,
+    /// Loaded libraries.
+    Libraries,
+}
+
+impl ProcessInfo {
+    /// Returns the title.
+    pub fn title(&self) -> &str {
+        match self {
+            ProcessInfo::Maps => "Memory Map",
+            ProcessInfo::Libraries => "Libraries",
+        }
+    }
+
+    /// Returns the headers.
+    pub fn headers(&self) -> &[&str] {
+        match self {
+            ProcessInfo::Maps => &["Start", "End", "Perms", "Offset", "Size", "Segment", "Path"],
+            ProcessInfo::Libraries => &["Library", "Status", "Path"],
+        }
+    }
+}
+
+/// Memory mapping of a process.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mapping {
+    /// Start address.
+    pub start: u64,
+    /// End address.
+    pub end: u64,
+    /// Permissions (e.g. `r-xp`).
+    pub perms: String,
+    /// Offset in the mapped file.
+    pub offset: u64,
+    /// Inode of the mapped file (`0` for the anonymous mappings).
+    pub inode: u64,
+    /// Path of the mapped file or the name of the mapping (e.g. `[heap]`).
+    pub path: Option<String>,
+}
+
+impl Mapping {
+    /// Returns the path of the mapped file.
+    pub fn file(&self) -> Option<&str> {
+        self.path
+            .as_deref()
+            .filter(|_| self.inode != 0)
+            .map(|path| path.trim_end_matches(DELETED_SUFFIX))
+    }
+}
+
+/// Running process.
+#[derive(Clone, Debug)]
+pub struct Process {
+    /// Process ID.
+    pub pid: u32,
+    /// Path of the executable.
+    pub exe: PathBuf,
+    /// Memory mappings.
+    pub maps: Vec<Mapping>,
+    /// Whether if the process was running during the last refresh.
+    pub running: bool,
+}
+
+impl Process {
+    /// Constructs a new instance by reading the `/proc` entries of the process.
+    pub fn new(pid: u32) -> Result<Self> {
+        let exe = fs::read_link(Self::proc_path(pid, "exe"))
+            .map_err(|e| Error::ProcessError(format!("cannot access process {pid}: {e}")))?;
+        let mut process = Self {
+            pid,
+            exe: PathBuf::from(exe.to_string_lossy().trim_end_matches(DELETED_SUFFIX)),
+            maps: Vec::new(),
+            running: true,
+        };
+        process.refresh()?;
+        Ok(process)
+    }
+
+    /// Returns the path of the given `/proc` entry of the process.
+    pub fn proc_path(pid: u32, entry: &str) -> PathBuf {
+        PathBuf::from(format!("/proc/{pid}/{entry}"))
+    }
+
+    /// Re-reads the memory mappings.
+    ///
+    /// The last known mappings are kept if the process is exited.
+    pub fn refresh(&mut self) -> Result<()> {
+        match fs::read_to_string(Self::proc_path(self.pid, "maps")) {
+            Ok(maps) => {
+                self.maps = parse_maps(&maps);
+                self.running = true;
+                Ok(())
+            }
+            Err(e) if self.maps.is_empty() => Err(Error::ProcessError(format!(
+                "cannot read the memory map of process {}: {e}",
+                self.pid
+            ))),
+            Err(_) => {
+                self.running = false;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the paths of the loaded libraries in the order of mapping.
+    pub fn libraries(&self) -> Vec<&str> {
+        let mut libraries = Vec::new();
+        for path in self.maps.iter().filter_map(Mapping::file) {
+            if Path::new(path) != self.exe
+                && !libraries.contains(&path)
+                && scan::is_elf(Path::new(path))
+            {
+                libraries.push(path);
+            }
+        }
+        libraries
+    }
+
+    /// Returns the rows of the memory mappings.
+    ///
+    /// The mappings of the executable are joined with the loadable segments.
+    pub fn map_rows(&self, elf: &Elf) -> Vec<Vec<String>> {
+        let segments = elf
+            .program_headers
+            .inner()
+            .iter()
+            .filter(|v| v.p_type == abi::PT_LOAD)
+            .collect::<Vec<_>>();
+        self.maps
+            .iter()
+            .map(|mapping| {
+                let segment = if mapping.file().map(Path::new) == Some(self.exe.as_path()) {
+                    segments
+                        .iter()
+                        .position(|v| {
+                            let page_offset = v.p_offset - v.p_offset % v.p_align.max(1);
+                            (page_offset..v.p_offset.saturating_add(v.p_filesz.max(1)))
+                                .contains(&mapping.offset)
+                        })
+                        .map(|i| {
+                            let flags = segments[i].p_flags;
+                            format!(
+                                "LOAD {i} ({}{}{})",
+                                if flags & abi::PF_R != 0 { "R" } else { "-" },
+                                if flags & abi::PF_W != 0 { "W" } else { "-" },
+                                if flags & abi::PF_X != 0 { "X" } else { "-" },
+                            )
+                        })
+                } else {
+                    None
+                };
+                vec![
+                    format!("{:#x}", mapping.start),
+                    format!("{:#x}", mapping.end),
+                    mapping.perms.clone(),
+                    format!("{:#x}", mapping.offset),
+                    ByteSize(mapping.end - mapping.start).to_string(),
+                    segment.unwrap_or_else(|| String::from("-")),
+                    mapping.path.clone().unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns the rows of the libraries (name, status and path).
+    ///
+    /// The loaded libraries are compared with the library dependencies:
+    ///
+    /// - `loaded`: needed and loaded
+    /// - `dlopen`: loaded at runtime (e.g. via `dlopen(3)`)
+    /// - `not loaded`: needed but not loaded
+    pub fn library_rows(&self, dependencies: &DependencyTree) -> Vec<Vec<String>> {
+        let mut needed = Vec::new();
+        for library in &dependencies.libraries {
+            if !needed
+                .iter()
+                .any(|(_, path)| *path == library.display_path())
+            {
+                needed.push((library.name.as_str(), library.display_path()));
+            }
+        }
+        let loaded = self.libraries();
+        let mut rows = loaded
+            .iter()
+            .map(|path| {
+                let canonical = fs::canonicalize(path).ok();
+                let name = needed
+                    .iter()
+                    .find(|(_, needed)| {
+                        *needed == Some(Path::new(path)) || *needed == canonical.as_deref()
+                    })
+                    .map(|(name, _)| name.to_string());
+                vec![
+                    name.clone().unwrap_or_else(|| {
+                        Path::new(path)
+                            .file_name()
+                            .map(|v| v.to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    }),
+                    String::from(if name.is_some() { "loaded" } else { "dlopen" }),
+                    path.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        for (name, path) in needed {
+            if !rows.iter().any(|row| row[0] == name && row[1] == "loaded") {
+                rows.push(vec![
+                    name.to_string(),
+                    String::from("not loaded"),
+                    path.map(|v| v.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                ]);
+            }
+        }
+        rows
+    }
+}
+
+/// Parses the memory mappings in the format of `/proc/<pid>/maps`.
+fn parse_maps(maps: &str) -> Vec<Mapping> {
+    maps.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (start, end) = fields.next()?.split_once('-')?;
+            let perms = fields.next()?.to_string();
+            let offset = fields.next()?;
+            let _device = fields.next()?;
+            let inode = fields.next()?.parse().ok()?;
+            // The path can contain whitespace.
+            let path = line
+                .splitn(6, char::is_whitespace)
+                .nth(5)
+                .map(str::trim_start)
+                .filter(|v| !v.is_empty())
+                .map(String::from);
+            Some(Mapping {
+                start: u64::from_str_radix(start, 16).ok()?,
+                end: u64::from_str_radix(end, 16).ok()?,
+                perms,
+                offset: u64::from_str_radix(offset, 16).ok()?,
+                inode,
+                path,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_maps() {
+        let maps = parse_maps(
+            "\
+5581f9a00000-5581f9a04000 r--p 00000000 fd:01 1835114                    /usr/bin/cat
+5581fb2c1000-5581fb2e2000 rw-p 00000000 00:00 0                          [heap]
+7f0d8e400000-7f0d8e428000 r--p 00000000 fd:01 1838362                    /opt/my app/lib.so (deleted)
+7f0d8e600000-7f0d8e601000 rw-p 00000000 00:00 0
+",
+        );
+        assert_eq!(4, maps.len());
+        assert_eq!(
+            Mapping {
+                start: 0x5581f9a00000,
+                end: 0x5581f9a04000,
+                perms: String::from("r--p"),
+                offset: 0,
+                inode: 1835114,
+                path: Some(String::from("/usr/bin/cat")),
+            },
+            maps[0]
+        );
+        assert_eq!(Some("[heap]"), maps[1].path.as_deref());
+        assert_eq!(None, maps[1].file());
+        assert_eq!(Some("/opt/my app/lib.so"), maps[2].file());
+        assert_eq!(None, maps[3].path);
+    }
+}
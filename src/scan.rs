@@ -0,0 +1,259 @@
+use crate::elf::Elf;
+use crate::error::{Error, Result};
+use crate::file::FileData;
+use bytesize::ByteSize;
+use elf::{abi, endian::AnyEndian, ElfBytes};
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Headers of the scan table.
+pub const SCAN_HEADERS: &[&str] = &[
+    "Path", "Type", "Arch", "Size", "Stripped", "PIE", "RELRO", "NX", "Deps", "GLIBC",
+];
+
+/// Prefix of the glibc version requirements.
+const GLIBC_PREFIX: &str = "GLIBC_";
+
+/// Relocation read-only (RELRO) protection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relro {
+    /// No `PT_GNU_RELRO` segment.
+    None,
+    /// `PT_GNU_RELRO` segment without immediate binding.
+    Partial,
+    /// `PT_GNU_RELRO` segment with immediate binding (`BIND_NOW`).
+    Full,
+}
+
+impl Relro {
+    /// Returns the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Relro::None => "no",
+            Relro::Partial => "partial",
+            Relro::Full => "full",
+        }
+    }
+}
+
+/// Summary of a scanned ELF file.
+#[derive(Clone, Debug)]
+pub struct ScanEntry {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Type of the file (e.g. executable).
+    pub kind: &'static str,
+    /// Architecture.
+    pub arch: String,
+    /// File size.
+    pub size: u64,
+    /// Whether if the symbol table (`.symtab`) is removed.
+    pub stripped: bool,
+    /// Whether if the executable is position independent.
+    ///
+    /// It is not applicable to shared objects and relocatable files.
+    pub pie: Option<bool>,
+    /// RELRO protection (if there are program headers).
+    pub relro: Option<Relro>,
+    /// Whether if the stack is not executable (if there are program headers).
+    pub nx: Option<bool>,
+    /// Number of the needed libraries (`DT_NEEDED`).
+    pub dependencies: usize,
+    /// Highest required glibc version (e.g. `2.34`).
+    pub glibc: Option<String>,
+}
+
+impl ScanEntry {
+    /// Constructs a new instance from the contents of the file.
+    pub fn new(path: PathBuf, bytes: &[u8]) -> Result<Self> {
+        let elf = Elf::try_from(ElfBytes::<AnyEndian>::minimal_parse(bytes)?)?;
+        let header = elf.file_headers.inner();
+        let segments = elf.program_headers.inner();
+        let has_segment = |p_type| segments.iter().any(|v| v.p_type == p_type);
+        let flags = elf.dynamic.value(abi::DT_FLAGS).unwrap_or_default();
+        let flags_1 = elf.dynamic.value(abi::DT_FLAGS_1).unwrap_or_default();
+        let bind_now = elf.dynamic.value(abi::DT_BIND_NOW).is_some()
+            || flags & abi::DF_BIND_NOW as u64 != 0
+            || flags_1 & abi::DF_1_NOW as u64 != 0;
+        // Older linkers do not set `DF_1_PIE`, so the position independent executables
+        // are also recognized by the interpreter (shared objects such as libc can have one too).
+        let pie = match header.e_type {
+            abi::ET_EXEC => Some(false),
+            abi::ET_DYN
+                if flags_1 & abi::DF_1_PIE as u64 != 0
+                    || (has_segment(abi::PT_INTERP) && elf.dynamic.soname().is_none()) =>
+            {
+                Some(true)
+            }
+            _ => None,
+        };
+        let kind = match header.e_type {
+            abi::ET_EXEC => "executable",
+            abi::ET_DYN if pie.is_some() => "executable",
+            abi::ET_DYN => "shared object",
+            abi::ET_REL => "relocatable",
+            abi::ET_CORE => "core",
+            _ => "unknown",
+        };
+        let has_segments = !segments.is_empty();
+        let stripped = elf.symbols.iter().next().is_none();
+        Ok(Self {
+            path,
+            kind,
+            arch: match elf::to_str::e_machine_to_str(header.e_machine) {
+                Some(s) => s.trim_start_matches("EM_").to_string(),
+                None => format!("{:#x}", header.e_machine),
+            },
+            size: bytes.len() as u64,
+            stripped,
+            pie,
+            relro: has_segments.then(|| {
+                if !has_segment(abi::PT_GNU_RELRO) {
+                    Relro::None
+                } else if bind_now {
+                    Relro::Full
+                } else {
+                    Relro::Partial
+                }
+            }),
+            nx: has_segments.then(|| {
+                segments
+                    .iter()
+                    .find(|v| v.p_type == abi::PT_GNU_STACK)
+                    .is_some_and(|v| v.p_flags & abi::PF_X == 0)
+            }),
+            dependencies: elf.dynamic.strings(abi::DT_NEEDED).count(),
+            glibc: (0..elf.dynamic_symbols.iter().count())
+                .filter_map(|i| elf.dynamic_symbols.requirement(i))
+                .filter_map(glibc_version)
+                .max_by(|a, b| version_key(a).cmp(&version_key(b)))
+                .map(String::from),
+        })
+    }
+
+    /// Returns the table row.
+    pub fn row(&self) -> Vec<String> {
+        let flag = |v: Option<bool>| match v {
+            Some(true) => String::from("yes"),
+            Some(false) => String::from("no"),
+            None => String::from("-"),
+        };
+        vec![
+            self.path.to_string_lossy().to_string(),
+            self.kind.to_string(),
+            self.arch.clone(),
+            ByteSize(self.size).to_string(),
+            flag(Some(self.stripped)),
+            flag(self.pie),
+            self.relro.map_or("-", |v| v.as_str()).to_string(),
+            flag(self.nx),
+            self.dependencies.to_string(),
+            self.glibc.clone().unwrap_or_else(|| String::from("-")),
+        ]
+    }
+}
+
+/// Summary of the ELF files in the scanned paths.
+#[derive(Clone, Debug, Default)]
+pub struct Scan {
+    /// Scanned ELF files.
+    pub entries: Vec<ScanEntry>,
+    /// Number of the ELF files that could not be read or parsed.
+    pub skipped: usize,
+}
+
+impl Scan {
+    /// Scans the given files and directories.
+    ///
+    /// The directories are scanned recursively without following the symbolic links.
+    pub fn new(paths: &[PathBuf]) -> Result<Self> {
+        let mut files = Vec::new();
+        for path in paths {
+            collect_files(path, &mut files)?;
+        }
+        let mut scan = Self::default();
+        for file in files {
+            match FileData::open(&file).and_then(|data| ScanEntry::new(file.clone(), &data)) {
+                Ok(entry) => scan.entries.push(entry),
+                Err(_) => scan.skipped += 1,
+            }
+        }
+        if scan.entries.is_empty() {
+            return Err(Error::ScanError(String::from("no ELF files found")));
+        }
+        Ok(scan)
+    }
+
+    /// Returns the table rows.
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        self.entries.iter().map(ScanEntry::row).collect()
+    }
+}
+
+/// Collects the ELF files from the given path.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !path.is_dir() {
+        if is_elf(path) {
+            files.push(path.to_path_buf());
+        }
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|v| v.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        let Ok(metadata) = fs::symlink_metadata(&entry) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            // Unreadable directories (e.g. due to permissions) are skipped.
+            let _ = collect_files(&entry, files);
+        } else if metadata.is_file() && is_elf(&entry) {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the file starts with the ELF magic.
+pub(crate) fn is_elf(path: &Path) -> bool {
+    let mut magic = [0; 4];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| magic == abi::ELFMAGIC)
+}
+
+/// Returns the glibc version of the given version requirement (e.g. `GLIBC_2.34`).
+fn glibc_version(requirement: &str) -> Option<&str> {
+    requirement
+        .strip_prefix(GLIBC_PREFIX)
+        .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Returns the sort key of the given version (e.g. `[2, 34]`).
+fn version_key(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|v| v.parse().unwrap_or_default())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_glibc_version() {
+        assert_eq!(Some("2.34"), glibc_version("GLIBC_2.34"));
+        assert_eq!(None, glibc_version("GLIBC_PRIVATE"));
+        assert_eq!(None, glibc_version("GCC_3.0"));
+        let mut versions = vec!["2.3.4", "2.34", "2.2.5", "2.17"];
+        versions.sort_by_key(|v| version_key(v));
+        assert_eq!(vec!["2.2.5", "2.3.4", "2.17", "2.34"], versions);
+    }
+}
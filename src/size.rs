@@ -0,0 +1,388 @@
+use crate::elf::Elf;
+use bytesize::ByteSize;
+use elf::abi;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// Name of the pseudo-section for the ELF file header and the header tables.
+pub const ELF_HEADERS: &str = "[ELF headers]";
+
+/// Name of the pseudo-section for the bytes that are not in any section.
+pub const UNACCOUNTED: &str = "[Unaccounted]";
+
+/// Name of the pseudo-crate for the symbols that are not mangled by rustc.
+pub const NON_RUST: &str = "[non-Rust]";
+
+/// Size information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeInfo {
+    /// Sizes of the sections.
+    Sections,
+    /// Sizes of the symbols.
+    Symbols,
+    /// Sizes of the crates.
+    Crates,
+    /// Treemap of the sections and symbols.
+    Treemap,
+}
+
+impl SizeInfo {
+    /// Returns the title.
+    pub fn title(&self) -> &str {
+        match self {
+            SizeInfo::Sections => "Sections",
+            SizeInfo::Symbols => "Symbols",
+            SizeInfo::Crates => "Crates",
+            SizeInfo::Treemap => "Treemap",
+        }
+    }
+
+    /// Returns the headers.
+    pub fn headers(&self) -> &[&str] {
+        match self {
+            SizeInfo::Sections => &["Section", "File size", "VM size", "File %", "VM %"],
+            SizeInfo::Symbols => &["Symbol", "File size", "VM size", "File %", "VM %"],
+            SizeInfo::Crates => &["Crate", "File size", "VM size", "File %", "VM %"],
+            SizeInfo::Treemap => &["Name", "File size", "VM size", "File %", "VM %"],
+        }
+    }
+}
+
+/// Size of a symbol.
+#[derive(Clone, Debug, Default)]
+struct SymbolSize {
+    /// Size entry.
+    entry: SizeEntry,
+    /// Demangled Rust symbol path.
+    demangled: Option<String>,
+    /// Name of the section that contains the symbol.
+    section: Option<String>,
+}
+
+/// Size of an item.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SizeEntry {
+    /// Name of the item.
+    pub name: String,
+    /// Size in the file.
+    pub file_size: u64,
+    /// Size in the memory.
+    pub vm_size: u64,
+}
+
+/// Size breakdown of an ELF file.
+#[derive(Clone, Debug, Default)]
+pub struct SizeReport {
+    /// Size of the file.
+    pub file_size: u64,
+    /// Size of the loaded segments in the memory.
+    pub vm_size: u64,
+    /// Section sizes.
+    sections: Vec<SizeEntry>,
+    /// Symbol sizes.
+    symbols: Vec<SizeEntry>,
+    /// Sections of the symbols.
+    symbol_sections: Vec<Option<String>>,
+    /// Crate sizes.
+    crates: Vec<SizeEntry>,
+}
+
+impl SizeReport {
+    /// Constructs a new instance from the given ELF file and its size.
+    pub fn new(elf: &Elf, file_size: u64) -> Self {
+        let vm_size = elf
+            .program_headers
+            .inner()
+            .iter()
+            .filter(|header| header.p_type == abi::PT_LOAD)
+            .fold(0, |size: u64, header| size.saturating_add(header.p_memsz));
+        let sections = sections(elf, file_size, vm_size);
+        let symbols = symbols(elf);
+        let crates = crates(&symbols);
+        Self {
+            file_size,
+            vm_size,
+            sections,
+            symbol_sections: symbols
+                .iter()
+                .map(|symbol| symbol.section.clone())
+                .collect(),
+            symbols: symbols.into_iter().map(|symbol| symbol.entry).collect(),
+            crates,
+        }
+    }
+
+    /// Returns the entries of the given category (the largest first).
+    pub fn entries(&self, info: &SizeInfo) -> &[SizeEntry] {
+        match info {
+            SizeInfo::Sections | SizeInfo::Treemap => &self.sections,
+            SizeInfo::Symbols => &self.symbols,
+            SizeInfo::Crates => &self.crates,
+        }
+    }
+
+    /// Returns the symbol entries in the given section (the largest first).
+    pub fn section_symbols<'a>(
+        &'a self,
+        section: &'a str,
+    ) -> impl Iterator<Item = &'a SizeEntry> + 'a {
+        self.symbols
+            .iter()
+            .zip(self.symbol_sections.iter())
+            .filter(move |(_, symbol_section)| symbol_section.as_deref() == Some(section))
+            .map(|(entry, _)| entry)
+    }
+
+    /// Returns the non-empty entries that are shown in the treemap.
+    ///
+    /// These are the sections or the symbols of the given section.
+    pub fn treemap_entries<'a>(&'a self, section: Option<&'a str>) -> Vec<&'a SizeEntry> {
+        match section {
+            Some(section) => self.section_symbols(section).collect::<Vec<_>>(),
+            None => self.sections.iter().collect(),
+        }
+        .into_iter()
+        .filter(|entry| entry.file_size.max(entry.vm_size) != 0)
+        .collect()
+    }
+
+    /// Returns the rows of the given category.
+    pub fn items(&self, info: &SizeInfo) -> Vec<Vec<String>> {
+        self.entries(info)
+            .iter()
+            .map(|entry| self.row(entry))
+            .collect()
+    }
+
+    /// Returns the table row of the given entry.
+    pub fn row(&self, entry: &SizeEntry) -> Vec<String> {
+        vec![
+            entry.name.to_string(),
+            ByteSize(entry.file_size).to_string(),
+            ByteSize(entry.vm_size).to_string(),
+            percentage(entry.file_size, self.file_size),
+            percentage(entry.vm_size, self.vm_size),
+        ]
+    }
+}
+
+/// Returns the section entries along with the pseudo-sections.
+fn sections(elf: &Elf, file_size: u64, vm_size: u64) -> Vec<SizeEntry> {
+    let file_header = elf.file_headers.inner();
+    let headers_ranges = [
+        (0, u64::from(file_header.e_ehsize)),
+        (
+            file_header.e_phoff,
+            file_header.e_phoff.saturating_add(
+                u64::from(file_header.e_phnum) * u64::from(file_header.e_phentsize),
+            ),
+        ),
+        (
+            file_header.e_shoff,
+            file_header.e_shoff.saturating_add(
+                u64::from(file_header.e_shnum) * u64::from(file_header.e_shentsize),
+            ),
+        ),
+    ];
+    let mut section_ranges = Vec::new();
+    let mut entries = Vec::new();
+    for (name, header) in elf.section_headers.iter() {
+        if header.sh_type == abi::SHT_NULL {
+            continue;
+        }
+        let file_size = if header.sh_type == abi::SHT_NOBITS {
+            0
+        } else {
+            // Skip the malformed sections that do not fit in the address space.
+            let Some(end) = header.sh_offset.checked_add(header.sh_size) else {
+                continue;
+            };
+            section_ranges.push((header.sh_offset, end));
+            header.sh_size
+        };
+        let vm_size = if header.sh_flags & u64::from(abi::SHF_ALLOC) != 0 {
+            header.sh_size
+        } else {
+            0
+        };
+        entries.push(SizeEntry {
+            name: name.to_string(),
+            file_size,
+            vm_size,
+        });
+    }
+    let headers_size = covered_size(headers_ranges.to_vec(), file_size);
+    let covered_size = covered_size(
+        headers_ranges.into_iter().chain(section_ranges).collect(),
+        file_size,
+    );
+    let sections_vm_size = entries
+        .iter()
+        .fold(0, |size: u64, entry| size.saturating_add(entry.vm_size));
+    entries.push(SizeEntry {
+        name: ELF_HEADERS.to_string(),
+        file_size: headers_size,
+        vm_size: 0,
+    });
+    entries.push(SizeEntry {
+        name: UNACCOUNTED.to_string(),
+        file_size: file_size.saturating_sub(covered_size),
+        vm_size: vm_size.saturating_sub(sections_vm_size),
+    });
+    sort_entries(&mut entries);
+    entries
+}
+
+/// Returns the symbol sizes.
+///
+/// Aliases (symbols with the same address and size) are only counted once.
+fn symbols(elf: &Elf) -> Vec<SymbolSize> {
+    let sections = elf.section_headers.iter().collect::<Vec<_>>();
+    let mut symbols = elf.symbols.iter().collect::<Vec<_>>();
+    if symbols.is_empty() {
+        symbols = elf.dynamic_symbols.iter().collect();
+    }
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for (name, symbol) in symbols {
+        if name.is_empty()
+            || symbol.st_size == 0
+            || symbol.is_undefined()
+            || matches!(symbol.st_symtype(), abi::STT_SECTION | abi::STT_FILE)
+            || !seen.insert((symbol.st_shndx, symbol.st_value, symbol.st_size))
+        {
+            continue;
+        }
+        let section = sections.get(usize::from(symbol.st_shndx));
+        let file_size = match section {
+            Some((_, section)) if section.sh_type != abi::SHT_NOBITS => symbol.st_size,
+            _ => 0,
+        };
+        let vm_size = match section {
+            Some((_, section)) if section.sh_flags & u64::from(abi::SHF_ALLOC) != 0 => {
+                symbol.st_size
+            }
+            _ => 0,
+        };
+        let demangled = rustc_demangle::try_demangle(name)
+            .ok()
+            .map(|name| format!("{name:#}"));
+        entries.push(SymbolSize {
+            entry: SizeEntry {
+                name: demangled.clone().unwrap_or_else(|| name.to_string()),
+                file_size,
+                vm_size,
+            },
+            demangled,
+            section: section.map(|(name, _)| name.to_string()),
+        });
+    }
+    entries.sort_by(|a, b| compare_entries(&a.entry, &b.entry));
+    entries
+}
+
+/// Returns the crate entries that are inferred from the demangled symbol paths.
+fn crates(symbols: &[SymbolSize]) -> Vec<SizeEntry> {
+    let mut crates = HashMap::<String, SizeEntry>::new();
+    for symbol in symbols {
+        let name = symbol
+            .demangled
+            .as_deref()
+            .and_then(crate_name)
+            .unwrap_or(NON_RUST);
+        let entry = crates.entry(name.to_string()).or_insert_with(|| SizeEntry {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        entry.file_size = entry.file_size.saturating_add(symbol.entry.file_size);
+        entry.vm_size = entry.vm_size.saturating_add(symbol.entry.vm_size);
+    }
+    let mut entries = crates.into_values().collect::<Vec<_>>();
+    sort_entries(&mut entries);
+    entries
+}
+
+/// Returns the crate name from the demangled symbol path.
+///
+/// For trait implementations on primitive types (e.g. `<[T] as core::fmt::Debug>::fmt`),
+/// the crate of the trait is returned.
+fn crate_name(path: &str) -> Option<&str> {
+    let mut path = path.trim_start_matches(['<', '&', '*', ' ']);
+    for prefix in ["mut ", "const ", "dyn "] {
+        path = path.trim_start_matches(prefix);
+    }
+    if path.starts_with(['[', '(']) {
+        path = &path[path.find(" as ")? + 4..];
+    }
+    let end = path
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(path.len());
+    match &path[..end] {
+        "" => None,
+        name => Some(name),
+    }
+}
+
+/// Returns the total size of the given ranges (without the overlaps) within the limit.
+fn covered_size(mut ranges: Vec<(u64, u64)>, limit: u64) -> u64 {
+    ranges.sort_unstable();
+    let mut size = 0;
+    let mut end = 0;
+    for (range_start, range_end) in ranges {
+        let (range_start, range_end) = (range_start.max(end), range_end.min(limit));
+        if range_end > range_start {
+            size += range_end - range_start;
+            end = range_end;
+        }
+    }
+    size
+}
+
+/// Sorts the entries by size (the largest first).
+fn sort_entries(entries: &mut [SizeEntry]) {
+    entries.sort_by(compare_entries);
+}
+
+/// Compares the entries by size in descending order and then by name.
+fn compare_entries(a: &SizeEntry, b: &SizeEntry) -> Ordering {
+    b.file_size
+        .max(b.vm_size)
+        .cmp(&a.file_size.max(a.vm_size))
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+/// Returns the percentage of the value in the total.
+fn percentage(value: u64, total: u64) -> String {
+    if total == 0 {
+        return String::from("-");
+    }
+    format!("{:.2}%", value as f64 * 100. / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(Some("core"), crate_name("core::fmt::write"));
+        assert_eq!(
+            Some("alloc"),
+            crate_name("<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop")
+        );
+        assert_eq!(Some("core"), crate_name("<[T] as core::fmt::Debug>::fmt"));
+        assert_eq!(
+            Some("std"),
+            crate_name("<&mut std::io::Stdout as std::io::Write>::flush")
+        );
+        assert_eq!(None, crate_name("<(A, B)>::f"));
+    }
+
+    #[test]
+    fn test_covered_size() {
+        assert_eq!(0, covered_size(vec![], 100));
+        assert_eq!(30, covered_size(vec![(0, 10), (5, 20), (20, 30)], 100));
+        assert_eq!(40, covered_size(vec![(40, 60), (0, 10), (90, 120)], 100));
+    }
+}
@@ -14,6 +14,10 @@ pub enum ScrollType {
     List,
     /// Block.
     Block,
+    /// Open files.
+    File,
+    /// Sort order of the table.
+    Sort,
 }
 
 /// Application command.
@@ -23,6 +27,10 @@ pub enum Command {
     OpenRepo,
     /// Show details.
     ShowDetails,
+    /// Explain the selected dependency.
+    Explain,
+    /// Show the file picker.
+    PickFile,
     /// Next.
     Next(ScrollType, usize),
     /// Previous.
@@ -47,6 +55,14 @@ pub enum Command {
     Nothing,
     /// Change data to human readable format
     HumanReadable,
+    /// Cycle through the filters.
+    Filter,
+    /// Cycle through the encodings.
+    Encoding,
+    /// Cycle through the sections.
+    Section,
+    /// Show the selected row in the hexdump.
+    JumpToHexdump,
 }
 
 impl From<KeyEvent> for Command {
@@ -93,11 +109,20 @@ impl From<KeyEvent> for Command {
                 if key_event.modifiers == KeyModifiers::CONTROL {
                     Self::Input(InputCommand::Enter)
                 } else {
-                    Self::Nothing
+                    Self::Filter
                 }
             }
+            KeyCode::Char('e') => Self::Encoding,
+            KeyCode::Char('S') => Self::Section,
+            KeyCode::Char('x') => Self::JumpToHexdump,
             KeyCode::Backspace => Self::Input(InputCommand::Resume(Event::Key(key_event))),
             KeyCode::Enter => Self::ShowDetails,
+            KeyCode::Char('i') => Self::Explain,
+            KeyCode::Char('O') => Self::PickFile,
+            KeyCode::Char(']') => Self::Next(ScrollType::File, 1),
+            KeyCode::Char('[') => Self::Previous(ScrollType::File, 1),
+            KeyCode::Char('>') => Self::Next(ScrollType::Sort, 1),
+            KeyCode::Char('<') => Self::Previous(ScrollType::Sort, 1),
             KeyCode::Char('o') => Self::OpenRepo,
             KeyCode::Char('r') => Self::TraceCalls,
             KeyCode::Char('s') => Self::HumanReadable,
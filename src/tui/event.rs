@@ -1,4 +1,8 @@
 use crate::error::Result;
+use crate::hashes::FileHashes;
+use crate::insights::{crypto::CryptoConstant, rules::RuleMatch};
+use crate::strings::FileString;
+use crate::xrefs::Xrefs;
 use crate::TraceData;
 use ratatui::crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::path::PathBuf;
@@ -20,14 +24,22 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize.
     Resize(u16, u16),
-    /// Result of `strings` call.
-    FileStrings(Result<Vec<(String, u64)>>),
+    /// Result of `strings` call (with the file identifier).
+    FileStrings(usize, Result<Vec<FileString>>),
+    /// Cross-references (with the file identifier).
+    Xrefs(usize, Result<Xrefs>),
+    /// Rule matches (with the file identifier).
+    RuleMatches(usize, Result<Vec<RuleMatch>>),
+    /// Cryptographic constants (with the file identifier).
+    CryptoConstants(usize, Result<Vec<CryptoConstant>>),
+    /// File hashes (with the file identifier).
+    FileHashes(usize, Result<FileHashes>),
     /// Trace system calls.
     Trace,
     /// Results of tracer.
     TraceResult(Result<TraceData>),
-    /// Restart TUI with a new path.
-    Restart(Option<PathBuf>),
+    /// Open a file (or switch to it if it is already open).
+    OpenFile(PathBuf),
 }
 
 /// Terminal event handler.
@@ -1,24 +1,37 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::mem;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 
+use crate::deps::Dependency;
+use crate::diff::Diff;
+use crate::elf::Info;
 use crate::error::{Error, Result};
+use crate::insights::{capabilities::Capability, rules::RuleMatch, InsightInfo};
 use crate::prelude::Analyzer;
+use crate::process::ProcessInfo;
+use crate::scan::Scan;
+use crate::size::SizeInfo;
+use crate::strings::{FileString, StringKind, STRING_ENCODINGS, STRING_KINDS};
 use crate::tui::command::*;
 use crate::tui::event::Event;
-use crate::tui::ui::{Tab, ELF_INFO_TABS, MAIN_TABS};
+use crate::tui::ui::{
+    Tab, DIFF_INFO_TABS, ELF_INFO_TABS, INSIGHTS_INFO_TABS, MAIN_TABS, PROCESS_INFO_TABS,
+    SIZE_INFO_TABS, STRINGS_HEADERS,
+};
 use crate::tui::widgets::list::SelectableList;
 use crate::tui::widgets::logo::Logo;
+use crate::tui::widgets::sort::TableSort;
 use ansi_to_tui::IntoText;
 use heh::windows::Window;
+use ratatui::crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::style::Color;
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
-/// Application state.
+/// View state of an open file.
 #[derive(Debug)]
-pub struct State<'a> {
-    /// Is the application running?
-    pub running: bool,
+pub struct FileView<'a> {
     /// Binary analyzer.
     pub analyzer: Analyzer<'a>,
     /// Selected tab.
@@ -27,20 +40,20 @@ pub struct State<'a> {
     pub info_index: usize,
     /// Selected block in static analysis.
     pub block_index: usize,
-    /// List items.
-    pub list: SelectableList<Vec<String>>,
-    /// Show heh.
-    pub show_heh: bool,
-    /// Show details.
-    pub show_details: bool,
+    /// Rows of the table (unless they are indexed by the analyzer).
+    pub rows: Vec<Vec<String>>,
+    /// Indices of the shown rows.
+    pub list: SelectableList<usize>,
     /// Input.
     pub input: Input,
-    /// Enable input.
-    pub input_mode: bool,
     /// Strings call completed.
     pub strings_loaded: bool,
     /// System calls completed.
     pub system_calls_loaded: bool,
+    /// Category filter of the strings.
+    pub strings_filter: Option<StringKind>,
+    /// Section filter of the strings.
+    pub strings_section: Option<String>,
     /// System calls scroll index.
     pub dynamic_scroll_index: usize,
     /// File info scroll index.
@@ -49,56 +62,232 @@ pub struct State<'a> {
     pub notes_scroll_index: usize,
     /// File headers scroll index.
     pub headers_scroll_index: usize,
-    /// Terminal accent color.
-    pub accent_color: Color,
-    /// Logo widget.
-    pub logo: Logo,
+    /// Size info.
+    pub size_index: usize,
+    /// Selected section in the size treemap.
+    pub size_section: Option<String>,
+    /// Differences with another file.
+    pub diff: Option<Diff>,
+    /// Diff info.
+    pub diff_index: usize,
+    /// Insights info.
+    pub insights_index: usize,
+    /// Selected capability in the insights.
+    pub insights_capability: Option<Capability>,
+    /// Process info.
+    pub process_index: usize,
+    /// Sort order of the table.
+    pub table_sort: Option<TableSort>,
 }
 
-impl<'a> State<'a> {
-    /// Constructs a new instance of [`State`].
-    pub fn new(analyzer: Analyzer<'a>, accent_color: Option<Color>) -> Result<Self> {
-        let mut state = Self {
-            running: true,
+impl<'a> FileView<'a> {
+    /// Constructs a new instance.
+    pub fn new(analyzer: Analyzer<'a>) -> Self {
+        Self {
+            analyzer,
             tab: Tab::default(),
             info_index: 0,
             block_index: 2,
+            rows: Vec::new(),
             list: SelectableList::default(),
-            analyzer,
-            show_heh: false,
-            show_details: false,
             input: Input::default(),
-            input_mode: false,
             strings_loaded: false,
             system_calls_loaded: false,
+            strings_filter: None,
+            strings_section: None,
             dynamic_scroll_index: 0,
             general_scroll_index: 0,
             notes_scroll_index: 0,
             headers_scroll_index: 0,
+            size_index: 0,
+            size_section: None,
+            diff: None,
+            diff_index: 0,
+            insights_index: 0,
+            insights_capability: None,
+            process_index: 0,
+            table_sort: None,
+        }
+    }
+}
+
+/// Application state.
+#[derive(Debug)]
+pub struct State<'a> {
+    /// Is the application running?
+    pub running: bool,
+    /// View state of the active file.
+    pub view: FileView<'a>,
+    /// Show heh.
+    pub show_heh: bool,
+    /// Show details.
+    pub show_details: bool,
+    /// Enable input.
+    pub input_mode: bool,
+    /// Terminal accent color.
+    pub accent_color: Color,
+    /// Logo widget.
+    pub logo: Logo,
+    /// Open files in the order of opening (`None` for the active file).
+    pub open_files: Vec<Option<FileView<'a>>>,
+    /// File picker (name, status and path).
+    pub file_picker: Option<SelectableList<Vec<String>>>,
+    /// Summary of the scanned files.
+    pub scan: Option<Scan>,
+    /// Error of the last file that could not be opened.
+    pub error: Option<String>,
+}
+
+impl<'a> State<'a> {
+    /// Constructs a new instance of [`State`].
+    pub fn new(mut analyzer: Analyzer<'a>, accent_color: Option<Color>) -> Result<Self> {
+        analyzer.id = 0;
+        let mut state = Self {
+            running: true,
+            view: FileView::new(analyzer),
+            show_heh: false,
+            show_details: false,
+            input_mode: false,
             accent_color: accent_color.unwrap_or(Color::White),
             logo: Logo::default(),
+            open_files: vec![None],
+            file_picker: None,
+            scan: None,
+            error: None,
         };
         state.handle_tab()?;
         Ok(state)
     }
 
+    /// Shows the view, stores the active file and rebuilds the list.
+    ///
+    /// The selection of the list is restored after rebuilding.
+    fn show_view(&mut self, mut view: FileView<'a>) -> Result<()> {
+        mem::swap(&mut self.view, &mut view);
+        // The analyses of the inactive file are resumed when it is shown again.
+        view.analyzer.jobs.cancel();
+        let id = view.analyzer.id;
+        self.open_files[id] = Some(view);
+        self.show_details = false;
+        self.input_mode = false;
+        let list_state = self.view.list.state;
+        self.handle_tab()?;
+        if list_state
+            .selected()
+            .is_some_and(|v| v < self.view.list.items.len())
+        {
+            self.view.list.state = list_state;
+        }
+        Ok(())
+    }
+
+    /// Opens a new file and switches to it.
+    pub fn open_file(&mut self, mut analyzer: Analyzer<'a>) -> Result<()> {
+        analyzer.id = self.open_files.len();
+        self.open_files.push(None);
+        self.show_view(FileView::new(analyzer))
+    }
+
+    /// Switches to the open file and resumes its background jobs.
+    pub fn switch_file(&mut self, id: usize, event_sender: mpsc::Sender<Event>) -> Result<()> {
+        if let Some(view) = self.open_files.get_mut(id).and_then(Option::take) {
+            self.show_view(view)?;
+            self.view.analyzer.resume_jobs(event_sender);
+        }
+        Ok(())
+    }
+
+    /// Returns the identifier of the open file with the given path.
+    pub fn find_file(&self, path: &Path) -> Option<usize> {
+        self.open_files
+            .iter()
+            .map(|view| view.as_ref().map_or(&self.view.analyzer, |v| &v.analyzer))
+            .find(|analyzer| Path::new(&analyzer.file.path) == path)
+            .map(|analyzer| analyzer.id)
+    }
+
+    /// Returns the view of an open file.
+    pub fn file_view(&mut self, id: usize) -> Option<&mut FileView<'a>> {
+        if id == self.view.analyzer.id {
+            Some(&mut self.view)
+        } else {
+            self.open_files.get_mut(id)?.as_mut()
+        }
+    }
+
+    /// Returns the rows of the file picker (open files and the libraries of the active file).
+    fn file_picker_rows(&self) -> Vec<Vec<String>> {
+        let row = |path: &Path, status: &str| {
+            vec![
+                path.file_name()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                status.to_string(),
+                path.to_string_lossy().to_string(),
+            ]
+        };
+        let mut rows = self
+            .open_files
+            .iter()
+            .map(|view| match view {
+                Some(view) => row(Path::new(&view.analyzer.file.path), "open"),
+                None => row(Path::new(&self.view.analyzer.file.path), "active"),
+            })
+            .collect::<Vec<_>>();
+        for path in self
+            .view
+            .analyzer
+            .dependencies()
+            .libraries
+            .iter()
+            .filter_map(|library| library.display_path())
+        {
+            if self.find_file(path).is_none() && !rows.iter().any(|v| Path::new(&v[2]) == path) {
+                rows.push(row(path, "library"));
+            }
+        }
+        rows
+    }
+
     /// Runs a command and updates the state.
     pub fn run_command(
         &mut self,
         command: Command,
         event_sender: mpsc::Sender<Event>,
     ) -> Result<()> {
+        if let Some(file_picker) = &mut self.file_picker {
+            match command {
+                Command::Next(ScrollType::List, amount) => file_picker.next(amount),
+                Command::Previous(ScrollType::List, amount) => file_picker.previous(amount),
+                Command::ShowDetails => {
+                    if let Some(row) = file_picker.selected() {
+                        event_sender
+                            .send(Event::OpenFile(PathBuf::from(&row[2])))
+                            .expect("failed to send open file event");
+                    }
+                    self.file_picker = None;
+                }
+                Command::Exit | Command::PickFile => self.file_picker = None,
+                _ => {}
+            }
+            return Ok(());
+        }
         match command {
+            Command::PickFile => {
+                let mut file_picker = SelectableList::with_items(self.file_picker_rows());
+                file_picker.state.select(Some(self.view.analyzer.id));
+                self.file_picker = Some(file_picker);
+            }
             Command::Input(command) => {
                 match command {
                     InputCommand::Handle(event) => {
-                        self.input.handle_event(&event);
-                        if self.tab == Tab::DynamicAnalysis {
-                            self.dynamic_scroll_index = 0;
+                        self.view.input.handle_event(&event);
+                        if self.view.tab == Tab::DynamicAnalysis {
+                            self.view.dynamic_scroll_index = 0;
                         }
                     }
                     InputCommand::Enter => {
-                        if self.tab != Tab::DynamicAnalysis || self.system_calls_loaded {
+                        if self.view.tab != Tab::DynamicAnalysis || self.view.system_calls_loaded {
                             self.input_mode = true;
                         }
                     }
@@ -106,19 +295,46 @@ impl<'a> State<'a> {
                         self.input_mode = false;
                     }
                     InputCommand::Resume(event) => {
-                        if self.tab == Tab::General {
-                            event_sender
-                                .send(Event::Restart(None))
-                                .expect("failed to send restart event");
+                        if self.view.tab == Tab::General {
+                            // Go back to the file that the library was opened from.
+                            let files = &self.view.analyzer.files;
+                            if let Some(id) = files
+                                .len()
+                                .checked_sub(1)
+                                .filter(|len| *len != 0)
+                                .and_then(|len| {
+                                    self.open_files
+                                        .iter()
+                                        .flatten()
+                                        .find(|view| view.analyzer.files[..] == files[..len])
+                                })
+                                .map(|view| view.analyzer.id)
+                            {
+                                self.switch_file(id, event_sender.clone())?;
+                            }
                             return Ok(());
                         }
-                        if !self.input.value().is_empty() {
+                        if self.view.tab == Tab::Size {
+                            if let Some(section) = self.view.size_section.take() {
+                                self.handle_tab()?;
+                                self.select_row(|row| row[0] == section);
+                                return Ok(());
+                            }
+                        }
+                        if self.view.tab == Tab::Insights {
+                            if let Some(capability) = self.view.insights_capability.take() {
+                                self.handle_tab()?;
+                                self.select_row(|row| row[0] == capability.title());
+                                return Ok(());
+                            }
+                        }
+                        if !self.view.input.value().is_empty() {
                             self.input_mode = true;
-                            self.input.handle_event(&event);
+                            self.view.input.handle_event(&event);
                         }
                     }
                     InputCommand::Exit => {
-                        self.input = Input::default();
+                        self.view.input = Input::default();
                         self.input_mode = false;
                     }
                 }
@@ -126,65 +342,82 @@ impl<'a> State<'a> {
             }
             Command::Hexdump(command) => match command {
                 HexdumpCommand::Handle(event) => {
-                    self.analyzer
+                    self.view
+                        .analyzer
                         .heh
                         .handle_input(&event)
                         .map_err(|e| Error::HexdumpError(e.to_string()))?;
                 }
                 HexdumpCommand::HandleCustom(event, original_event) => {
-                    self.analyzer
+                    self.view
+                        .analyzer
                         .heh
-                        .handle_input(
-                            &if self.analyzer.heh.key_handler.is_focusing(Window::Search)
-                                || self
-                                    .analyzer
-                                    .heh
-                                    .key_handler
-                                    .is_focusing(Window::JumpToByte)
-                            {
-                                original_event
-                            } else {
-                                event
-                            },
-                        )
+                        .handle_input(&if self
+                            .view
+                            .analyzer
+                            .heh
+                            .key_handler
+                            .is_focusing(Window::Search)
+                            || self
+                                .view
+                                .analyzer
+                                .heh
+                                .key_handler
+                                .is_focusing(Window::JumpToByte)
+                        {
+                            original_event
+                        } else {
+                            event
+                        })
                         .map_err(|e| Error::HexdumpError(e.to_string()))?;
                 }
                 HexdumpCommand::Warn(message, event) => {
-                    if self.analyzer.heh.key_handler.is_focusing(Window::Search)
+                    if self
+                        .view
+                        .analyzer
+                        .heh
+                        .key_handler
+                        .is_focusing(Window::Search)
                         || self
+                            .view
                             .analyzer
                             .heh
                             .key_handler
                             .is_focusing(Window::JumpToByte)
                     {
-                        self.analyzer
+                        self.view
+                            .analyzer
                             .heh
                             .handle_input(&event)
                             .map_err(|e| Error::HexdumpError(e.to_string()))?;
                     } else {
-                        self.analyzer.heh.labels.notification = message;
+                        self.view.analyzer.heh.labels.notification = message;
                     }
                 }
                 HexdumpCommand::CancelNext => {
-                    self.tab = ((self.tab as usize + 1) % MAIN_TABS.len()).into();
+                    self.next_tab(1);
                     self.handle_tab()?;
                 }
                 HexdumpCommand::CancelPrevious => {
-                    self.tab = (self.tab as usize)
-                        .checked_sub(1)
-                        .unwrap_or(MAIN_TABS.len() - 1)
-                        .into();
+                    self.previous_tab(1);
                     self.handle_tab()?;
                 }
                 HexdumpCommand::Exit(event) => {
-                    if self.analyzer.heh.key_handler.is_focusing(Window::Search)
+                    if self
+                        .view
+                        .analyzer
+                        .heh
+                        .key_handler
+                        .is_focusing(Window::Search)
                         || self
+                            .view
                             .analyzer
                             .heh
                             .key_handler
                             .is_focusing(Window::JumpToByte)
                     {
-                        self.analyzer
+                        self.view
+                            .analyzer
                             .heh
                             .handle_input(&event)
                             .map_err(|e| Error::HexdumpError(e.to_string()))?;
@@ -194,14 +427,57 @@ impl<'a> State<'a> {
                 }
             },
             Command::ShowDetails => {
-                if self.tab == Tab::General {
-                    if let Some(path) = self.list.selected().map(|v| PathBuf::from(v[1].clone())) {
+                if self.view.tab == Tab::General {
+                    if let Some(path) = self
+                        .selected_row()
+                        .map(|v| PathBuf::from(v[1].clone()))
+                        .filter(|path| path.exists())
+                    {
+                        event_sender
+                            .send(Event::OpenFile(path))
+                            .expect("failed to send open file event");
+                    }
+                    return Ok(());
+                } else if self.view.tab == Tab::Scan {
+                    if let Some(row) = self.selected_row() {
                         event_sender
-                            .send(Event::Restart(Some(path)))
-                            .expect("failed to send trace event");
+                            .send(Event::OpenFile(PathBuf::from(&row[0])))
+                            .expect("failed to send open file event");
+                    }
+                    return Ok(());
+                } else if self.view.tab == Tab::Size
+                    && SIZE_INFO_TABS[self.view.size_index] == SizeInfo::Treemap
+                    && self.view.size_section.is_none()
+                {
+                    if let Some(section) = self.selected_row().map(|v| v[0].to_string()) {
+                        if self
+                            .view
+                            .analyzer
+                            .size()
+                            .section_symbols(&section)
+                            .next()
+                            .is_some()
+                        {
+                            self.view.size_section = Some(section);
+                            self.handle_tab()?;
+                            return Ok(());
+                        }
+                    }
+                    self.show_details = !self.show_details;
+                } else if self.view.tab == Tab::Insights
+                    && INSIGHTS_INFO_TABS[self.view.insights_index] == InsightInfo::Capabilities
+                    && self.view.insights_capability.is_none()
+                {
+                    if let Some(capability) = self.selected_row().and_then(|v| {
+                        Capability::ALL
+                            .iter()
+                            .find(|capability| capability.title() == v[0])
+                    }) {
+                        self.view.insights_capability = Some(*capability);
+                        self.handle_tab()?;
                     }
                     return Ok(());
-                } else if self.tab == Tab::DynamicAnalysis && !self.system_calls_loaded {
+                } else if self.view.tab == Tab::DynamicAnalysis && !self.view.system_calls_loaded {
                     event_sender
                         .send(Event::Trace)
                         .expect("failed to send trace event");
@@ -210,117 +486,221 @@ impl<'a> State<'a> {
                     self.show_details = !self.show_details;
                 }
             }
+            Command::Explain => {
+                if self.view.tab == Tab::General && self.selected_dependency().is_some() {
+                    self.show_details = !self.show_details;
+                }
+            }
             Command::OpenRepo => {
-                if self.tab == Tab::General {
+                if self.view.tab == Tab::General {
                     webbrowser::open(env!("CARGO_PKG_HOMEPAGE"))?;
                 }
             }
             Command::TraceCalls => {
-                if self.tab == Tab::DynamicAnalysis {
+                if self.view.tab == Tab::DynamicAnalysis {
                     event_sender
                         .send(Event::Trace)
                         .expect("failed to send trace event");
+                } else if self.view.tab == Tab::Process {
+                    let list_state = self.view.list.state;
+                    self.handle_tab()?;
+                    if list_state
+                        .selected()
+                        .is_some_and(|v| v < self.view.list.items.len())
+                    {
+                        self.view.list.state = list_state;
+                    }
                 }
             }
             Command::Next(scroll_type, amount) => match scroll_type {
+                ScrollType::File => {
+                    self.switch_file(
+                        (self
+                            .view
+                            .analyzer
+                            .id
+                            .checked_add(amount)
+                            .unwrap_or_default())
+                            % self.open_files.len(),
+                        event_sender.clone(),
+                    )?;
+                }
                 ScrollType::Tab => {
-                    self.tab = (((self.tab as usize).checked_add(amount).unwrap_or_default())
-                        % MAIN_TABS.len())
-                    .into();
+                    self.next_tab(amount);
                     self.handle_tab()?;
                 }
                 ScrollType::Table => {
-                    if self.tab == Tab::StaticAnalysis {
-                        self.info_index = (self.info_index.checked_add(amount).unwrap_or_default())
-                            % ELF_INFO_TABS.len();
+                    if self.view.tab == Tab::StaticAnalysis {
+                        self.view.info_index =
+                            (self.view.info_index.checked_add(amount).unwrap_or_default())
+                                % ELF_INFO_TABS.len();
+                        self.view.table_sort = None;
                         self.handle_tab()?;
-                    } else if self.tab == Tab::General {
-                        self.general_scroll_index =
-                            self.general_scroll_index.saturating_add(amount);
+                    } else if self.view.tab == Tab::Size {
+                        self.view.size_index =
+                            (self.view.size_index.checked_add(amount).unwrap_or_default())
+                                % SIZE_INFO_TABS.len();
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Diff {
+                        self.view.diff_index =
+                            (self.view.diff_index.checked_add(amount).unwrap_or_default())
+                                % DIFF_INFO_TABS.len();
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Process {
+                        self.view.process_index = (self
+                            .view
+                            .process_index
+                            .checked_add(amount)
+                            .unwrap_or_default())
+                            % PROCESS_INFO_TABS.len();
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Insights {
+                        self.view.insights_index = (self
+                            .view
+                            .insights_index
+                            .checked_add(amount)
+                            .unwrap_or_default())
+                            % INSIGHTS_INFO_TABS.len();
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::General {
+                        self.view.general_scroll_index =
+                            self.view.general_scroll_index.saturating_add(amount);
                     }
                 }
                 ScrollType::List => {
-                    if self.tab == Tab::DynamicAnalysis {
-                        self.dynamic_scroll_index =
-                            self.dynamic_scroll_index.saturating_add(amount);
-                    } else if self.tab == Tab::StaticAnalysis {
-                        match self.block_index {
+                    if self.view.tab == Tab::DynamicAnalysis {
+                        self.view.dynamic_scroll_index =
+                            self.view.dynamic_scroll_index.saturating_add(amount);
+                    } else if self.view.tab == Tab::StaticAnalysis {
+                        match self.view.block_index {
                             0 => {
-                                self.headers_scroll_index =
-                                    self.headers_scroll_index.saturating_add(amount);
+                                self.view.headers_scroll_index =
+                                    self.view.headers_scroll_index.saturating_add(amount);
                             }
                             1 => {
-                                self.notes_scroll_index =
-                                    self.notes_scroll_index.saturating_add(amount);
+                                self.view.notes_scroll_index =
+                                    self.view.notes_scroll_index.saturating_add(amount);
                             }
-                            _ => self.list.next(amount),
+                            _ => self.view.list.next(amount),
                         }
                     } else {
-                        self.list.next(amount)
+                        self.view.list.next(amount)
                     }
                 }
                 ScrollType::Block => {
-                    if self.tab == Tab::StaticAnalysis {
-                        self.block_index = (self.block_index.saturating_add(1)) % 3;
+                    if self.view.tab == Tab::StaticAnalysis {
+                        self.view.block_index = (self.view.block_index.saturating_add(1)) % 3;
+                    }
+                }
+                ScrollType::Sort => {
+                    if let Some(columns) = self.sort_columns() {
+                        self.view.table_sort =
+                            TableSort::next(self.view.table_sort, columns, amount);
+                        self.handle_tab()?;
                     }
                 }
             },
             Command::Previous(scroll_type, amount) => match scroll_type {
+                ScrollType::File => {
+                    self.switch_file(
+                        self.view
+                            .analyzer
+                            .id
+                            .checked_sub(amount)
+                            .unwrap_or(self.open_files.len() - 1),
+                        event_sender.clone(),
+                    )?;
+                }
                 ScrollType::Tab => {
-                    self.tab = (self.tab as usize)
-                        .checked_sub(amount)
-                        .unwrap_or(MAIN_TABS.len() - 1)
-                        .into();
+                    self.previous_tab(amount);
                     self.handle_tab()?;
                 }
                 ScrollType::Table => {
-                    if self.tab == Tab::StaticAnalysis {
-                        self.info_index = self
+                    if self.view.tab == Tab::StaticAnalysis {
+                        self.view.info_index = self
+                            .view
                             .info_index
                             .checked_sub(amount)
                             .unwrap_or(ELF_INFO_TABS.len() - 1);
+                        self.view.table_sort = None;
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Size {
+                        self.view.size_index = self
+                            .view
+                            .size_index
+                            .checked_sub(amount)
+                            .unwrap_or(SIZE_INFO_TABS.len() - 1);
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Diff {
+                        self.view.diff_index = self
+                            .view
+                            .diff_index
+                            .checked_sub(amount)
+                            .unwrap_or(DIFF_INFO_TABS.len() - 1);
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Process {
+                        self.view.process_index = self
+                            .view
+                            .process_index
+                            .checked_sub(amount)
+                            .unwrap_or(PROCESS_INFO_TABS.len() - 1);
+                        self.handle_tab()?;
+                    } else if self.view.tab == Tab::Insights {
+                        self.view.insights_index = self
+                            .view
+                            .insights_index
+                            .checked_sub(amount)
+                            .unwrap_or(INSIGHTS_INFO_TABS.len() - 1);
                         self.handle_tab()?;
-                    } else if self.tab == Tab::General {
-                        self.general_scroll_index =
-                            self.general_scroll_index.saturating_sub(amount);
+                    } else if self.view.tab == Tab::General {
+                        self.view.general_scroll_index =
+                            self.view.general_scroll_index.saturating_sub(amount);
                     }
                 }
                 ScrollType::List => {
-                    if self.tab == Tab::DynamicAnalysis {
-                        self.dynamic_scroll_index =
-                            self.dynamic_scroll_index.saturating_sub(amount);
-                    } else if self.tab == Tab::StaticAnalysis {
-                        match self.block_index {
+                    if self.view.tab == Tab::DynamicAnalysis {
+                        self.view.dynamic_scroll_index =
+                            self.view.dynamic_scroll_index.saturating_sub(amount);
+                    } else if self.view.tab == Tab::StaticAnalysis {
+                        match self.view.block_index {
                             0 => {
-                                self.headers_scroll_index =
-                                    self.headers_scroll_index.saturating_sub(amount);
+                                self.view.headers_scroll_index =
+                                    self.view.headers_scroll_index.saturating_sub(amount);
                             }
                             1 => {
-                                self.notes_scroll_index =
-                                    self.notes_scroll_index.saturating_sub(amount);
+                                self.view.notes_scroll_index =
+                                    self.view.notes_scroll_index.saturating_sub(amount);
                             }
-                            _ => self.list.previous(amount),
+                            _ => self.view.list.previous(amount),
                         }
                     } else {
-                        self.list.previous(amount)
+                        self.view.list.previous(amount)
                     }
                 }
                 ScrollType::Block => {
-                    if self.tab == Tab::StaticAnalysis {
-                        self.block_index = self.block_index.checked_sub(1).unwrap_or(2);
+                    if self.view.tab == Tab::StaticAnalysis {
+                        self.view.block_index = self.view.block_index.checked_sub(1).unwrap_or(2);
+                    }
+                }
+                ScrollType::Sort => {
+                    if let Some(columns) = self.sort_columns() {
+                        self.view.table_sort =
+                            TableSort::previous(self.view.table_sort, columns, amount);
+                        self.handle_tab()?;
                     }
                 }
             },
             Command::Top => {
-                if self.tab == Tab::DynamicAnalysis {
-                    self.dynamic_scroll_index = 0;
+                if self.view.tab == Tab::DynamicAnalysis {
+                    self.view.dynamic_scroll_index = 0;
                 } else {
-                    self.list.first();
+                    self.view.list.first();
                 }
             }
             Command::Bottom => {
-                if self.tab == Tab::DynamicAnalysis {
-                    self.dynamic_scroll_index = self
+                if self.view.tab == Tab::DynamicAnalysis {
+                    self.view.dynamic_scroll_index = self
+                        .view
                         .analyzer
                         .tracer
                         .syscalls
@@ -329,28 +709,29 @@ impl<'a> State<'a> {
                         .lines
                         .len();
                 } else {
-                    self.list.last();
+                    self.view.list.last();
                 }
             }
             Command::Increment => {
-                if self.tab == Tab::Strings {
-                    self.analyzer.strings_len = self
+                if self.view.tab == Tab::Strings {
+                    self.view.analyzer.strings_len = self
+                        .view
                         .analyzer
                         .strings_len
                         .checked_add(1)
-                        .unwrap_or(self.analyzer.strings_len);
-                    self.strings_loaded = false;
-                    self.analyzer.extract_strings(event_sender.clone());
+                        .unwrap_or(self.view.analyzer.strings_len);
+                    self.view.strings_loaded = false;
+                    self.view.analyzer.extract_strings(event_sender.clone());
                 }
             }
             Command::Decrement => {
-                if self.tab == Tab::Strings {
-                    if self.analyzer.strings_len > 1 {
-                        self.analyzer.strings_len =
-                            self.analyzer.strings_len.checked_sub(1).unwrap_or_default();
+                if self.view.tab == Tab::Strings {
+                    if self.view.analyzer.strings_len > 1 {
+                        self.view.analyzer.strings_len =
+                            self.view.analyzer.strings_len.saturating_sub(1);
                     }
-                    self.strings_loaded = false;
-                    self.analyzer.extract_strings(event_sender.clone());
+                    self.view.strings_loaded = false;
+                    self.view.analyzer.extract_strings(event_sender.clone());
                 }
             }
             Command::Exit => {
@@ -361,10 +742,62 @@ impl<'a> State<'a> {
                 }
             }
             Command::HumanReadable => {
-                if self.tab == Tab::StaticAnalysis {
-                    self.analyzer.elf.program_headers.toggle_readability();
-                    self.analyzer.elf.section_headers.toggle_readability();
+                if self.view.tab == Tab::StaticAnalysis {
+                    self.view.analyzer.elf.program_headers.toggle_readability();
+                    self.view.analyzer.elf.section_headers.toggle_readability();
+                    self.view.analyzer.info_rows.clear();
+                    self.handle_tab()?;
+                }
+            }
+            Command::Filter => {
+                if self.view.tab == Tab::Strings {
+                    self.view.strings_filter = match self.view.strings_filter {
+                        None => STRING_KINDS.first().copied(),
+                        Some(kind) => STRING_KINDS
+                            .iter()
+                            .skip_while(|v| **v != kind)
+                            .nth(1)
+                            .copied(),
+                    };
+                    self.handle_tab()?;
+                }
+            }
+            Command::Encoding => {
+                if self.view.tab == Tab::Strings {
+                    // Cycle through the single encodings and then select all of them.
+                    self.view.analyzer.strings_encodings =
+                        match self.view.analyzer.strings_encodings[..] {
+                            [encoding] => STRING_ENCODINGS
+                                .iter()
+                                .skip_while(|v| **v != encoding)
+                                .nth(1)
+                                .map(|v| vec![*v])
+                                .unwrap_or_else(|| STRING_ENCODINGS.to_vec()),
+                            _ => vec![STRING_ENCODINGS[0]],
+                        };
+                    self.view.strings_loaded = false;
+                    self.view.analyzer.extract_strings(event_sender.clone());
+                }
+            }
+            Command::Section => {
+                if self.view.tab == Tab::Strings {
+                    let sections = self.strings_sections();
+                    self.view.strings_section = match &self.view.strings_section {
+                        None => sections.first().cloned(),
+                        Some(section) => sections
+                            .iter()
+                            .skip_while(|v| *v != section)
+                            .nth(1)
+                            .cloned(),
+                    };
+                    self.handle_tab()?;
+                }
+            }
+            Command::JumpToHexdump => {
+                if let Some((offset, size)) = self.selected_range() {
+                    self.view.tab = Tab::Hexdump;
                     self.handle_tab()?;
+                    self.jump_to_offset(offset, size)?;
                 }
             }
             Command::Nothing => {}
@@ -372,39 +805,243 @@ impl<'a> State<'a> {
         Ok(())
     }
 
+    /// Returns the rows of the table.
+    ///
+    /// The shown rows are selected by the indices in the list.
+    pub fn rows(&self) -> &[Vec<String>] {
+        match self.view.tab {
+            Tab::StaticAnalysis => self
+                .view
+                .analyzer
+                .info_rows
+                .get(&ELF_INFO_TABS[self.view.info_index])
+                .map(|index| index.rows.as_slice())
+                .unwrap_or_default(),
+            Tab::Strings => &self.view.analyzer.strings_rows.rows,
+            _ => &self.view.rows,
+        }
+    }
+
+    /// Returns the selected row.
+    pub fn selected_row(&self) -> Option<&Vec<String>> {
+        self.rows().get(*self.view.list.selected()?)
+    }
+
+    /// Shows the given rows in the table.
+    fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.view.list = SelectableList::with_items((0..rows.len()).collect());
+        self.view.rows = rows;
+    }
+
+    /// Selects the first shown row that matches the given predicate.
+    fn select_row(&mut self, predicate: impl Fn(&Vec<String>) -> bool) {
+        let rows = self.rows();
+        if let Some(index) = self
+            .view
+            .list
+            .items
+            .iter()
+            .position(|i| rows.get(*i).is_some_and(&predicate))
+        {
+            self.view.list.state.select(Some(index));
+        }
+    }
+
+    /// Returns the file offset and the size of the selected row.
+    fn selected_range(&self) -> Option<(u64, u64)> {
+        let index = *self.view.list.selected()?;
+        let analyzer = &self.view.analyzer;
+        let elf = &analyzer.elf;
+        match self.view.tab {
+            Tab::Strings => self
+                .selected_string()
+                .map(|string| (string.offset, string.size())),
+            Tab::StaticAnalysis if self.view.block_index == 2 => {
+                match ELF_INFO_TABS[self.view.info_index] {
+                    Info::ProgramHeaders => elf
+                        .program_headers
+                        .inner()
+                        .get(index)
+                        .map(|segment| (segment.p_offset, segment.p_filesz)),
+                    Info::SectionHeaders => elf
+                        .section_headers
+                        .iter()
+                        .nth(index)
+                        .filter(|(_, header)| header.sh_type != elf::abi::SHT_NOBITS)
+                        .map(|(_, header)| (header.sh_offset, header.sh_size)),
+                    Info::Symbols | Info::DynamicSymbols => {
+                        let (address, size) = self.selected_address()?;
+                        Some((elf.file_offset(address)?, size))
+                    }
+                    _ => None,
+                }
+            }
+            Tab::Insights => match INSIGHTS_INFO_TABS[self.view.insights_index] {
+                InsightInfo::Rules => analyzer
+                    .rule_matches
+                    .iter()
+                    .flatten()
+                    .flat_map(RuleMatch::row_strings)
+                    .nth(index)?
+                    .map(|string| (string.offset, string.data.len() as u64)),
+                InsightInfo::Crypto => analyzer
+                    .crypto_constants
+                    .as_ref()?
+                    .get(index)
+                    .map(|constant| (constant.offset, constant.size)),
+                InsightInfo::Capabilities => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the virtual address and the size of the selected string, symbol or constant.
+    pub fn selected_address(&self) -> Option<(u64, u64)> {
+        let index = *self.view.list.selected()?;
+        let analyzer = &self.view.analyzer;
+        let (address, size) = match self.view.tab {
+            Tab::Strings => {
+                let string = self.selected_string()?;
+                (string.address?, string.size())
+            }
+            Tab::StaticAnalysis if self.view.block_index == 2 => {
+                let (_, symbol) = match ELF_INFO_TABS[self.view.info_index] {
+                    Info::Symbols => analyzer.elf.symbols.iter().nth(index)?,
+                    Info::DynamicSymbols => analyzer.elf.dynamic_symbols.iter().nth(index)?,
+                    _ => return None,
+                };
+                (symbol.st_value, symbol.st_size)
+            }
+            Tab::Insights
+                if INSIGHTS_INFO_TABS[self.view.insights_index] == InsightInfo::Crypto =>
+            {
+                let constant = analyzer.crypto_constants.as_ref()?.get(index)?;
+                (constant.address?, constant.size)
+            }
+            _ => return None,
+        };
+        // Undefined symbols do not have an address.
+        (address != 0).then_some((address, size))
+    }
+
+    /// Returns the dependency of the selected row (searched paths belong to the row above).
+    pub fn selected_dependency(&self) -> Option<&Dependency> {
+        let selected = self.view.list.state.selected()?;
+        let rows = self.rows();
+        self.view
+            .list
+            .items
+            .get(..=selected)?
+            .iter()
+            .rev()
+            .find_map(|i| self.view.analyzer.dependencies().dependency(&rows[*i]))
+    }
+
+    /// Returns the selected string.
+    fn selected_string(&self) -> Option<&FileString> {
+        self.view
+            .analyzer
+            .strings
+            .as_deref()?
+            .get(*self.view.list.selected()?)
+    }
+
+    /// Moves the hexdump cursor to the given offset and shows the range.
+    ///
+    /// The jump dialog of the hexdump is used since the cursor is not exposed.
+    /// The range is shown as a notification instead of being highlighted since
+    /// the hexdump does not support selections.
+    fn jump_to_offset(&mut self, offset: u64, size: u64) -> Result<()> {
+        let input = offset.to_string();
+        let events = [
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL),
+        ]
+        .into_iter()
+        .chain(
+            input
+                .chars()
+                .map(|c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)),
+        )
+        .chain([KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)]);
+        for event in events {
+            self.view
+                .analyzer
+                .heh
+                .handle_input(&CrosstermEvent::Key(event))
+                .map_err(|e| Error::HexdumpError(e.to_string()))?;
+        }
+        if self.view.analyzer.heh.labels.notification.is_empty() {
+            self.view.analyzer.heh.labels.notification = format!(
+                "{:#x}..{:#x} ({size} bytes)",
+                offset,
+                offset.saturating_add(size)
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the sections that contain strings (in the order of the section headers).
+    fn strings_sections(&self) -> Vec<String> {
+        let mut sections = self
+            .view
+            .analyzer
+            .strings
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|string| string.section.as_deref())
+            .collect::<HashSet<_>>();
+        self.view
+            .analyzer
+            .elf
+            .section_headers
+            .iter()
+            .filter(|(name, _)| sections.remove(name))
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// Returns the number of columns of the table that can be sorted (if any).
+    fn sort_columns(&self) -> Option<usize> {
+        match self.view.tab {
+            Tab::StaticAnalysis if self.view.block_index == 2 => {
+                Some(ELF_INFO_TABS[self.view.info_index].headers().len())
+            }
+            Tab::Strings => Some(STRINGS_HEADERS.len()),
+            _ => None,
+        }
+    }
+
     /// Update the state based on selected tab.
     pub fn handle_tab(&mut self) -> Result<()> {
         self.show_heh = false;
-        match self.tab {
+        match self.view.tab {
             Tab::General => {
-                self.list = SelectableList::with_items(
-                    self.analyzer
-                        .dependencies
-                        .clone()
-                        .into_iter()
-                        .map(|(name, lib)| vec![name, lib])
-                        .collect(),
-                );
+                let rows = self
+                    .view
+                    .analyzer
+                    .dependencies()
+                    .rows()
+                    .into_iter()
+                    .map(|(_, row)| row)
+                    .collect();
+                self.set_rows(rows);
             }
             Tab::StaticAnalysis => {
-                self.list = SelectableList::with_items(
-                    self.analyzer
-                        .elf
-                        .info(&ELF_INFO_TABS[self.info_index])
-                        .items()
-                        .into_iter()
-                        .filter(|items| {
-                            self.input.value().is_empty()
-                                || items.iter().any(|item| {
-                                    item.to_lowercase()
-                                        .contains(&self.input.value().to_lowercase())
-                                })
-                        })
-                        .collect(),
-                );
+                let index = self
+                    .view
+                    .analyzer
+                    .info_rows(ELF_INFO_TABS[self.view.info_index]);
+                let mut matches = index.search(self.view.input.value()).to_vec();
+                if let Some(sort) = self.view.table_sort {
+                    sort.apply(index, &mut matches);
+                }
+                self.view.list = SelectableList::with_items(matches);
             }
             Tab::DynamicAnalysis => {
-                self.analyzer.system_calls = self
+                self.view.analyzer.system_calls = self
+                    .view
                     .analyzer
                     .tracer
                     .syscalls
@@ -413,36 +1050,162 @@ impl<'a> State<'a> {
                     .lines
                     .into_iter()
                     .filter(|line| {
-                        self.input.value().is_empty()
+                        self.view.input.value().is_empty()
                             || line
                                 .clone()
                                 .reset_style()
                                 .to_string()
                                 .to_lowercase()
-                                .contains(&self.input.value().to_lowercase())
+                                .contains(&self.view.input.value().to_lowercase())
                     })
                     .collect();
             }
             Tab::Strings => {
-                self.list = SelectableList::with_items(
-                    self.analyzer
-                        .strings
-                        .clone()
-                        .unwrap_or_default()
-                        .iter()
-                        .map(|(v, i)| vec![v.to_string(), i.to_string()])
+                let analyzer = &mut self.view.analyzer;
+                let strings = analyzer.strings.as_deref().unwrap_or_default();
+                let mut matches = analyzer
+                    .strings_rows
+                    .search(self.view.input.value())
+                    .iter()
+                    .copied()
+                    .filter(|i| {
+                        self.view
+                            .strings_filter
+                            .is_none_or(|kind| strings[*i].kind == kind)
+                    })
+                    .filter(|i| {
+                        self.view.strings_section.is_none()
+                            || strings[*i].section.as_deref()
+                                == self.view.strings_section.as_deref()
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(sort) = self.view.table_sort {
+                    sort.apply(&analyzer.strings_rows, &mut matches);
+                }
+                self.view.list = SelectableList::with_items(matches);
+            }
+            Tab::Hexdump => {
+                self.show_heh = true;
+            }
+            Tab::Size => {
+                let size = self.view.analyzer.size();
+                let rows = match SIZE_INFO_TABS[self.view.size_index] {
+                    // The treemap is not filtered to keep the selection in sync.
+                    SizeInfo::Treemap => size
+                        .treemap_entries(self.view.size_section.as_deref())
+                        .into_iter()
+                        .map(|entry| size.row(entry))
+                        .collect(),
+                    info => size
+                        .items(&info)
+                        .into_iter()
                         .filter(|items| {
-                            self.input.value().is_empty()
-                                || items.iter().any(|item| {
-                                    item.to_lowercase()
-                                        .contains(&self.input.value().to_lowercase())
-                                })
+                            self.view.input.value().is_empty()
+                                || items[0]
+                                    .to_lowercase()
+                                    .contains(&self.view.input.value().to_lowercase())
                         })
                         .collect(),
-                )
+                };
+                self.set_rows(rows);
             }
-            Tab::Hexdump => {
-                self.show_heh = true;
+            Tab::Diff => {
+                let rows = self
+                    .view
+                    .diff
+                    .as_ref()
+                    .map(|diff| diff.rows(&DIFF_INFO_TABS[self.view.diff_index]).to_vec())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|items| {
+                        self.view.input.value().is_empty()
+                            || items.iter().any(|item| {
+                                item.to_lowercase()
+                                    .contains(&self.view.input.value().to_lowercase())
+                            })
+                    })
+                    .collect();
+                self.set_rows(rows);
+            }
+            Tab::Process => {
+                if let Some(process) = &mut self.view.analyzer.process {
+                    process.refresh()?;
+                }
+                let rows = self
+                    .view
+                    .analyzer
+                    .process
+                    .as_ref()
+                    .map(|process| match PROCESS_INFO_TABS[self.view.process_index] {
+                        ProcessInfo::Maps => process.map_rows(&self.view.analyzer.elf),
+                        ProcessInfo::Libraries => {
+                            process.library_rows(self.view.analyzer.dependencies())
+                        }
+                    })
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|items| {
+                        self.view.input.value().is_empty()
+                            || items.iter().any(|item| {
+                                item.to_lowercase()
+                                    .contains(&self.view.input.value().to_lowercase())
+                            })
+                    })
+                    .collect();
+                self.set_rows(rows);
+            }
+            Tab::Scan => {
+                let rows = self
+                    .scan
+                    .as_ref()
+                    .map(Scan::rows)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|items| {
+                        self.view.input.value().is_empty()
+                            || items.iter().any(|item| {
+                                item.to_lowercase()
+                                    .contains(&self.view.input.value().to_lowercase())
+                            })
+                    })
+                    .collect();
+                self.set_rows(rows);
+            }
+            Tab::Insights => {
+                let rows = match INSIGHTS_INFO_TABS[self.view.insights_index] {
+                    InsightInfo::Rules => self
+                        .view
+                        .analyzer
+                        .rule_matches
+                        .iter()
+                        .flatten()
+                        .flat_map(|rule_match| rule_match.rows())
+                        .collect::<Vec<_>>(),
+                    InsightInfo::Crypto => self
+                        .view
+                        .analyzer
+                        .crypto_constants
+                        .iter()
+                        .flatten()
+                        .map(|constant| constant.row())
+                        .collect(),
+                    InsightInfo::Capabilities => match self.view.insights_capability {
+                        Some(capability) => self.view.analyzer.capabilities().rows(capability),
+                        None => self.view.analyzer.capabilities().summary(),
+                    },
+                };
+                // All rows are kept so that the selection maps to the matches.
+                let query = self.view.input.value().to_lowercase();
+                let matches = (0..rows.len())
+                    .filter(|i| {
+                        query.is_empty()
+                            || rows[*i]
+                                .iter()
+                                .any(|item| item.to_lowercase().contains(&query))
+                    })
+                    .collect();
+                self.view.list = SelectableList::with_items(matches);
+                self.view.rows = rows;
             }
         }
         Ok(())
@@ -450,11 +1213,13 @@ impl<'a> State<'a> {
 
     /// Returns the key bindings.
     pub fn get_key_bindings(&self) -> Vec<(&'a str, &'a str)> {
-        match self.tab {
+        match self.view.tab {
             Tab::General => {
                 vec![
                     ("o", "Open docs"),
                     ("⏎ ", "Analyze lib"),
+                    ("i", "Details"),
+                    ("O", "Files"),
                     ("h/j/k/l", "Scroll"),
                     ("Tab", "Next"),
                     ("⇧+Tab", "Previous"),
@@ -468,11 +1233,13 @@ impl<'a> State<'a> {
                 ("h/j/k/l", "Scroll"),
                 ("n/p", "Toggle"),
                 ("s", "Readability"),
+                ("</>", "Sort"),
+                ("x", "Hexdump"),
                 ("Tab", "Next"),
                 ("q", "Quit"),
             ],
             Tab::DynamicAnalysis => {
-                if self.system_calls_loaded {
+                if self.view.system_calls_loaded {
                     vec![
                         ("Enter", "Details"),
                         ("r", "Re-run"),
@@ -493,6 +1260,11 @@ impl<'a> State<'a> {
             }
             Tab::Strings => vec![
                 ("Enter", "Details"),
+                ("f", "Filter"),
+                ("e", "Encoding"),
+                ("S", "Section"),
+                ("</>", "Sort"),
+                ("x", "Hexdump"),
                 ("+", "Increment"),
                 ("-", "Decrement"),
                 ("/", "Search"),
@@ -509,11 +1281,120 @@ impl<'a> State<'a> {
                 ("Tab", "Next"),
                 ("q", "Quit"),
             ],
+            Tab::Size => {
+                if SIZE_INFO_TABS[self.view.size_index] == SizeInfo::Treemap {
+                    vec![
+                        ("⏎ ", "Drill down"),
+                        ("h/j/k/l", "Scroll"),
+                        ("Tab", "Next"),
+                        ("Bksp", "Back"),
+                        ("q", "Quit"),
+                    ]
+                } else {
+                    vec![
+                        ("Enter", "Details"),
+                        ("/", "Search"),
+                        ("h/j/k/l", "Scroll"),
+                        ("Tab", "Next"),
+                        ("q", "Quit"),
+                    ]
+                }
+            }
+            Tab::Process => vec![
+                ("Enter", "Details"),
+                ("r", "Refresh"),
+                ("/", "Search"),
+                ("h/j/k/l", "Scroll"),
+                ("Tab", "Next"),
+                ("q", "Quit"),
+            ],
+            Tab::Scan => vec![
+                ("⏎ ", "Analyze"),
+                ("/", "Search"),
+                ("j/k", "Scroll"),
+                ("Tab", "Next"),
+                ("q", "Quit"),
+            ],
+            Tab::Diff => vec![
+                ("Enter", "Details"),
+                ("/", "Search"),
+                ("h/j/k/l", "Scroll"),
+                ("Tab", "Next"),
+                ("q", "Quit"),
+            ],
+            Tab::Insights => match INSIGHTS_INFO_TABS[self.view.insights_index] {
+                InsightInfo::Capabilities if self.view.insights_capability.is_none() => vec![
+                    ("⏎ ", "Drill down"),
+                    ("/", "Search"),
+                    ("h/j/k/l", "Scroll"),
+                    ("Tab", "Next"),
+                    ("q", "Quit"),
+                ],
+                InsightInfo::Capabilities => vec![
+                    ("Enter", "Details"),
+                    ("/", "Search"),
+                    ("h/j/k/l", "Scroll"),
+                    ("Tab", "Next"),
+                    ("Bksp", "Back"),
+                    ("q", "Quit"),
+                ],
+                _ => vec![
+                    ("Enter", "Details"),
+                    ("x", "Hexdump"),
+                    ("/", "Search"),
+                    ("h/j/k/l", "Scroll"),
+                    ("Tab", "Next"),
+                    ("q", "Quit"),
+                ],
+            },
         }
     }
 
     /// Changes the tab
+    ///
+    /// The general tab is selected if the given tab is not shown.
     pub fn set_tab(&mut self, tab: Tab) {
-        self.tab = tab;
+        self.view.tab = if self.tabs().contains(&tab) {
+            tab
+        } else {
+            Tab::default()
+        };
+    }
+
+    /// Returns the tabs to show.
+    ///
+    /// The tabs of the modes that are not active (e.g. diff) are hidden.
+    pub fn tabs(&self) -> Vec<Tab> {
+        (0..MAIN_TABS.len())
+            .map(Tab::from)
+            .filter(|tab| match tab {
+                Tab::Diff => self.view.diff.is_some(),
+                Tab::Scan => self.scan.is_some(),
+                Tab::Process => self.view.analyzer.process.is_some(),
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Selects the tab that is the given amount of tabs after the current one.
+    fn next_tab(&mut self, amount: usize) {
+        let tabs = self.tabs();
+        let index = tabs
+            .iter()
+            .position(|v| *v == self.view.tab)
+            .unwrap_or_default();
+        self.view.tab = tabs[(index + amount) % tabs.len()];
+        self.view.table_sort = None;
+    }
+
+    /// Selects the tab that is the given amount of tabs before the current one.
+    fn previous_tab(&mut self, amount: usize) {
+        let tabs = self.tabs();
+        let index = tabs
+            .iter()
+            .position(|v| *v == self.view.tab)
+            .unwrap_or_default();
+        self.view.tab = tabs[(index + tabs.len() - amount % tabs.len()) % tabs.len()];
+        self.view.table_sort = None;
     }
 }
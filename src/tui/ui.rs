@@ -1,12 +1,32 @@
-use crate::{elf::Info, tui::state::State};
+use crate::{
+    abi::Verdict,
+    deps::{Provider, RowKind},
+    diff::{Change, DiffInfo},
+    elf::Info,
+    insights::{capabilities::IMPORT_HEADERS, InsightInfo},
+    job::JobKind,
+    process::ProcessInfo,
+    scan::SCAN_HEADERS,
+    size::SizeInfo,
+    strings::STRING_ENCODINGS,
+    tui::{
+        state::State,
+        widgets::{
+            sort::TableSort,
+            treemap::{Treemap, TreemapItem},
+        },
+    },
+    xrefs::XREFS_LIMIT,
+};
 use ansi_to_tui::IntoText;
+use bytesize::ByteSize;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{
         Block, Borders, Cell, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Table, TableState, Tabs, Wrap,
+        ScrollbarState, Table, Tabs, Wrap,
     },
     Frame,
 };
@@ -19,10 +39,18 @@ use unicode_width::UnicodeWidthStr;
 pub const MAIN_TABS: &[&str] = Tab::get_headers();
 
 /// Header for the strings table.
-const STRINGS_HEADERS: &[&str] = &["Location", "String"];
+pub const STRINGS_HEADERS: &[&str] = &[
+    "Location", "Address", "Section", "Encoding", "Category", "String",
+];
 
-/// Maximum number of elements to show in table/list.
-const LIST_LIMIT: usize = 100;
+/// Maximum width of the section column in the strings table.
+const STRINGS_SECTION_WIDTH: usize = 20;
+
+/// Width of the encoding column in the strings table.
+const STRINGS_ENCODING_WIDTH: usize = 8;
+
+/// Width of the category column in the strings table.
+const STRINGS_CATEGORY_WIDTH: usize = 8;
 
 /// Titles of the ELF info tabs.
 pub const ELF_INFO_TABS: &[Info] = &[
@@ -34,6 +62,39 @@ pub const ELF_INFO_TABS: &[Info] = &[
     Info::Relocations,
 ];
 
+/// Titles of the size tabs.
+pub const SIZE_INFO_TABS: &[SizeInfo] = &[
+    SizeInfo::Sections,
+    SizeInfo::Symbols,
+    SizeInfo::Crates,
+    SizeInfo::Treemap,
+];
+
+/// Maximum number of items to show in the treemap.
+const TREEMAP_LIMIT: usize = 200;
+
+/// Titles of the diff tabs.
+pub const DIFF_INFO_TABS: &[DiffInfo] = &[
+    DiffInfo::FileHeaders,
+    DiffInfo::ProgramHeaders,
+    DiffInfo::SectionHeaders,
+    DiffInfo::Symbols,
+    DiffInfo::Dependencies,
+    DiffInfo::Notes,
+    DiffInfo::Strings,
+    DiffInfo::Abi,
+];
+
+/// Titles of the process tabs.
+pub const PROCESS_INFO_TABS: &[ProcessInfo] = &[ProcessInfo::Maps, ProcessInfo::Libraries];
+
+/// Titles of the insights tabs.
+pub const INSIGHTS_INFO_TABS: &[InsightInfo] = &[
+    InsightInfo::Rules,
+    InsightInfo::Crypto,
+    InsightInfo::Capabilities,
+];
+
 /// Application tab.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Default, clap::ValueEnum)]
 pub enum Tab {
@@ -48,12 +109,30 @@ pub enum Tab {
     Strings = 3,
     /// Hexdump.
     Hexdump = 4,
+    /// Size.
+    Size = 5,
+    /// Diff.
+    Diff = 6,
+    /// Insights.
+    Insights = 7,
+    /// Scan.
+    Scan = 8,
+    /// Process.
+    Process = 9,
 }
 
 impl Tab {
+    /// Returns the title.
+    pub fn title(&self) -> &'static str {
+        MAIN_TABS[*self as usize]
+    }
+
     /// Returns the available tabs.
     const fn get_headers() -> &'static [&'static str] {
-        &["General", "Static", "Dynamic", "Strings", "Hexdump"]
+        &[
+            "General", "Static", "Dynamic", "Strings", "Hexdump", "Size", "Diff", "Insights",
+            "Scan", "Process",
+        ]
     }
 }
 
@@ -65,6 +144,11 @@ impl From<usize> for Tab {
             2 => Self::DynamicAnalysis,
             3 => Self::Strings,
             4 => Self::Hexdump,
+            5 => Self::Size,
+            6 => Self::Diff,
+            7 => Self::Insights,
+            8 => Self::Scan,
+            9 => Self::Process,
             _ => Self::default(),
         }
     }
@@ -72,7 +156,7 @@ impl From<usize> for Tab {
 
 /// Renders the user interface widgets.
 pub fn render(state: &mut State, frame: &mut Frame) {
-    if !state.logo.is_rendered && state.analyzer.files.len() == 1 {
+    if !state.logo.is_rendered && state.view.analyzer.files.len() == 1 {
         let area = frame.area();
         let (logo_width, logo_height) = state.logo.get_size();
         if logo_width < area.width && logo_height < area.height {
@@ -107,17 +191,22 @@ pub fn render(state: &mut State, frame: &mut Frame) {
                     env!("CARGO_PKG_VERSION").into(),
                     "|".fg(Color::Rgb(100, 100, 100)),
                 ])
-                .title_alignment(Alignment::Center),
+                .title_alignment(Alignment::Center)
+                .title_top(get_jobs_line(state).right_aligned()),
             chunks[0],
         );
+        let tabs = state.tabs();
         let chunks = Layout::new(
             Direction::Horizontal,
-            [Constraint::Percentage(50), Constraint::Percentage(50)],
+            [
+                Constraint::Length(tabs.iter().map(|v| v.title().len() as u16 + 3).sum()),
+                Constraint::Min(0),
+            ],
         )
         .margin(1)
         .split(chunks[0]);
-        let tabs = Tabs::new(MAIN_TABS.iter().map(|v| Line::from(*v)))
-            .select(state.tab as usize)
+        let tabs = Tabs::new(tabs.iter().map(|v| Line::from(v.title())))
+            .select(tabs.iter().position(|v| *v == state.view.tab))
             .style(Style::default().fg(Color::Cyan))
             .highlight_style(
                 Style::default()
@@ -125,9 +214,25 @@ pub fn render(state: &mut State, frame: &mut Frame) {
                     .fg(state.accent_color),
             );
         frame.render_widget(tabs, chunks[0]);
+        if state.open_files.len() > 1 {
+            let names = state.open_files.iter().map(|view| {
+                let analyzer = view.as_ref().map_or(&state.view.analyzer, |v| &v.analyzer);
+                Line::from(analyzer.file.name.to_string().italic())
+            });
+            frame.render_widget(
+                Tabs::new(names)
+                    .select(state.view.analyzer.id)
+                    .highlight_style(
+                        Style::default()
+                            .add_modifier(Modifier::BOLD)
+                            .fg(state.accent_color),
+                    ),
+                chunks[1],
+            );
+        }
         let mut files = Vec::new();
-        for (i, file) in state.analyzer.files.iter().enumerate() {
-            if i != state.analyzer.files.len() - 1 {
+        for (i, file) in state.view.analyzer.files.iter().enumerate() {
+            if i != state.view.analyzer.files.len() - 1 {
                 files.push(
                     file.file_name()
                         .map(|v| v.to_string_lossy().to_string())
@@ -140,12 +245,14 @@ pub fn render(state: &mut State, frame: &mut Frame) {
             }
         }
         files.push(" ".into());
-        frame.render_widget(
-            Paragraph::new(Line::from(files)).alignment(Alignment::Right),
-            chunks[1],
-        )
+        if state.open_files.len() == 1 {
+            frame.render_widget(
+                Paragraph::new(Line::from(files)).alignment(Alignment::Right),
+                chunks[1],
+            )
+        }
     }
-    match state.tab {
+    match state.view.tab {
         Tab::General => {
             render_general_info(state, frame, chunks[1]);
         }
@@ -162,12 +269,98 @@ pub fn render(state: &mut State, frame: &mut Frame) {
             {
                 let chunks = Layout::vertical([Constraint::Percentage(100), Constraint::Min(1)])
                     .split(chunks[1]);
-                state.analyzer.heh.render_frame(frame, chunks[0]);
+                state.view.analyzer.heh.render_frame(frame, chunks[0]);
             }
             frame.render_widget(Block::new().borders(Borders::BOTTOM), chunks[1])
         }
+        Tab::Size => {
+            render_size(state, frame, chunks[1]);
+        }
+        Tab::Diff => {
+            render_diff(state, frame, chunks[1]);
+        }
+        Tab::Insights => {
+            render_insights(state, frame, chunks[1]);
+        }
+        Tab::Scan => {
+            render_scan(state, frame, chunks[1]);
+        }
+        Tab::Process => {
+            render_process(state, frame, chunks[1]);
+        }
     }
     render_key_bindings(state, frame, chunks[1]);
+    render_file_picker(state, frame);
+}
+
+/// Returns the line that shows the progress of the running background jobs.
+///
+/// The failed jobs are also shown (see the details in their tabs), along with the
+/// error of the file that could not be opened.
+fn get_jobs_line<'a>(state: &State) -> Line<'a> {
+    let mut spans = Vec::new();
+    for job in state.view.analyzer.jobs.running() {
+        spans.push("|".fg(Color::Rgb(100, 100, 100)));
+        spans.push(format!("{} ", job.kind.title()).italic());
+        spans.push(
+            format!("{}%", job.handle.progress())
+                .fg(state.accent_color)
+                .bold(),
+        );
+    }
+    for (kind, _) in state.view.analyzer.jobs.failed() {
+        spans.push("|".fg(Color::Rgb(100, 100, 100)));
+        spans.push(format!("{} failed", kind.title()).red().bold());
+    }
+    if let Some(error) = &state.error {
+        spans.push("|".fg(Color::Rgb(100, 100, 100)));
+        spans.push(error.to_string().red().bold());
+    }
+    if !spans.is_empty() {
+        spans.push("|".fg(Color::Rgb(100, 100, 100)));
+    }
+    Line::from(spans)
+}
+
+/// Renders the file picker popup.
+fn render_file_picker(state: &State, frame: &mut Frame) {
+    let Some(file_picker) = &state.file_picker else {
+        return;
+    };
+    let selected = file_picker.state.selected();
+    let name_width = file_picker
+        .items
+        .iter()
+        .map(|v| v[0].width())
+        .max()
+        .unwrap_or_default();
+    let lines = file_picker
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let line = Line::from(vec![
+                format!("{:<name_width$}  ", row[0]).into(),
+                format!("{:<8}", row[1]).fg(match row[1].as_str() {
+                    "active" => state.accent_color,
+                    "open" => Color::Cyan,
+                    _ => Color::Rgb(100, 100, 100),
+                }),
+                row[2].to_string().italic(),
+            ]);
+            if Some(i) == selected {
+                line.style(Style::default().fg(Color::Green))
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>();
+    let popup = Popup::new(Text::from(lines)).title(Line::from(vec![
+        "|".fg(Color::Rgb(100, 100, 100)),
+        "Files".fg(state.accent_color).bold(),
+        "|".fg(Color::Rgb(100, 100, 100)),
+    ]));
+    frame.render_widget(&popup, frame.area());
 }
 
 /// Renders the key bindings.
@@ -192,7 +385,7 @@ pub fn render_key_bindings(state: &mut State, frame: &mut Frame, rect: Rect) {
     );
     if line.width() as u16 > chunks[1].width.saturating_sub(25)
         && get_input_line(state).width() != 0
-        && (state.tab != Tab::StaticAnalysis || state.tab != Tab::Hexdump)
+        && (state.view.tab != Tab::StaticAnalysis || state.view.tab != Tab::Hexdump)
     {
         return;
     }
@@ -201,8 +394,8 @@ pub fn render_key_bindings(state: &mut State, frame: &mut Frame, rect: Rect) {
 
 /// Renders the general info tab.
 pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
-    let selected_index = state.list.state.selected().unwrap_or_default();
-    let items_len = state.list.items.len();
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
 
     frame.render_widget(Block::bordered(), rect);
     let area = Layout::new(
@@ -224,9 +417,9 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
     let banner_area = Layout::new(
         Direction::Horizontal,
         [
-            Constraint::Length((area[1].width.checked_sub(banner_width)).unwrap_or_default() / 2),
+            Constraint::Length(area[1].width.saturating_sub(banner_width) / 2),
             Constraint::Min(banner_width),
-            Constraint::Length((area[1].width.checked_sub(banner_width)).unwrap_or_default() / 2),
+            Constraint::Length(area[1].width.saturating_sub(banner_width) / 2),
         ],
     )
     .split(area[1]);
@@ -269,13 +462,20 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
             Line::from(vec![
                 "Size".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
-                state.analyzer.file.size.to_string().fg(state.accent_color),
+                state
+                    .view
+                    .analyzer
+                    .file
+                    .size
+                    .to_string()
+                    .fg(state.accent_color),
             ]),
             Line::from(vec![
                 " ".into(),
                 "Blocks".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .blocks
@@ -287,6 +487,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Block Size".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .block_size
@@ -296,22 +497,41 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
             Line::from(vec![
                 "Device".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
-                state.analyzer.file.links.to_string().fg(state.accent_color),
+                state
+                    .view
+                    .analyzer
+                    .file
+                    .links
+                    .to_string()
+                    .fg(state.accent_color),
             ]),
             Line::from(vec![
                 "Inode".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
-                state.analyzer.file.inode.to_string().fg(state.accent_color),
+                state
+                    .view
+                    .analyzer
+                    .file
+                    .inode
+                    .to_string()
+                    .fg(state.accent_color),
             ]),
             Line::from(vec![
                 "Links".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
-                state.analyzer.file.links.to_string().fg(state.accent_color),
+                state
+                    .view
+                    .analyzer
+                    .file
+                    .links
+                    .to_string()
+                    .fg(state.accent_color),
             ]),
             Line::from(vec![
                 "Access".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .access
@@ -323,6 +543,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Uid".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .access
@@ -334,6 +555,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Gid".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .access
@@ -345,6 +567,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Access".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .date
@@ -356,6 +579,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Modify".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .date
@@ -367,6 +591,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Change".cyan(),
                 Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .date
@@ -378,6 +603,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                 "Birth".cyan(),
                 Span::raw(":  ").fg(Color::Rgb(100, 100, 100)),
                 state
+                    .view
                     .analyzer
                     .file
                     .date
@@ -387,6 +613,77 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
             ]),
         ]
     };
+    let providers = state.view.analyzer.symbol_providers();
+    let count = |provider| providers.iter().filter(|v| **v == Some(provider)).count();
+    let (missing, weak) = (count(Provider::Missing), count(Provider::Weak));
+    let mut import_spans = vec![
+        "Imports".cyan(),
+        Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+        providers
+            .iter()
+            .flatten()
+            .count()
+            .to_string()
+            .fg(state.accent_color),
+    ];
+    if missing != 0 || weak != 0 {
+        import_spans.push(" (".fg(Color::Rgb(100, 100, 100)));
+        if missing != 0 {
+            import_spans.push(format!("{missing} not found").red().bold());
+        }
+        if missing != 0 && weak != 0 {
+            import_spans.push(", ".fg(Color::Rgb(100, 100, 100)));
+        }
+        if weak != 0 {
+            import_spans.push(format!("{weak} weak").fg(Color::Rgb(100, 100, 100)));
+        }
+        import_spans.push(")".fg(Color::Rgb(100, 100, 100)));
+    }
+    let unused = state
+        .view
+        .analyzer
+        .dependencies()
+        .unused(providers)
+        .map(|library| library.name.as_str())
+        .collect::<Vec<_>>();
+    let lines = lines
+        .into_iter()
+        .chain([
+            Line::from(import_spans),
+            Line::from(vec![
+                "Unused libraries".cyan(),
+                Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                if unused.is_empty() {
+                    "-".fg(state.accent_color)
+                } else {
+                    unused.join(", ").yellow()
+                },
+            ]),
+        ])
+        .collect::<Vec<_>>();
+    let lines: Vec<Line> = match &state.view.analyzer.hashes {
+        Some(hashes) => lines
+            .into_iter()
+            .chain(hashes.items().into_iter().map(|(name, value)| {
+                Line::from(vec![
+                    name.cyan(),
+                    Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                    value.fg(state.accent_color),
+                ])
+            }))
+            .collect(),
+        None => lines
+            .into_iter()
+            .chain([Line::from(vec![
+                "Hashes".cyan(),
+                Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                match state.view.analyzer.jobs.error(JobKind::Hashes) {
+                    Some(e) => e.to_string().red(),
+                    None => "Loading...".fg(state.accent_color),
+                },
+            ])])
+            .collect(),
+    };
 
     let info_width = lines.iter().map(|v| v.width()).max().unwrap_or_default() as u16 + 2;
     let rect = area[2].inner(Margin {
@@ -395,7 +692,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
     });
     let area = Layout::new(
         Direction::Vertical,
-        if state.list.items.is_empty() {
+        if state.view.list.items.is_empty() {
             vec![Constraint::Max(lines.len() as u16 + 2)]
         } else if (lines.len() as u16).saturating_sub(2) < rect.height / 2 {
             vec![
@@ -411,16 +708,16 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
     let info_area = Layout::new(
         Direction::Horizontal,
         [
-            Constraint::Length((area[0].width.checked_sub(info_width)).unwrap_or_default() / 2),
+            Constraint::Length(area[0].width.saturating_sub(info_width) / 2),
             Constraint::Min(info_width),
-            Constraint::Length((area[0].width.checked_sub(info_width)).unwrap_or_default() / 2),
+            Constraint::Length(area[0].width.saturating_sub(info_width) / 2),
         ],
     )
     .split(area[0])[1];
 
     let max_height = lines.len().saturating_sub(info_area.height as usize);
-    if max_height + 2 < state.general_scroll_index {
-        state.general_scroll_index = max_height + 2;
+    if max_height + 2 < state.view.general_scroll_index {
+        state.view.general_scroll_index = max_height + 2;
     }
 
     frame.render_widget(
@@ -432,6 +729,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                         "File".cyan(),
                         Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
                         state
+                            .view
                             .analyzer
                             .file
                             .name
@@ -443,7 +741,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
                     .title_alignment(Alignment::Center)
                     .border_style(Style::default().fg(Color::Rgb(100, 100, 100))),
             )
-            .scroll((state.general_scroll_index as u16, 0))
+            .scroll((state.view.general_scroll_index as u16, 0))
             .wrap(Wrap { trim: true }),
         info_area,
     );
@@ -455,18 +753,17 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
             vertical: 1,
             horizontal: 0,
         }),
-        &mut ScrollbarState::new(max_height).position(state.general_scroll_index),
+        &mut ScrollbarState::new(max_height).position(state.view.general_scroll_index),
     );
 
-    if state.list.items.is_empty() {
+    if state.view.list.items.is_empty() {
         return;
     }
 
     let max_row_width = state
-        .list
-        .items
+        .rows()
         .iter()
-        .map(|v| v.join(" ").len())
+        .map(|v| v.join(" ").width())
         .max()
         .unwrap_or_default() as u16
         + 5;
@@ -474,9 +771,9 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
     let table_area = Layout::new(
         Direction::Horizontal,
         [
-            Constraint::Length((area[1].width.checked_sub(max_row_width)).unwrap_or_default() / 2),
+            Constraint::Length(area[1].width.saturating_sub(max_row_width) / 2),
             Constraint::Min(max_row_width),
-            Constraint::Length((area[1].width.checked_sub(max_row_width)).unwrap_or_default() / 2),
+            Constraint::Length(area[1].width.saturating_sub(max_row_width) / 2),
         ],
     )
     .split(area[1]);
@@ -484,17 +781,34 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
     let table_area = Layout::new(
         Direction::Vertical,
         [
-            Constraint::Min(state.list.items.len() as u16 + 3),
+            Constraint::Min(state.view.list.items.len() as u16 + 3),
             Constraint::Percentage(100),
         ],
     )
     .split(table_area[1])[0];
+    let kinds = state
+        .view
+        .analyzer
+        .dependencies()
+        .rows()
+        .into_iter()
+        .map(|(kind, _)| kind)
+        .collect::<Vec<_>>();
+    let rows = state.rows();
     let items = state
+        .view
         .list
         .items
-        .clone()
-        .into_iter()
-        .map(Row::new)
+        .iter()
+        .map(|i| {
+            Row::new(rows[*i].clone()).style(match kinds.get(*i) {
+                Some(RowKind::Missing) => Style::default().fg(Color::Red),
+                Some(RowKind::Duplicate) | Some(RowKind::Searched) => {
+                    Style::default().fg(Color::Rgb(100, 100, 100))
+                }
+                _ => Style::default(),
+            })
+        })
         .collect::<Vec<Row>>();
 
     frame.render_stateful_widget(
@@ -502,14 +816,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
             items.clone(),
             &[
                 Constraint::Min(
-                    state
-                        .list
-                        .items
-                        .iter()
-                        .map(|v| v[0].len())
-                        .max()
-                        .unwrap_or_default() as u16
-                        + 1,
+                    rows.iter().map(|v| v[0].width()).max().unwrap_or_default() as u16 + 1,
                 ),
                 Constraint::Percentage(100),
             ],
@@ -517,11 +824,20 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
         .header(Row::new(vec!["Library".bold(), "Path".bold()]))
         .block(
             Block::bordered()
-                .title(vec![
-                    "|".fg(Color::Rgb(100, 100, 100)),
-                    "Dependencies".fg(state.accent_color).bold(),
-                    "|".fg(Color::Rgb(100, 100, 100)),
-                ])
+                .title(match state.view.analyzer.dependencies().missing().count() {
+                    0 => vec![
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        "Dependencies".fg(state.accent_color).bold(),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                    ],
+                    missing => vec![
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        "Dependencies".fg(state.accent_color).bold(),
+                        " (".fg(Color::Rgb(100, 100, 100)),
+                        format!("{missing} missing").red().bold(),
+                        ")|".fg(Color::Rgb(100, 100, 100)),
+                    ],
+                })
                 .title_alignment(Alignment::Center)
                 .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
                 .title_bottom(
@@ -541,7 +857,7 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
         )
         .row_highlight_style(Style::default().fg(Color::Green)),
         table_area,
-        &mut state.list.state,
+        &mut state.view.list.state,
     );
     frame.render_stateful_widget(
         Scrollbar::new(ScrollbarOrientation::VerticalRight)
@@ -552,8 +868,51 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
             horizontal: 0,
         }),
         &mut ScrollbarState::new(items.len())
-            .position(state.list.state.selected().unwrap_or_default()),
+            .position(state.view.list.state.selected().unwrap_or_default()),
     );
+    let area = frame.area();
+    render_dependency_details(state, area, frame);
+}
+
+/// Renders the details popup of the selected dependency.
+fn render_dependency_details(state: &State<'_>, area: Rect, frame: &mut Frame<'_>) {
+    if !state.show_details {
+        return;
+    }
+    let Some(dependency) = state.selected_dependency() else {
+        return;
+    };
+    let lines = state
+        .view
+        .analyzer
+        .dependencies()
+        .explain(dependency)
+        .into_iter()
+        .map(|(name, value)| {
+            let skipped = name.starts_with('-');
+            Line::from(vec![
+                if skipped {
+                    name.fg(Color::Rgb(100, 100, 100))
+                } else {
+                    name.cyan()
+                },
+                Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                if skipped {
+                    value.into()
+                } else if value == "not found" {
+                    value.red().bold()
+                } else {
+                    value.fg(state.accent_color)
+                },
+            ])
+        })
+        .collect::<Vec<_>>();
+    let popup = Popup::new(Text::from(lines)).title(Line::from(vec![
+        "|".fg(Color::Rgb(100, 100, 100)),
+        "Details".fg(state.accent_color).bold(),
+        "|".fg(Color::Rgb(100, 100, 100)),
+    ]));
+    frame.render_widget(&popup, area);
 }
 
 /// Renders the static analysis tab.
@@ -569,10 +928,10 @@ pub fn render_general_info(state: &mut State, frame: &mut Frame, rect: Rect) {
 /// - notes
 pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect) {
     let headers: Vec<Line> = state
+        .view
         .analyzer
-        .elf
-        .info(&Info::FileHeaders)
-        .items()
+        .info_rows(Info::FileHeaders)
+        .rows
         .iter()
         .map(|items| {
             Line::from(vec![
@@ -586,7 +945,7 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
         })
         .collect();
     let mut notes = Vec::new();
-    for note in state.analyzer.elf.notes.inner.iter() {
+    for note in state.view.analyzer.elf.notes.inner.iter() {
         notes.push(Line::from(vec![
             "Notes in ".cyan(),
             note.name.to_string().cyan().italic(),
@@ -618,8 +977,8 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
         )
         .split(chunks[0]);
         let max_height = headers.len().saturating_sub(chunks[0].height as usize);
-        if max_height + 3 < state.headers_scroll_index {
-            state.headers_scroll_index = max_height + 3;
+        if max_height + 3 < state.view.headers_scroll_index {
+            state.view.headers_scroll_index = max_height + 3;
         }
         frame.render_widget(
             Paragraph::new(headers)
@@ -631,14 +990,14 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                             "|".fg(Color::Rgb(100, 100, 100)),
                         ])
                         .border_style({
-                            if state.block_index == 0 {
+                            if state.view.block_index == 0 {
                                 Style::default().fg(state.accent_color).bold()
                             } else {
                                 Style::default().fg(Color::Rgb(100, 100, 100))
                             }
                         }),
                 )
-                .scroll((state.headers_scroll_index as u16, 0))
+                .scroll((state.view.headers_scroll_index as u16, 0))
                 .wrap(Wrap { trim: true }),
             chunks[0],
         );
@@ -650,11 +1009,11 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                 vertical: 1,
                 horizontal: 0,
             }),
-            &mut ScrollbarState::new(max_height).position(state.headers_scroll_index),
+            &mut ScrollbarState::new(max_height).position(state.view.headers_scroll_index),
         );
         let max_height = notes.len().saturating_sub(chunks[0].height as usize);
-        if max_height + 2 < state.notes_scroll_index {
-            state.notes_scroll_index = max_height + 2;
+        if max_height + 2 < state.view.notes_scroll_index {
+            state.view.notes_scroll_index = max_height + 2;
         }
         frame.render_widget(
             Paragraph::new(notes)
@@ -665,13 +1024,13 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                             "Notes".fg(state.accent_color).bold(),
                             "|".fg(Color::Rgb(100, 100, 100)),
                         ])
-                        .border_style(Style::default().fg(if state.block_index == 1 {
+                        .border_style(Style::default().fg(if state.view.block_index == 1 {
                             Color::Yellow
                         } else {
                             Color::Rgb(100, 100, 100)
                         })),
                 )
-                .scroll((state.notes_scroll_index as u16, 0))
+                .scroll((state.view.notes_scroll_index as u16, 0))
                 .wrap(Wrap { trim: true }),
             chunks[1],
         );
@@ -683,7 +1042,7 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                 vertical: 1,
                 horizontal: 0,
             }),
-            &mut ScrollbarState::new(max_height).position(state.notes_scroll_index),
+            &mut ScrollbarState::new(max_height).position(state.view.notes_scroll_index),
         );
     }
     {
@@ -693,38 +1052,36 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
             [Constraint::Length(1), Constraint::Percentage(100)],
         )
         .split(area);
-        let tabs = Tabs::new(MAIN_TABS.iter().map(|v| Line::from(*v)))
-            .select(state.tab as usize)
+        let tabs = state.tabs();
+        let tabs = Tabs::new(tabs.iter().map(|v| Line::from(v.title())))
+            .select(tabs.iter().position(|v| *v == state.view.tab))
             .highlight_style(
                 Style::default()
                     .add_modifier(Modifier::BOLD)
                     .fg(state.accent_color),
             );
         frame.render_widget(tabs, chunks[0]);
-        let selected_index = state.list.state.selected().unwrap_or_default();
-        let items_len = state.list.items.len();
-        let page = selected_index / LIST_LIMIT;
-        let headers = ELF_INFO_TABS[state.info_index].headers();
-        let mut table_state = TableState::default();
-        table_state.select(Some(selected_index % LIST_LIMIT));
+        let selected_index = state.view.list.state.selected().unwrap_or_default();
+        let items_len = state.view.list.items.len();
+        let headers = ELF_INFO_TABS[state.view.info_index].headers();
+        let window = state.view.list.window(table_height(area));
+        let mut table_state = state.view.list.window_state(&window);
         let max_row_width = (area.width as usize / headers.len()).saturating_sub(2);
-        let items = state
-            .list
-            .items
+        let rows = state.rows();
+        let items = state.view.list.items[window.clone()]
             .iter()
-            .skip(page * LIST_LIMIT)
-            .take(LIST_LIMIT)
+            .filter_map(|i| rows.get(*i))
             .map(|items| {
                 Row::new(items.iter().enumerate().map(|(i, value)| {
                     Cell::from(Line::from(if value.width() > max_row_width && i == 0 {
                         let mut spans = highlight_search_result(
                             value.chars().take(max_row_width).collect::<String>().into(),
-                            &state.input,
+                            &state.view.input,
                         );
                         spans.push("…".fg(Color::Rgb(100, 100, 100)));
                         spans
                     } else {
-                        highlight_search_result(value.to_string().into(), &state.input)
+                        highlight_search_result(value.to_string().into(), &state.view.input)
                     }))
                 }))
             });
@@ -736,12 +1093,12 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                 )]
                 .repeat(headers.len()),
             )
-            .header(Row::new(
-                headers.to_vec().iter().map(|v| Cell::from((*v).bold())),
-            ))
+            .header(Row::new(headers.iter().enumerate().map(|(i, v)| {
+                Cell::from(TableSort::header(state.view.table_sort, i, v).bold())
+            })))
             .block(
                 Block::bordered()
-                    .border_style(Style::default().fg(if state.block_index == 2 {
+                    .border_style(Style::default().fg(if state.view.block_index == 2 {
                         Color::Yellow
                     } else {
                         Color::Rgb(100, 100, 100)
@@ -796,7 +1153,7 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
         .split(chunks[0]);
         frame.render_widget(Clear, chunks[1]);
         let tabs = Tabs::new(ELF_INFO_TABS.iter().map(|v| Line::from(v.title())))
-            .select(state.info_index)
+            .select(state.view.info_index)
             .style(Style::default().fg(Color::Cyan))
             .highlight_style(
                 Style::default()
@@ -810,15 +1167,19 @@ pub fn render_static_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
 
 /// Renders the strings tab.
 pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
-    let selected_index = state.list.state.selected().unwrap_or_default();
-    let items_len = state.list.items.len();
-    let page = selected_index / LIST_LIMIT;
-    let items = state
-        .list
-        .items
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let window = state.view.list.window(table_height(rect));
+    let mut list_state = state.view.list.window_state(&window);
+    let rows = state.rows();
+    let items = state.view.list.items[window]
         .iter()
-        .skip(page * LIST_LIMIT)
-        .take(LIST_LIMIT);
+        .filter_map(|i| rows.get(*i));
+    let headers = STRINGS_HEADERS
+        .iter()
+        .enumerate()
+        .map(|(i, v)| TableSort::header(state.view.table_sort, i, v))
+        .collect::<Vec<_>>();
     let left_padding = items
         .clone()
         .next_back()
@@ -828,10 +1189,30 @@ pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
         .map(|v| v.len())
         .unwrap_or_default()
         + 1;
-    if !state.strings_loaded {
+    let address_width = items
+        .clone()
+        .map(|items| items[1].len())
+        .chain([headers[1].width()])
+        .max()
+        .unwrap_or_default();
+    let section_width = items
+        .clone()
+        .map(|items| items[2].width())
+        .chain([headers[2].width()])
+        .max()
+        .unwrap_or_default()
+        .min(STRINGS_SECTION_WIDTH.max(headers[2].width()));
+    let encoding_width = STRINGS_ENCODING_WIDTH.max(headers[3].width());
+    let category_width = STRINGS_CATEGORY_WIDTH.max(headers[4].width());
+    let error = state.view.analyzer.jobs.error(JobKind::Strings);
+    if !state.view.strings_loaded || error.is_some() {
         frame.render_widget(Block::bordered(), rect);
         frame.render_widget(
-            Paragraph::new("Loading...".italic()).alignment(Alignment::Center),
+            Paragraph::new(match error {
+                Some(e) => e.to_string().red(),
+                None => "Loading...".italic(),
+            })
+            .alignment(Alignment::Center),
             rect.inner(Margin {
                 vertical: 1,
                 horizontal: 0,
@@ -839,16 +1220,34 @@ pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
         );
         return;
     }
-    let mut list_state = TableState::default();
-    list_state.select(Some(selected_index % LIST_LIMIT));
     let max_row_width = rect.width.saturating_sub(4) as usize;
     frame.render_stateful_widget(
         Table::new(
             items.map(|items| {
                 Row::new(vec![Cell::from({
                     let index = format!("{:>p$}", items[0], p = left_padding);
-                    let value = items[1].to_string();
-                    let mut spans = vec![index.clone().cyan(), " ".into()];
+                    let address = format!("{:>p$}", items[1], p = address_width);
+                    let section = format!(
+                        "{:<p$}",
+                        items[2].chars().take(section_width).collect::<String>(),
+                        p = section_width
+                    );
+                    let encoding = format!("{:<p$}", items[3], p = encoding_width);
+                    let category = format!("{:<p$}", items[4], p = category_width);
+                    let value = items[5].to_string();
+                    let mut spans = vec![
+                        index.clone().cyan(),
+                        " ".into(),
+                        address.clone().cyan(),
+                        " ".into(),
+                        section.clone().fg(state.accent_color),
+                        " ".into(),
+                        encoding.clone().fg(Color::Rgb(100, 100, 100)),
+                        " ".into(),
+                        category.clone().fg(state.accent_color).italic(),
+                        " ".into(),
+                    ];
+                    let index = format!("{index} {address} {section} {encoding} {category} ");
                     if index.width() + value.width() > max_row_width {
                         spans.extend(highlight_search_result(
                             value
@@ -857,7 +1256,7 @@ pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
                                 .take(max_row_width.saturating_sub(index.width()))
                                 .collect::<String>()
                                 .into(),
-                            &state.input,
+                            &state.view.input,
                         ));
                         spans.push("…".fg(Color::Rgb(100, 100, 100)));
                     } else {
@@ -867,7 +1266,7 @@ pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
                                 .map(|c| if c.is_whitespace() { ' ' } else { c })
                                 .collect::<String>()
                                 .into(),
-                            &state.input,
+                            &state.view.input,
                         ))
                     }
                     Line::from(spans)
@@ -875,15 +1274,64 @@ pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
             }),
             &[Constraint::Percentage(100)],
         )
-        .header(Row::new(vec![
-            format!(" {}", STRINGS_HEADERS.join(" ")).bold()
-        ]))
+        .header(Row::new(vec![format!(
+            "{:>p$} {:>a$} {:<s$} {:<e$} {:<c$} {}",
+            headers[0],
+            headers[1],
+            headers[2],
+            headers[3],
+            headers[4],
+            headers[5],
+            p = left_padding,
+            a = address_width,
+            s = section_width,
+            e = encoding_width,
+            c = category_width
+        )
+        .bold()]))
         .block(
             Block::bordered()
                 .title_top(
                     Line::from(vec![
                         "|".fg(Color::Rgb(100, 100, 100)),
-                        format!("Min length: {}", state.analyzer.strings_len)
+                        format!(
+                            "Filter: {}",
+                            state
+                                .view
+                                .strings_filter
+                                .map(|v| v.title())
+                                .unwrap_or("All")
+                        )
+                        .fg(state.accent_color)
+                        .bold(),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        format!(
+                            "Section: {}",
+                            state.view.strings_section.as_deref().unwrap_or("All")
+                        )
+                        .fg(state.accent_color)
+                        .bold(),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        format!(
+                            "Encoding: {}",
+                            if state.view.analyzer.strings_encodings.len() == STRING_ENCODINGS.len()
+                            {
+                                String::from("All")
+                            } else {
+                                state
+                                    .view
+                                    .analyzer
+                                    .strings_encodings
+                                    .iter()
+                                    .map(|v| v.title())
+                                    .collect::<Vec<_>>()
+                                    .join(",")
+                            }
+                        )
+                        .fg(state.accent_color)
+                        .bold(),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        format!("Min length: {}", state.view.analyzer.strings_len)
                             .fg(state.accent_color)
                             .bold(),
                         "|".fg(Color::Rgb(100, 100, 100)),
@@ -924,48 +1372,848 @@ pub fn render_strings(state: &mut State, frame: &mut Frame, rect: Rect) {
     );
 }
 
-/// Renders the cursor.
-fn render_cursor(state: &mut State<'_>, area: Rect, frame: &mut Frame<'_>) {
-    if state.input_mode {
-        let (x, y) = (
-            area.x
-                + Input::default()
-                    .with_value(format!("search: {}", state.input.value()))
-                    .visual_cursor() as u16
-                + 2,
-            area.bottom().saturating_sub(1),
+/// Renders the size tab.
+pub fn render_size(state: &mut State, frame: &mut Frame, rect: Rect) {
+    if SIZE_INFO_TABS[state.view.size_index] == SizeInfo::Treemap {
+        render_treemap(state, frame, rect);
+    } else {
+        render_size_table(state, frame, rect);
+    }
+    let chunks = Layout::new(
+        Direction::Vertical,
+        [Constraint::Length(1), Constraint::Percentage(100)],
+    )
+    .split(rect);
+    let chunks = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Length(1),
+            Constraint::Length(
+                SIZE_INFO_TABS
+                    .iter()
+                    .map(|v| v.title().len() as u16)
+                    .sum::<u16>()
+                    + ((SIZE_INFO_TABS.len() as u16 - 1) * 3)
+                    + 2,
+            ),
+            Constraint::Percentage(100),
+        ],
+    )
+    .split(chunks[0]);
+    frame.render_widget(Clear, chunks[1]);
+    let tabs = Tabs::new(SIZE_INFO_TABS.iter().map(|v| Line::from(v.title())))
+        .select(state.view.size_index)
+        .style(Style::default().fg(Color::Cyan))
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(state.accent_color),
         );
-        frame.render_widget(
-            Clear,
-            Rect {
-                x,
-                y,
-                width: 1,
-                height: 1,
+    frame.render_widget(tabs, chunks[1]);
+    render_details(state, rect, frame);
+}
+
+fn render_size_table(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let info = SIZE_INFO_TABS[state.view.size_index];
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let headers = info.headers();
+    let window = state.view.list.window(table_height(rect));
+    let mut table_state = state.view.list.window_state(&window);
+    let max_row_width = (rect.width as usize / 2).saturating_sub(4);
+    let rows = state.rows();
+    let items = state.view.list.items[window.clone()]
+        .iter()
+        .filter_map(|i| rows.get(*i))
+        .map(|items| {
+            Row::new(items.iter().enumerate().map(|(i, value)| {
+                Cell::from(Line::from(if value.width() > max_row_width && i == 0 {
+                    let mut spans = highlight_search_result(
+                        value.chars().take(max_row_width).collect::<String>().into(),
+                        &state.view.input,
+                    );
+                    spans.push("…".fg(Color::Rgb(100, 100, 100)));
+                    spans
+                } else {
+                    highlight_search_result(value.to_string().into(), &state.view.input)
+                }))
+            }))
+        });
+    let size = state.view.analyzer.size();
+    frame.render_stateful_widget(
+        Table::new(
+            items,
+            [Constraint::Percentage(50)].into_iter().chain(
+                [Constraint::Percentage(
+                    (50 / headers.len().saturating_sub(1))
+                        .try_into()
+                        .unwrap_or_default(),
+                )]
+                .repeat(headers.len().saturating_sub(1)),
+            ),
+        )
+        .header(Row::new(
+            headers.to_vec().iter().map(|v| Cell::from((*v).bold())),
+        ))
+        .block(
+            Block::bordered()
+                .title_top(
+                    Line::from(vec![
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        "File: ".into(),
+                        ByteSize(size.file_size).to_string().fg(state.accent_color),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        "VM: ".into(),
+                        ByteSize(size.vm_size).to_string().fg(state.accent_color),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                    ])
+                    .right_aligned(),
+                )
+                .title_bottom(
+                    if items_len != 0 {
+                        Line::from(vec![
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            format!("{}/{}", selected_index.saturating_add(1), items_len)
+                                .fg(state.accent_color)
+                                .bold(),
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                        ])
+                    } else {
+                        Line::default()
+                    }
+                    .right_aligned(),
+                )
+                .title_bottom(get_input_line(state)),
+        )
+        .row_highlight_style(Style::default().fg(Color::Green)),
+        rect,
+        &mut table_state,
+    );
+    render_cursor(state, rect, frame);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        rect.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut ScrollbarState::new(items_len).position(selected_index),
+    );
+}
+
+/// Renders the size treemap.
+fn render_treemap(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let size = state.view.analyzer.size();
+    let items = size
+        .treemap_entries(state.view.size_section.as_deref())
+        .into_iter()
+        .take(TREEMAP_LIMIT)
+        .map(|entry| TreemapItem {
+            label: format!(
+                "{} ({})",
+                entry.name,
+                ByteSize(entry.file_size.max(entry.vm_size))
+            ),
+            value: entry.file_size.max(entry.vm_size),
+            children: if state.view.size_section.is_none() {
+                size.section_symbols(&entry.name)
+                    .take(TREEMAP_LIMIT)
+                    .map(|symbol| TreemapItem {
+                        label: symbol.name.to_string(),
+                        value: symbol.file_size.max(symbol.vm_size),
+                        children: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                Vec::new()
             },
+        })
+        .collect::<Vec<_>>();
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let block = Block::bordered()
+        .title_top(
+            Line::from(vec![
+                "|".fg(Color::Rgb(100, 100, 100)),
+                state
+                    .view
+                    .size_section
+                    .as_deref()
+                    .unwrap_or("Sections")
+                    .fg(state.accent_color)
+                    .bold(),
+                "|".fg(Color::Rgb(100, 100, 100)),
+            ])
+            .right_aligned(),
+        )
+        .title_bottom(
+            if let Some(selected) = state.selected_row() {
+                Line::from(vec![
+                    "|".fg(Color::Rgb(100, 100, 100)),
+                    selected[0].to_string().fg(state.accent_color),
+                    "|".fg(Color::Rgb(100, 100, 100)),
+                    selected[1].to_string().into(),
+                    "|".fg(Color::Rgb(100, 100, 100)),
+                    format!("{}/{}", selected_index.saturating_add(1), items_len)
+                        .fg(state.accent_color)
+                        .bold(),
+                    "|".fg(Color::Rgb(100, 100, 100)),
+                ])
+            } else {
+                Line::default()
+            }
+            .right_aligned(),
         );
-        frame.set_cursor_position(Position::new(x, y));
-    }
+    frame.render_widget(
+        Treemap::new(&items)
+            .select(Some(selected_index))
+            .highlight_color(state.accent_color),
+        block.inner(rect),
+    );
+    frame.render_widget(block, rect);
 }
 
-/// Renders details popup.
-fn render_details(state: &mut State<'_>, area: Rect, frame: &mut Frame<'_>) {
-    if state.show_details {
-        let headers;
-        match state.tab {
-            Tab::StaticAnalysis => {
-                headers = ELF_INFO_TABS[state.info_index].headers();
-            }
+/// Renders the diff tab.
+pub fn render_diff(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let Some(diff) = &state.view.diff else {
+        frame.render_widget(
+            Paragraph::new(Text::from(vec![Line::from(vec![
+                "Run ".into(),
+                "binsider diff <old> <new>".yellow(),
+                " to compare two files.".into(),
+            ])]))
+            .block(Block::bordered())
+            .alignment(Alignment::Center),
+            rect,
+        );
+        return;
+    };
+    let info = DIFF_INFO_TABS[state.view.diff_index];
+    let (added, removed, modified) = diff.summary(&info);
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let headers = info.headers();
+    let window = state.view.list.window(table_height(rect));
+    let mut table_state = state.view.list.window_state(&window);
+    let chunks = Layout::new(
+        Direction::Vertical,
+        [Constraint::Length(1), Constraint::Percentage(100)],
+    )
+    .split(rect);
+    let max_row_width = (rect.width as usize / headers.len().saturating_sub(1)).saturating_sub(2);
+    let rows = state.rows();
+    let items = state.view.list.items[window.clone()]
+        .iter()
+        .filter_map(|i| rows.get(*i))
+        .map(|items| {
+            let style = match Change::from_marker(&items[0]) {
+                Change::Added => Style::default().fg(Color::Green),
+                Change::Removed => Style::default().fg(Color::Red),
+                Change::Modified => Style::default().fg(Color::Yellow),
+                Change::Unchanged => Style::default(),
+            };
+            Row::new(items.iter().enumerate().map(|(i, value)| {
+                Cell::from(Line::from(if value.width() > max_row_width && i == 1 {
+                    let mut spans = highlight_search_result(
+                        value.chars().take(max_row_width).collect::<String>().into(),
+                        &state.view.input,
+                    );
+                    spans.push("…".fg(Color::Rgb(100, 100, 100)));
+                    spans
+                } else {
+                    highlight_search_result(value.to_string().into(), &state.view.input)
+                }))
+            }))
+            .style(style)
+        });
+    frame.render_stateful_widget(
+        Table::new(
+            items,
+            [Constraint::Length(1)].into_iter().chain(
+                [Constraint::Percentage(
+                    (100 / headers.len().saturating_sub(1))
+                        .try_into()
+                        .unwrap_or_default(),
+                )]
+                .repeat(headers.len().saturating_sub(1)),
+            ),
+        )
+        .header(Row::new(
+            headers.to_vec().iter().map(|v| Cell::from((*v).bold())),
+        ))
+        .block(
+            Block::bordered()
+                .title_top(
+                    if info == DiffInfo::Abi {
+                        Line::from(vec![
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            match diff.abi.verdict() {
+                                Verdict::Compatible => "compatible".green().bold(),
+                                Verdict::Suspect => "suspect".yellow().bold(),
+                                Verdict::Breaking => "breaking".red().bold(),
+                            },
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                        ])
+                    } else {
+                        Line::default()
+                    }
+                    .right_aligned(),
+                )
+                .title_top(
+                    Line::from(vec![
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                        diff.old.to_string().red(),
+                        " → ".fg(Color::Rgb(100, 100, 100)),
+                        diff.new.to_string().green(),
+                        "|".fg(Color::Rgb(100, 100, 100)),
+                    ])
+                    .right_aligned(),
+                )
+                .title_bottom(
+                    if items_len != 0 {
+                        Line::from(vec![
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            format!("+{added}").green().bold(),
+                            " ".into(),
+                            format!("-{removed}").red().bold(),
+                            " ".into(),
+                            format!("~{modified}").yellow().bold(),
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            format!("{}/{}", selected_index.saturating_add(1), items_len)
+                                .fg(state.accent_color)
+                                .bold(),
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                        ])
+                    } else {
+                        Line::default()
+                    }
+                    .right_aligned(),
+                )
+                .title_bottom(get_input_line(state)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        rect,
+        &mut table_state,
+    );
+    render_cursor(state, rect, frame);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        rect.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut ScrollbarState::new(items_len).position(selected_index),
+    );
+
+    let chunks = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Length(1),
+            Constraint::Length(
+                DIFF_INFO_TABS
+                    .iter()
+                    .map(|v| v.title().len() as u16)
+                    .sum::<u16>()
+                    + ((DIFF_INFO_TABS.len() as u16 - 1) * 3)
+                    + 2,
+            ),
+            Constraint::Percentage(100),
+        ],
+    )
+    .split(chunks[0]);
+    frame.render_widget(Clear, chunks[1]);
+    let tabs = Tabs::new(DIFF_INFO_TABS.iter().map(|v| Line::from(v.title())))
+        .select(state.view.diff_index)
+        .style(Style::default().fg(Color::Cyan))
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(state.accent_color),
+        );
+    frame.render_widget(tabs, chunks[1]);
+    render_details(state, rect, frame);
+}
+
+/// Renders the process tab.
+pub fn render_process(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let Some(process) = &state.view.analyzer.process else {
+        frame.render_widget(
+            Paragraph::new(Text::from(vec![Line::from(vec![
+                "Run ".into(),
+                "binsider --pid <PID>".yellow(),
+                " to analyze a running process.".into(),
+            ])]))
+            .block(Block::bordered())
+            .alignment(Alignment::Center),
+            rect,
+        );
+        return;
+    };
+    let info = PROCESS_INFO_TABS[state.view.process_index];
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let headers = info.headers();
+    let window = state.view.list.window(table_height(rect));
+    let mut table_state = state.view.list.window_state(&window);
+    let chunks = Layout::new(
+        Direction::Vertical,
+        [Constraint::Length(1), Constraint::Percentage(100)],
+    )
+    .split(rect);
+    let rows = state.rows();
+    let items = state.view.list.items[window.clone()]
+        .iter()
+        .filter_map(|i| rows.get(*i))
+        .map(|items| {
+            let style = match info {
+                ProcessInfo::Maps if items[5] != "-" => Style::default().fg(state.accent_color),
+                ProcessInfo::Maps => Style::default(),
+                ProcessInfo::Libraries => match items[1].as_str() {
+                    "loaded" => Style::default().fg(Color::Green),
+                    "dlopen" => Style::default().fg(Color::Yellow),
+                    _ => Style::default().fg(Color::Red),
+                },
+            };
+            Row::new(items.iter().map(|value| {
+                Cell::from(Line::from(highlight_search_result(
+                    value.to_string().into(),
+                    &state.view.input,
+                )))
+            }))
+            .style(style)
+        });
+    let widths = match info {
+        ProcessInfo::Maps => vec![
+            Constraint::Length(16),
+            Constraint::Length(16),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Length(11),
+            Constraint::Length(13),
+            Constraint::Min(0),
+        ],
+        ProcessInfo::Libraries => vec![
+            Constraint::Percentage(30),
+            Constraint::Length(12),
+            Constraint::Min(0),
+        ],
+    };
+    frame.render_stateful_widget(
+        Table::new(items, widths)
+            .header(Row::new(
+                headers.to_vec().iter().map(|v| Cell::from((*v).bold())),
+            ))
+            .block(
+                Block::bordered()
+                    .title_top(
+                        Line::from(vec![
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            "PID: ".into(),
+                            process.pid.to_string().fg(state.accent_color).bold(),
+                            if process.running {
+                                "".into()
+                            } else {
+                                " (exited)".red()
+                            },
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                        ])
+                        .right_aligned(),
+                    )
+                    .title_bottom(
+                        if items_len != 0 {
+                            Line::from(vec![
+                                "|".fg(Color::Rgb(100, 100, 100)),
+                                format!("{}/{}", selected_index.saturating_add(1), items_len)
+                                    .fg(state.accent_color)
+                                    .bold(),
+                                "|".fg(Color::Rgb(100, 100, 100)),
+                            ])
+                        } else {
+                            Line::default()
+                        }
+                        .right_aligned(),
+                    )
+                    .title_bottom(get_input_line(state)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        rect,
+        &mut table_state,
+    );
+    render_cursor(state, rect, frame);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        rect.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut ScrollbarState::new(items_len).position(selected_index),
+    );
+
+    let chunks = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Length(1),
+            Constraint::Length(
+                PROCESS_INFO_TABS
+                    .iter()
+                    .map(|v| v.title().len() as u16)
+                    .sum::<u16>()
+                    + ((PROCESS_INFO_TABS.len() as u16 - 1) * 3)
+                    + 2,
+            ),
+            Constraint::Percentage(100),
+        ],
+    )
+    .split(chunks[0]);
+    frame.render_widget(Clear, chunks[1]);
+    let tabs = Tabs::new(PROCESS_INFO_TABS.iter().map(|v| Line::from(v.title())))
+        .select(state.view.process_index)
+        .style(Style::default().fg(Color::Cyan))
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(state.accent_color),
+        );
+    frame.render_widget(tabs, chunks[1]);
+    render_details(state, rect, frame);
+}
+
+/// Renders the scan tab.
+pub fn render_scan(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let Some(scan) = &state.scan else {
+        frame.render_widget(
+            Paragraph::new(Text::from(vec![Line::from(vec![
+                "Run ".into(),
+                "binsider <dir>".yellow(),
+                " or ".into(),
+                "binsider <file>...".yellow(),
+                " to scan multiple files.".into(),
+            ])]))
+            .block(Block::bordered())
+            .alignment(Alignment::Center),
+            rect,
+        );
+        return;
+    };
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let window = state.view.list.window(table_height(rect));
+    let mut table_state = state.view.list.window_state(&window);
+    let rows = state.rows();
+    let items = state.view.list.items[window.clone()]
+        .iter()
+        .filter_map(|i| rows.get(*i))
+        .map(|items| {
+            Row::new(items.iter().enumerate().map(|(i, value)| {
+                let style = match (i, value.as_str()) {
+                    // Hardening flags (PIE, RELRO and NX).
+                    (5..=7, "yes" | "full") => Style::default().fg(Color::Green),
+                    (5..=7, "partial") => Style::default().fg(Color::Yellow),
+                    (5..=7, "no") => Style::default().fg(Color::Red),
+                    (0, _) => Style::default().fg(state.accent_color),
+                    _ => Style::default(),
+                };
+                Cell::from(Line::from(highlight_search_result(
+                    value.to_string().into(),
+                    &state.view.input,
+                )))
+                .style(style)
+            }))
+        });
+    frame.render_stateful_widget(
+        Table::new(
+            items,
+            [
+                Constraint::Min(20),
+                Constraint::Length(13),
+                Constraint::Length(9),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(4),
+                Constraint::Length(7),
+                Constraint::Length(3),
+                Constraint::Length(4),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(
+            SCAN_HEADERS.iter().map(|v| Cell::from((*v).bold())),
+        ))
+        .block(
+            Block::bordered()
+                .title_top(
+                    if scan.skipped != 0 {
+                        Line::from(vec![
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            format!("{} skipped", scan.skipped).yellow(),
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                        ])
+                    } else {
+                        Line::default()
+                    }
+                    .right_aligned(),
+                )
+                .title_bottom(
+                    if items_len != 0 {
+                        Line::from(vec![
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                            format!("{}/{}", selected_index.saturating_add(1), items_len)
+                                .fg(state.accent_color)
+                                .bold(),
+                            "|".fg(Color::Rgb(100, 100, 100)),
+                        ])
+                    } else {
+                        Line::default()
+                    }
+                    .right_aligned(),
+                )
+                .title_bottom(get_input_line(state)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        rect,
+        &mut table_state,
+    );
+    render_cursor(state, rect, frame);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        rect.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut ScrollbarState::new(items_len).position(selected_index),
+    );
+}
+
+/// Renders the insights tab.
+pub fn render_insights(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let info = INSIGHTS_INFO_TABS[state.view.insights_index];
+    let chunks = Layout::new(
+        Direction::Vertical,
+        [Constraint::Length(1), Constraint::Percentage(100)],
+    )
+    .split(rect);
+    let message =
+        match info {
+            InsightInfo::Rules if state.view.analyzer.rules.is_empty() => Some(Line::from(vec![
+                "Run ".into(),
+                "binsider --rules <PATH>".yellow(),
+                " to match YARA-style rules.".into(),
+            ])),
+            InsightInfo::Rules if state.view.analyzer.rule_matches.is_none() => {
+                Some(match state.view.analyzer.jobs.error(JobKind::Rules) {
+                    Some(e) => Line::from(e.to_string().red()),
+                    None => Line::from("Matching rules...".italic()),
+                })
+            }
+            InsightInfo::Crypto if state.view.analyzer.crypto_constants.is_none() => Some(
+                match state.view.analyzer.jobs.error(JobKind::CryptoConstants) {
+                    Some(e) => Line::from(e.to_string().red()),
+                    None => Line::from("Loading...".italic()),
+                },
+            ),
+            InsightInfo::Crypto
+                if state.view.list.items.is_empty() && state.view.input.value().is_empty() =>
+            {
+                Some(Line::from("No cryptographic constants found."))
+            }
+            InsightInfo::Capabilities if state.view.analyzer.capabilities().imports.is_empty() => {
+                Some(Line::from("No interesting imports found."))
+            }
+            _ => None,
+        };
+    if let Some(message) = message {
+        frame.render_widget(
+            Paragraph::new(Text::from(vec![message]))
+                .block(Block::bordered())
+                .alignment(Alignment::Center),
+            rect,
+        );
+    } else {
+        render_insights_table(state, frame, rect);
+    }
+    let chunks = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Length(1),
+            Constraint::Length(
+                INSIGHTS_INFO_TABS
+                    .iter()
+                    .map(|v| v.title().len() as u16)
+                    .sum::<u16>()
+                    + ((INSIGHTS_INFO_TABS.len() as u16 - 1) * 3)
+                    + 2,
+            ),
+            Constraint::Percentage(100),
+        ],
+    )
+    .split(chunks[0]);
+    frame.render_widget(Clear, chunks[1]);
+    let tabs = Tabs::new(INSIGHTS_INFO_TABS.iter().map(|v| Line::from(v.title())))
+        .select(state.view.insights_index)
+        .style(Style::default().fg(Color::Cyan))
+        .highlight_style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(state.accent_color),
+        );
+    frame.render_widget(tabs, chunks[1]);
+    render_details(state, rect, frame);
+}
+
+/// Returns the headers of the insights table.
+fn insights_headers(state: &State) -> &'static [&'static str] {
+    match &INSIGHTS_INFO_TABS[state.view.insights_index] {
+        InsightInfo::Capabilities if state.view.insights_capability.is_some() => IMPORT_HEADERS,
+        info => info.headers(),
+    }
+}
+
+/// Renders the insights table.
+fn render_insights_table(state: &mut State, frame: &mut Frame, rect: Rect) {
+    let selected_index = state.view.list.state.selected().unwrap_or_default();
+    let items_len = state.view.list.items.len();
+    let headers = insights_headers(state);
+    let window = state.view.list.window(table_height(rect));
+    let mut table_state = state.view.list.window_state(&window);
+    let rows = state.rows();
+    let items = state.view.list.items[window.clone()]
+        .iter()
+        .filter_map(|i| rows.get(*i))
+        .map(|items| {
+            Row::new(items.iter().map(|value| {
+                Cell::from(Line::from(highlight_search_result(
+                    value.to_string().into(),
+                    &state.view.input,
+                )))
+            }))
+        });
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let width = state
+                .view
+                .list
+                .items
+                .iter()
+                .filter_map(|row| rows.get(*row))
+                .map(|items| items[i].width())
+                .max()
+                .unwrap_or_default()
+                .max(header.width());
+            if i == headers.len() - 1 {
+                Constraint::Min(width as u16)
+            } else {
+                Constraint::Length(width as u16)
+            }
+        })
+        .collect::<Vec<_>>();
+    frame.render_stateful_widget(
+        Table::new(items, widths)
+            .header(Row::new(
+                headers.to_vec().iter().map(|v| Cell::from((*v).bold())),
+            ))
+            .block(
+                Block::bordered()
+                    .title_bottom(
+                        if items_len != 0 {
+                            Line::from(vec![
+                                "|".fg(Color::Rgb(100, 100, 100)),
+                                format!("{}/{}", selected_index.saturating_add(1), items_len)
+                                    .fg(state.accent_color)
+                                    .bold(),
+                                "|".fg(Color::Rgb(100, 100, 100)),
+                            ])
+                        } else {
+                            Line::default()
+                        }
+                        .right_aligned(),
+                    )
+                    .title_bottom(get_input_line(state)),
+            )
+            .row_highlight_style(Style::default().fg(Color::Green)),
+        rect,
+        &mut table_state,
+    );
+    render_cursor(state, rect, frame);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        rect.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut ScrollbarState::new(items_len).position(selected_index),
+    );
+}
+
+/// Returns the number of the rows that fit into the bordered table with a header.
+fn table_height(area: Rect) -> usize {
+    area.height.saturating_sub(3) as usize
+}
+
+/// Renders the cursor.
+fn render_cursor(state: &mut State<'_>, area: Rect, frame: &mut Frame<'_>) {
+    if state.input_mode {
+        let (x, y) = (
+            area.x
+                + Input::default()
+                    .with_value(format!("search: {}", state.view.input.value()))
+                    .visual_cursor() as u16
+                + 2,
+            area.bottom().saturating_sub(1),
+        );
+        frame.render_widget(
+            Clear,
+            Rect {
+                x,
+                y,
+                width: 1,
+                height: 1,
+            },
+        );
+        frame.set_cursor_position(Position::new(x, y));
+    }
+}
+
+/// Renders details popup.
+fn render_details(state: &mut State<'_>, area: Rect, frame: &mut Frame<'_>) {
+    if state.show_details {
+        let headers;
+        match state.view.tab {
+            Tab::StaticAnalysis => {
+                headers = ELF_INFO_TABS[state.view.info_index].headers();
+            }
             Tab::Strings => {
                 headers = STRINGS_HEADERS;
             }
+            Tab::Size => {
+                headers = SIZE_INFO_TABS[state.view.size_index].headers();
+            }
+            Tab::Diff => {
+                headers = DIFF_INFO_TABS[state.view.diff_index].headers();
+            }
+            Tab::Insights => {
+                headers = insights_headers(state);
+            }
+            Tab::Process => {
+                headers = PROCESS_INFO_TABS[state.view.process_index].headers();
+            }
             _ => {
                 unimplemented!()
             }
         }
         let max_row_width = (area.width - 2) / 2;
-        let items = state.list.selected().cloned().unwrap_or_default();
-        let lines: Vec<Line> = items
+        let items = state.selected_row().cloned().unwrap_or_default();
+        let mut lines: Vec<Line> = items
             .iter()
             .enumerate()
             .flat_map(|(i, v)| {
@@ -1000,6 +2248,72 @@ fn render_details(state: &mut State<'_>, area: Rect, frame: &mut Frame<'_>) {
                 lines
             })
             .collect();
+        if state.view.tab == Tab::Insights
+            && INSIGHTS_INFO_TABS[state.view.insights_index] == InsightInfo::Rules
+        {
+            if let Some(rule_match) = state
+                .view
+                .analyzer
+                .rule_matches
+                .iter()
+                .flatten()
+                .find(|v| Some(&v.rule) == items.first())
+            {
+                lines.push(Line::default());
+                lines.extend(rule_match.meta.iter().map(|(key, value)| {
+                    Line::from(vec![
+                        Span::styled(key.to_string(), Style::default().fg(Color::Cyan)),
+                        Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                        value.to_string().into(),
+                    ])
+                }));
+                lines.push(Line::from(vec![
+                    Span::styled("Matches", Style::default().fg(Color::Cyan)),
+                    Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                    Span::styled(
+                        rule_match.strings.len().to_string(),
+                        Style::default().fg(state.accent_color),
+                    ),
+                ]));
+            }
+        }
+        if let Some((address, size)) = state.selected_address() {
+            lines.push(Line::default());
+            match &state.view.analyzer.xrefs {
+                Some(xrefs) => {
+                    let references = xrefs.references(address, size);
+                    lines.push(Line::from(vec![
+                        Span::styled("References", Style::default().fg(Color::Cyan)),
+                        Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                        Span::styled(
+                            references.len().to_string(),
+                            Style::default().fg(state.accent_color),
+                        ),
+                    ]));
+                    lines.extend(references.iter().take(XREFS_LIMIT).map(|xref| {
+                        Line::from(vec![
+                            Span::raw("- ").fg(Color::Rgb(100, 100, 100)),
+                            xrefs.describe(xref).into(),
+                        ])
+                    }));
+                    if references.len() > XREFS_LIMIT {
+                        lines.push(
+                            format!("… and {} more", references.len() - XREFS_LIMIT)
+                                .fg(Color::Rgb(100, 100, 100))
+                                .into(),
+                        );
+                    }
+                }
+                None => lines.push(Line::from(vec![
+                    Span::styled("References", Style::default().fg(Color::Cyan)),
+                    Span::raw(": ").fg(Color::Rgb(100, 100, 100)),
+                    match state.view.analyzer.jobs.error(JobKind::Xrefs) {
+                        Some(e) => e.to_string().red(),
+                        None => "Loading...".italic(),
+                    },
+                ])),
+            }
+        }
         let popup = Popup::new(Text::from(lines)).title(Line::from(vec![
             "|".fg(Color::Rgb(100, 100, 100)),
             "Details".fg(state.accent_color).bold(),
@@ -1011,7 +2325,7 @@ fn render_details(state: &mut State<'_>, area: Rect, frame: &mut Frame<'_>) {
 
 /// Renders the dynamic analysis tab.
 pub fn render_dynamic_analysis(state: &mut State, frame: &mut Frame, rect: Rect) {
-    if !state.system_calls_loaded {
+    if !state.view.system_calls_loaded {
         let lines = if cfg!(feature = "dynamic-analysis") {
             vec![Line::from(vec![
                 "Press ".into(),
@@ -1040,23 +2354,25 @@ pub fn render_dynamic_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
         );
     } else {
         let max_height = state
+            .view
             .analyzer
             .system_calls
             .len()
             .saturating_sub(rect.height as usize)
             + 2;
-        if max_height < state.dynamic_scroll_index {
-            state.dynamic_scroll_index = max_height;
+        if max_height < state.view.dynamic_scroll_index {
+            state.view.dynamic_scroll_index = max_height;
         }
 
         frame.render_widget(
             Paragraph::new(
                 state
+                    .view
                     .analyzer
                     .system_calls
                     .clone()
                     .into_iter()
-                    .map(|line| highlight_search_result(line, &state.input).into())
+                    .map(|line| highlight_search_result(line, &state.view.input).into())
                     .collect::<Vec<Line>>(),
             )
             .block(
@@ -1071,6 +2387,7 @@ pub fn render_dynamic_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                             "|".fg(Color::Rgb(100, 100, 100)),
                             "Total: ".into(),
                             state
+                                .view
                                 .analyzer
                                 .system_calls
                                 .len()
@@ -1083,7 +2400,7 @@ pub fn render_dynamic_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                     )
                     .title_bottom(get_input_line(state)),
             )
-            .scroll((state.dynamic_scroll_index as u16, 0)),
+            .scroll((state.view.dynamic_scroll_index as u16, 0)),
             rect,
         );
 
@@ -1096,11 +2413,12 @@ pub fn render_dynamic_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
                 vertical: 1,
                 horizontal: 0,
             }),
-            &mut ScrollbarState::new(max_height).position(state.dynamic_scroll_index),
+            &mut ScrollbarState::new(max_height).position(state.view.dynamic_scroll_index),
         );
 
-        if state.show_details && !state.analyzer.tracer.summary.is_empty() {
+        if state.show_details && !state.view.analyzer.tracer.summary.is_empty() {
             let summary = state
+                .view
                 .analyzer
                 .tracer
                 .summary
@@ -1121,11 +2439,11 @@ pub fn render_dynamic_analysis(state: &mut State, frame: &mut Frame, rect: Rect)
 
 /// Returns the input line.
 fn get_input_line<'a>(state: &'a State) -> Line<'a> {
-    if !state.input.value().is_empty() || state.input_mode {
+    if !state.view.input.value().is_empty() || state.input_mode {
         Line::from(vec![
             "|".fg(Color::Rgb(100, 100, 100)),
             "search: ".yellow(),
-            state.input.value().fg(state.accent_color),
+            state.view.input.value().fg(state.accent_color),
             if state.input_mode { " " } else { "" }.into(),
             "|".fg(Color::Rgb(100, 100, 100)),
         ])
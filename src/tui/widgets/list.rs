@@ -1,4 +1,5 @@
 use ratatui::widgets::TableState as State;
+use std::ops::Range;
 
 /// List widget with TUI controlled states.
 #[derive(Debug)]
@@ -7,6 +8,8 @@ pub struct SelectableList<T> {
     pub items: Vec<T>,
     /// State that can be modified by TUI.
     pub state: State,
+    /// Index of the first visible item.
+    offset: usize,
 }
 
 impl<T> Default for SelectableList<T> {
@@ -19,7 +22,11 @@ impl<T> SelectableList<T> {
     /// Constructs a new instance of `SelectableList`.
     pub fn new(items: Vec<T>, mut state: State) -> SelectableList<T> {
         state.select(Some(0));
-        Self { items, state }
+        Self {
+            items,
+            state,
+            offset: 0,
+        }
     }
 
     /// Construct a new `SelectableList` with given items.
@@ -32,6 +39,31 @@ impl<T> SelectableList<T> {
         self.items.get(self.state.selected()?)
     }
 
+    /// Returns the range of the items that fit into the given height.
+    ///
+    /// The range is scrolled so that the selected item is visible, which allows
+    /// rendering only the visible items regardless of the number of items.
+    pub fn window(&mut self, height: usize) -> Range<usize> {
+        let height = height.max(1);
+        let selected = self.state.selected().unwrap_or_default();
+        if selected < self.offset {
+            self.offset = selected;
+        } else if selected >= self.offset + height {
+            self.offset = selected + 1 - height;
+        }
+        self.offset = self.offset.min(self.items.len().saturating_sub(height));
+        self.offset..self.items.len().min(self.offset + height)
+    }
+
+    /// Returns the table state for rendering the items in the given window.
+    pub fn window_state(&self, window: &Range<usize>) -> State {
+        State::default().with_selected(
+            self.state
+                .selected()
+                .map(|i| i.saturating_sub(window.start)),
+        )
+    }
+
     /// Selects the first item.
     pub fn first(&mut self) {
         self.state.select(Some(0));
@@ -94,4 +126,23 @@ mod tests {
         list.previous(1);
         assert_eq!(Some(0), list.state.selected());
     }
+
+    #[test]
+    fn test_window() {
+        let mut list = SelectableList::with_items((0..10).collect());
+        assert_eq!(0..4, list.window(4));
+        list.next(5);
+        assert_eq!(2..6, list.window(4));
+        assert_eq!(Some(3), list.window_state(&(2..6)).selected());
+        list.previous(2);
+        assert_eq!(2..6, list.window(4));
+        list.previous(2);
+        assert_eq!(1..5, list.window(4));
+        list.last();
+        assert_eq!(6..10, list.window(4));
+        assert_eq!(0..10, list.window(20));
+        list.items.truncate(3);
+        list.first();
+        assert_eq!(0..3, list.window(4));
+    }
 }
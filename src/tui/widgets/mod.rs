@@ -3,3 +3,12 @@ pub(crate) mod logo;
 
 /// Stateful list.
 pub mod list;
+
+/// Search index.
+pub mod search;
+
+/// Table sorting.
+pub mod sort;
+
+/// Treemap.
+pub mod treemap;
@@ -0,0 +1,88 @@
+/// Table rows with an incremental search index.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    /// Rows.
+    pub rows: Vec<Vec<String>>,
+    /// Numeric values of the cells for sorting (`None` for the text).
+    pub values: Vec<Vec<Option<i128>>>,
+    /// Lowercased contents of the rows.
+    contents: Vec<String>,
+    /// Last query.
+    query: String,
+    /// Indices of the rows that match the last query.
+    matches: Vec<usize>,
+}
+
+impl SearchIndex {
+    /// Constructs a new instance.
+    pub fn new(rows: Vec<Vec<String>>, values: Vec<Vec<Option<i128>>>) -> Self {
+        Self {
+            matches: (0..rows.len()).collect(),
+            rows,
+            values,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the indices of the rows that contain the given query (case-insensitive).
+    ///
+    /// If the query extends the last one (e.g. while typing), only the
+    /// previous matches are searched.
+    pub fn search(&mut self, query: &str) -> &[usize] {
+        let query = query.to_lowercase();
+        if query != self.query {
+            if self.contents.is_empty() {
+                // The cells are separated so that the query does not match across them.
+                self.contents = self
+                    .rows
+                    .iter()
+                    .map(|row| row.join("\n").to_lowercase())
+                    .collect();
+            }
+            let candidates = if !self.query.is_empty() && query.contains(&self.query) {
+                std::mem::take(&mut self.matches)
+            } else {
+                (0..self.rows.len()).collect()
+            };
+            self.matches = candidates
+                .into_iter()
+                .filter(|&i| self.contents[i].contains(&query))
+                .collect();
+            self.query = query;
+        }
+        &self.matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_search_index() {
+        let mut index = SearchIndex::new(
+            vec![
+                vec![String::from("main"), String::from("FUNC")],
+                vec![String::from("malloc"), String::from("FUNC")],
+                vec![String::from("environ"), String::from("OBJECT")],
+            ],
+            Vec::new(),
+        );
+        assert_eq!(3, index.search("").len());
+        let names = |index: &mut SearchIndex, query| {
+            let matches = index.search(query).to_vec();
+            matches
+                .iter()
+                .map(|i| index.rows[*i][0].as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_eq!("main,malloc", names(&mut index, "ma"));
+        assert_eq!("malloc", names(&mut index, "MAL"));
+        assert_eq!("main,malloc", names(&mut index, "ma"));
+        assert_eq!("environ", names(&mut index, "obj"));
+        assert_eq!("", names(&mut index, "cfunc"));
+        assert_eq!(3, index.search("").len());
+    }
+}
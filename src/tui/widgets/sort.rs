@@ -0,0 +1,158 @@
+use crate::tui::widgets::search::SearchIndex;
+use std::cmp::Reverse;
+
+/// Sort order of a table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableSort {
+    /// Index of the sorted column.
+    pub column: usize,
+    /// Whether if the order is descending.
+    pub descending: bool,
+}
+
+/// Key for sorting a table cell.
+///
+/// Numbers (e.g. addresses and sizes) are compared by their values and come before the text.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    /// Number.
+    Number(i128),
+    /// Text.
+    Text(String),
+}
+
+impl TableSort {
+    /// Returns the next sort order for the given number of columns.
+    ///
+    /// Each column is sorted in ascending and then descending order,
+    /// followed by the original (unsorted) order.
+    pub fn next(sort: Option<Self>, columns: usize, amount: usize) -> Option<Self> {
+        let orders = columns * 2 + 1;
+        Self::from_index((Self::index(sort) + amount) % orders)
+    }
+
+    /// Returns the previous sort order for the given number of columns.
+    pub fn previous(sort: Option<Self>, columns: usize, amount: usize) -> Option<Self> {
+        let orders = columns * 2 + 1;
+        Self::from_index((Self::index(sort) + orders - amount % orders) % orders)
+    }
+
+    /// Returns the index of the sort order in the cycle.
+    fn index(sort: Option<Self>) -> usize {
+        sort.map(|v| v.column * 2 + usize::from(v.descending) + 1)
+            .unwrap_or_default()
+    }
+
+    /// Constructs the sort order from its index in the cycle.
+    fn from_index(index: usize) -> Option<Self> {
+        index.checked_sub(1).map(|index| Self {
+            column: index / 2,
+            descending: index % 2 == 1,
+        })
+    }
+
+    /// Sorts the given indices of the rows.
+    ///
+    /// The numeric values of the cells are used instead of their (e.g. rounded) text.
+    pub fn apply(&self, index: &SearchIndex, indices: &mut [usize]) {
+        let key = |i: &usize| match index
+            .values
+            .get(*i)
+            .and_then(|values| values.get(self.column))
+            .copied()
+            .flatten()
+        {
+            Some(value) => SortKey::Number(value),
+            None => SortKey::Text(
+                index
+                    .rows
+                    .get(*i)
+                    .and_then(|row| row.get(self.column))
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+        };
+        if self.descending {
+            indices.sort_by_cached_key(|i| Reverse(key(i)));
+        } else {
+            indices.sort_by_cached_key(key);
+        }
+    }
+
+    /// Returns the given header with the sort indicator.
+    pub fn header(sort: Option<Self>, column: usize, header: &str) -> String {
+        match sort {
+            Some(sort) if sort.column == column => {
+                format!("{header} {}", if sort.descending { "▼" } else { "▲" })
+            }
+            _ => header.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_table_sort() {
+        let mut sort = None;
+        let mut orders = Vec::new();
+        for _ in 0..5 {
+            sort = TableSort::next(sort, 2, 1);
+            orders.push(sort.map(|v| (v.column, v.descending)));
+        }
+        assert_eq!(
+            vec![
+                Some((0, false)),
+                Some((0, true)),
+                Some((1, false)),
+                Some((1, true)),
+                None
+            ],
+            orders
+        );
+        assert_eq!(
+            Some(TableSort {
+                column: 1,
+                descending: true
+            }),
+            TableSort::previous(None, 2, 1)
+        );
+        assert_eq!(None, TableSort::previous(TableSort::next(None, 2, 1), 2, 1));
+
+        let rows = [
+            ["main", "0x1140", "1.2 KiB"],
+            ["_start", "0x1040", "34"],
+            ["environ", "0x4010", "8"],
+            ["", "", "1.2 KiB"],
+        ]
+        .map(|row| row.map(String::from).to_vec());
+        let values = [
+            [None, Some(0x1140), Some(1250)],
+            [None, Some(0x1040), Some(34)],
+            [None, Some(0x4010), Some(8)],
+            [None, None, Some(1230)],
+        ]
+        .map(|row| row.to_vec());
+        let index = SearchIndex::new(rows.to_vec(), values.to_vec());
+        let mut indices = (0..rows.len()).collect::<Vec<_>>();
+        let column = |indices: &[usize], column: usize| {
+            indices
+                .iter()
+                .map(|i| rows[*i][column].as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let sort = |column, descending| TableSort { column, descending };
+        sort(0, false).apply(&index, &mut indices);
+        assert_eq!(",_start,environ,main", column(&indices, 0));
+        sort(1, false).apply(&index, &mut indices);
+        assert_eq!("0x1040,0x1140,0x4010,", column(&indices, 1));
+        sort(2, true).apply(&index, &mut indices);
+        assert_eq!(vec![0, 3, 1, 2], indices);
+        assert_eq!("Size ▼", TableSort::header(Some(sort(2, true)), 2, "Size"));
+        assert_eq!("Name", TableSort::header(Some(sort(2, true)), 0, "Name"));
+    }
+}
@@ -0,0 +1,289 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, BorderType, Widget},
+};
+
+/// Colors of the top-level rectangles.
+const PALETTE: &[(u8, u8, u8)] = &[
+    (97, 175, 239),
+    (152, 195, 121),
+    (229, 192, 123),
+    (198, 120, 221),
+    (86, 182, 194),
+    (224, 108, 117),
+    (209, 154, 102),
+];
+
+/// Minimum width of a rectangle to show its label.
+const MIN_LABEL_WIDTH: u16 = 4;
+
+/// Item of a treemap.
+#[derive(Clone, Debug, Default)]
+pub struct TreemapItem {
+    /// Label of the item.
+    pub label: String,
+    /// Value that determines the area.
+    pub value: u64,
+    /// Nested items.
+    pub children: Vec<TreemapItem>,
+}
+
+/// Treemap widget that draws the items as nested rectangles sized by their values.
+///
+/// The items are expected to be sorted by their values in descending order.
+#[derive(Debug)]
+pub struct Treemap<'a> {
+    /// Items.
+    items: &'a [TreemapItem],
+    /// Selected item.
+    selected: Option<usize>,
+    /// Color of the selected item.
+    highlight_color: Color,
+}
+
+impl<'a> Treemap<'a> {
+    /// Constructs a new instance.
+    pub fn new(items: &'a [TreemapItem]) -> Self {
+        Self {
+            items,
+            selected: None,
+            highlight_color: Color::White,
+        }
+    }
+
+    /// Sets the selected item.
+    pub fn select(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Sets the color of the selected item.
+    pub fn highlight_color(mut self, color: Color) -> Self {
+        self.highlight_color = color;
+        self
+    }
+}
+
+impl Widget for Treemap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let values = self.items.iter().map(|item| item.value).collect::<Vec<_>>();
+        for (i, (item, rect)) in self.items.iter().zip(layout(&values, area)).enumerate() {
+            if rect.is_empty() {
+                continue;
+            }
+            let (r, g, b) = PALETTE[i % PALETTE.len()];
+            let color = Color::Rgb(r, g, b);
+            let is_selected = self.selected == Some(i);
+            if rect.width < 4 || rect.height < 3 {
+                buf.set_style(
+                    rect,
+                    Style::default().bg(if is_selected {
+                        self.highlight_color
+                    } else {
+                        color
+                    }),
+                );
+                continue;
+            }
+            let block = Block::bordered()
+                .border_type(if is_selected {
+                    BorderType::Thick
+                } else {
+                    BorderType::Plain
+                })
+                .border_style(if is_selected {
+                    Style::default()
+                        .fg(self.highlight_color)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(color)
+                })
+                .title(truncate(&item.label, rect.width.saturating_sub(2)));
+            let inner = block.inner(rect);
+            block.render(rect, buf);
+            render_children(item, color, inner, buf);
+        }
+    }
+}
+
+/// Renders the nested items of the given item.
+///
+/// The bytes that are not covered by the nested items are left blank.
+fn render_children(item: &TreemapItem, color: Color, area: Rect, buf: &mut Buffer) {
+    let mut values = item
+        .children
+        .iter()
+        .map(|child| child.value)
+        .collect::<Vec<_>>();
+    let remainder = item
+        .value
+        .saturating_sub(values.iter().fold(0, |sum, v| sum.saturating_add(*v)));
+    if remainder > 0 {
+        values.push(remainder);
+    }
+    for (i, (child, rect)) in item.children.iter().zip(layout(&values, area)).enumerate() {
+        if rect.is_empty() {
+            continue;
+        }
+        let background = shade(color, if i % 2 == 0 { 0.5 } else { 0.35 });
+        buf.set_style(rect, Style::default().bg(background).fg(Color::White));
+        if rect.width < MIN_LABEL_WIDTH {
+            continue;
+        }
+        buf.set_string(
+            rect.x,
+            rect.y,
+            truncate(&child.label, rect.width),
+            Style::default().bg(background).fg(Color::White),
+        );
+    }
+}
+
+/// Returns the rectangles of the given values using the squarified treemap algorithm.
+///
+/// The values are expected to be sorted in descending order.
+/// Since terminal cells are roughly twice as tall as they are wide,
+/// the layout is calculated with doubled heights to get square-looking rectangles.
+pub fn layout(values: &[u64], area: Rect) -> Vec<Rect> {
+    let total = values.iter().map(|v| *v as f64).sum::<f64>();
+    if total == 0. || area.is_empty() {
+        return vec![Rect::default(); values.len()];
+    }
+    let (mut x, mut y) = (f64::from(area.x), f64::from(area.y) * 2.);
+    let (mut width, mut height) = (f64::from(area.width), f64::from(area.height) * 2.);
+    let scale = width * height / total;
+    let areas = values
+        .iter()
+        .map(|value| *value as f64 * scale)
+        .collect::<Vec<_>>();
+    let mut rects = Vec::with_capacity(values.len());
+    let mut start = 0;
+    while start < areas.len() {
+        let side = width.min(height);
+        let mut end = start + 1;
+        let mut ratio = worst_ratio(&areas[start..end], side);
+        while end < areas.len() {
+            let next_ratio = worst_ratio(&areas[start..=end], side);
+            if next_ratio > ratio {
+                break;
+            }
+            ratio = next_ratio;
+            end += 1;
+        }
+        let row = &areas[start..end];
+        let row_area = row.iter().sum::<f64>();
+        if width >= height {
+            let row_width = if height > 0. { row_area / height } else { 0. };
+            let mut row_y = y;
+            for area in row {
+                let row_height = if row_width > 0. { area / row_width } else { 0. };
+                rects.push(to_rect(x, row_y, row_width, row_height));
+                row_y += row_height;
+            }
+            x += row_width;
+            width -= row_width;
+        } else {
+            let row_height = if width > 0. { row_area / width } else { 0. };
+            let mut row_x = x;
+            for area in row {
+                let row_width = if row_height > 0. {
+                    area / row_height
+                } else {
+                    0.
+                };
+                rects.push(to_rect(row_x, y, row_width, row_height));
+                row_x += row_width;
+            }
+            y += row_height;
+            height -= row_height;
+        }
+        start = end;
+    }
+    rects
+}
+
+/// Returns the worst aspect ratio of the rectangles in a row.
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum = row.iter().sum::<f64>();
+    let (min, max) = row.iter().fold((f64::MAX, f64::MIN), |(min, max), v| {
+        (min.min(*v), max.max(*v))
+    });
+    if sum == 0. || min == 0. {
+        return f64::MAX;
+    }
+    let side = side * side;
+    (side * max / (sum * sum)).max((sum * sum) / (side * min))
+}
+
+/// Converts the calculated coordinates (with doubled heights) to terminal cells.
+fn to_rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+    let (left, right) = (x.round(), (x + width).round());
+    let (top, bottom) = ((y / 2.).round(), ((y + height) / 2.).round());
+    Rect::new(
+        left as u16,
+        top as u16,
+        (right - left) as u16,
+        (bottom - top) as u16,
+    )
+}
+
+/// Returns a darker shade of the given color.
+fn shade(color: Color, factor: f64) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => Color::Rgb(
+            (f64::from(r) * factor) as u8,
+            (f64::from(g) * factor) as u8,
+            (f64::from(b) * factor) as u8,
+        ),
+        color => color,
+    }
+}
+
+/// Truncates the label to fit in the given width.
+fn truncate(label: &str, width: u16) -> String {
+    if label.chars().count() <= usize::from(width) {
+        label.to_string()
+    } else if width == 0 {
+        String::new()
+    } else {
+        let mut label = label
+            .chars()
+            .take(usize::from(width) - 1)
+            .collect::<String>();
+        label.push('…');
+        label
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_layout() {
+        let area = Rect::new(0, 0, 40, 10);
+        let rects = layout(&[50, 25, 25], area);
+        assert_eq!(3, rects.len());
+        assert_eq!(
+            u32::from(area.width) * u32::from(area.height),
+            rects
+                .iter()
+                .map(|v| u32::from(v.width) * u32::from(v.height))
+                .sum::<u32>()
+        );
+        assert!(rects.iter().all(|v| area.contains(v.as_position())));
+        assert_eq!(Rect::new(0, 0, 20, 10), rects[0]);
+        assert_eq!(vec![Rect::default(); 2], layout(&[0, 0], area));
+        assert_eq!(2, layout(&[u64::MAX, u64::MAX], area).len());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!("text", truncate("text", 4));
+        assert_eq!("te…", truncate("text", 3));
+        assert_eq!("", truncate("text", 0));
+    }
+}
@@ -0,0 +1,469 @@
+use crate::elf::Elf;
+use crate::job::{JobHandle, PROGRESS_INTERVAL};
+use elf::{abi, endian::AnyEndian, file::Class};
+use iced_x86::{Decoder, DecoderOptions, OpKind, Register};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Maximum number of references to show for an item.
+pub const XREFS_LIMIT: usize = 20;
+
+/// Kind of a cross-reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum XrefKind {
+    /// Relocation entry.
+    Relocation,
+    /// Absolute pointer in a data section.
+    Pointer,
+    /// Memory or immediate operand of an instruction (e.g. RIP-relative or ADRP).
+    Code,
+    /// Call instruction.
+    Call,
+}
+
+impl XrefKind {
+    /// Returns the title.
+    pub fn title(&self) -> &'static str {
+        match self {
+            XrefKind::Relocation => "reloc",
+            XrefKind::Pointer => "pointer",
+            XrefKind::Code => "code",
+            XrefKind::Call => "call",
+        }
+    }
+}
+
+/// Reference to an address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Xref {
+    /// Address of the referencing instruction or data.
+    pub from: u64,
+    /// Kind of the reference.
+    pub kind: XrefKind,
+}
+
+/// Function or data object.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Region {
+    /// Start address.
+    start: u64,
+    /// End address (exclusive).
+    end: u64,
+    /// Demangled name.
+    name: String,
+}
+
+/// Cross-references of a file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Xrefs {
+    /// References keyed by the referenced address.
+    references: BTreeMap<u64, Vec<Xref>>,
+    /// Functions and data objects sorted by their addresses.
+    regions: Vec<Region>,
+    /// Allocated sections.
+    sections: Vec<Region>,
+}
+
+impl Xrefs {
+    /// Finds the references in the given file.
+    ///
+    /// Relocatable object files are skipped since their addresses are not final.
+    /// The progress is reported to the given job and the search is stopped if it is cancelled.
+    pub fn new(elf: &Elf, bytes: &[u8], job: &JobHandle) -> Self {
+        let mut xrefs = Self {
+            references: BTreeMap::new(),
+            regions: regions(elf),
+            sections: elf
+                .section_headers
+                .iter()
+                .filter(|(_, header)| {
+                    header.sh_flags & u64::from(abi::SHF_ALLOC) != 0 && header.sh_addr != 0
+                })
+                .map(|(name, header)| Region {
+                    start: header.sh_addr,
+                    end: header.sh_addr.saturating_add(header.sh_size),
+                    name: name.to_string(),
+                })
+                .collect(),
+        };
+        let file_header = elf.file_headers.inner();
+        if file_header.e_type == abi::ET_REL {
+            return xrefs;
+        }
+        let mapped = xrefs
+            .sections
+            .iter()
+            .map(|section| section.start..section.end)
+            .collect::<Vec<_>>();
+        let is_mapped = |address: u64| mapped.iter().any(|range| range.contains(&address));
+        let reader = Reader {
+            bytes,
+            is_64: file_header.class == Class::ELF64,
+            is_little: matches!(file_header.endianness, AnyEndian::Little),
+        };
+        let mut seen = HashSet::new();
+        let mut add = |from: u64, to: u64, kind: XrefKind| {
+            if to != 0 && is_mapped(to) && seen.insert((from, to)) {
+                xrefs
+                    .references
+                    .entry(to)
+                    .or_default()
+                    .push(Xref { from, kind });
+            }
+        };
+
+        let symbols = elf
+            .dynamic_symbols
+            .iter()
+            .map(|(_, symbol)| symbol.st_value)
+            .collect::<Vec<_>>();
+        for rela in elf.relocations.relas() {
+            let target = match rela.r_sym {
+                0 => rela.r_addend as u64,
+                index => symbols
+                    .get(index as usize)
+                    .map(|value| value.wrapping_add(rela.r_addend as u64))
+                    .unwrap_or_default(),
+            };
+            add(rela.r_offset, target, XrefKind::Relocation);
+        }
+        for rel in elf.relocations.rels() {
+            // The addend is stored at the relocated location.
+            let target = match rel.r_sym {
+                0 => elf
+                    .file_offset(rel.r_offset)
+                    .and_then(|offset| reader.pointer(offset as usize))
+                    .unwrap_or_default(),
+                index => symbols.get(index as usize).copied().unwrap_or_default(),
+            };
+            add(rel.r_offset, target, XrefKind::Relocation);
+        }
+
+        // Position-independent files use relocations instead of absolute addresses,
+        // so the raw values in them are mostly integers that look like addresses.
+        let is_absolute = file_header.e_type == abi::ET_EXEC;
+        for (name, header) in elf.section_headers.iter() {
+            if header.sh_type != abi::SHT_PROGBITS
+                || header.sh_flags & u64::from(abi::SHF_ALLOC) == 0
+                || header.sh_addr == 0
+            {
+                continue;
+            }
+            let start = header.sh_offset as usize;
+            let Some(data) = bytes.get(start..start.saturating_add(header.sh_size as usize)) else {
+                continue;
+            };
+            let progress = |offset: usize| {
+                job.set_progress(start + offset, bytes.len());
+                !job.is_cancelled()
+            };
+            if !progress(0) {
+                break;
+            }
+            if header.sh_flags & u64::from(abi::SHF_EXECINSTR) != 0 {
+                let address = header.sh_addr;
+                match file_header.e_machine {
+                    abi::EM_X86_64 => {
+                        x86_references(data, address, 64, is_absolute, &mut add, &progress)
+                    }
+                    abi::EM_386 => {
+                        x86_references(data, address, 32, is_absolute, &mut add, &progress)
+                    }
+                    abi::EM_AARCH64 => {
+                        aarch64_references(data, address, reader.is_little, &mut add, &progress)
+                    }
+                    _ => {}
+                }
+            } else if is_absolute && !name.starts_with(".eh_frame") {
+                let size = reader.pointer_size();
+                let align = (size as u64 - header.sh_addr % size as u64) % size as u64;
+                for offset in (align as usize..data.len()).step_by(size) {
+                    if let Some(target) = reader.pointer(start + offset) {
+                        add(
+                            header.sh_addr.wrapping_add(offset as u64),
+                            target,
+                            XrefKind::Pointer,
+                        );
+                    }
+                }
+            }
+        }
+        xrefs
+    }
+
+    /// Returns the references to the given address range.
+    pub fn references(&self, address: u64, size: u64) -> Vec<&Xref> {
+        self.references
+            .range(address..address.saturating_add(size.max(1)))
+            .flat_map(|(_, xrefs)| xrefs)
+            .collect()
+    }
+
+    /// Returns the name of the function or data object that contains the given address
+    /// along with the offset from its start.
+    ///
+    /// Falls back to the containing section for the addresses that are not covered by symbols.
+    pub fn region(&self, address: u64) -> Option<(&str, u64)> {
+        let region = self
+            .regions
+            .partition_point(|region| region.start <= address)
+            .checked_sub(1)
+            .map(|index| &self.regions[index])
+            .filter(|region| address < region.end)
+            .or_else(|| {
+                self.sections
+                    .iter()
+                    .find(|section| (section.start..section.end).contains(&address))
+            })?;
+        Some((region.name.as_str(), address - region.start))
+    }
+
+    /// Returns the human readable description of the reference.
+    pub fn describe(&self, xref: &Xref) -> String {
+        let location = match self.region(xref.from) {
+            Some((name, 0)) => name.to_string(),
+            Some((name, offset)) => format!("{name}+{offset:#x}"),
+            None => format!("{:#x}", xref.from),
+        };
+        format!("{location} ({:#x}, {})", xref.from, xref.kind.title())
+    }
+}
+
+/// Reader for the pointers in the file.
+struct Reader<'a> {
+    /// File contents.
+    bytes: &'a [u8],
+    /// Whether if the pointers are 64-bit.
+    is_64: bool,
+    /// Whether if the file is little-endian.
+    is_little: bool,
+}
+
+impl Reader<'_> {
+    /// Returns the size of a pointer.
+    fn pointer_size(&self) -> usize {
+        if self.is_64 {
+            8
+        } else {
+            4
+        }
+    }
+
+    /// Reads a pointer at the given file offset.
+    fn pointer(&self, offset: usize) -> Option<u64> {
+        let bytes = self
+            .bytes
+            .get(offset..offset.checked_add(self.pointer_size())?)?;
+        Some(match (self.is_64, self.is_little) {
+            (true, true) => u64::from_le_bytes(bytes.try_into().ok()?),
+            (true, false) => u64::from_be_bytes(bytes.try_into().ok()?),
+            (false, true) => u64::from(u32::from_le_bytes(bytes.try_into().ok()?)),
+            (false, false) => u64::from(u32::from_be_bytes(bytes.try_into().ok()?)),
+        })
+    }
+}
+
+/// Returns the functions and data objects from the symbol tables.
+fn regions(elf: &Elf) -> Vec<Region> {
+    let mut seen = HashSet::new();
+    let mut regions = elf
+        .symbols
+        .iter()
+        .chain(elf.dynamic_symbols.iter())
+        .filter(|(name, symbol)| {
+            !name.is_empty()
+                && !symbol.is_undefined()
+                && symbol.st_value != 0
+                && matches!(symbol.st_symtype(), abi::STT_FUNC | abi::STT_OBJECT)
+                && seen.insert(symbol.st_value)
+        })
+        .map(|(name, symbol)| Region {
+            start: symbol.st_value,
+            end: symbol.st_value.saturating_add(symbol.st_size.max(1)),
+            name: rustc_demangle::try_demangle(name)
+                .map(|name| format!("{name:#}"))
+                .unwrap_or_else(|_| name.to_string()),
+        })
+        .collect::<Vec<_>>();
+    regions.sort_by_key(|region| region.start);
+    regions
+}
+
+/// Finds the references in x86 code.
+///
+/// The absolute memory displacements and immediates are only considered if `is_absolute` is set.
+/// The decoding is stopped if `progress` returns `false`.
+fn x86_references(
+    data: &[u8],
+    address: u64,
+    bitness: u32,
+    is_absolute: bool,
+    add: &mut impl FnMut(u64, u64, XrefKind),
+    progress: &dyn Fn(usize) -> bool,
+) {
+    let mut decoder = Decoder::with_ip(bitness, data, address, DecoderOptions::NONE);
+    for (i, instruction) in decoder.iter().enumerate() {
+        if i % PROGRESS_INTERVAL == 0 && !progress(instruction.ip().wrapping_sub(address) as usize)
+        {
+            return;
+        }
+        if instruction.is_invalid() {
+            continue;
+        }
+        if instruction.is_call_near() {
+            add(
+                instruction.ip(),
+                instruction.near_branch_target(),
+                XrefKind::Call,
+            );
+        } else if instruction.is_ip_rel_memory_operand() {
+            add(
+                instruction.ip(),
+                instruction.ip_rel_memory_address(),
+                XrefKind::Code,
+            );
+        } else if is_absolute {
+            for operand in 0..instruction.op_count() {
+                let target = match instruction.op_kind(operand) {
+                    OpKind::Memory
+                        if instruction.memory_base() == Register::None
+                            && instruction.memory_index() == Register::None =>
+                    {
+                        instruction.memory_displacement64()
+                    }
+                    OpKind::Immediate32 | OpKind::Immediate32to64 | OpKind::Immediate64 => {
+                        instruction.immediate(operand)
+                    }
+                    _ => continue,
+                };
+                add(instruction.ip(), target, XrefKind::Code);
+            }
+        }
+    }
+}
+
+/// Finds the references in AArch64 code.
+///
+/// The page addresses of `ADRP` instructions are tracked per register and the references
+/// are resolved by the following `ADD` or `LDR`/`STR` (unsigned offset) instructions.
+/// The decoding is stopped if `progress` returns `false`.
+fn aarch64_references(
+    data: &[u8],
+    address: u64,
+    is_little: bool,
+    add: &mut impl FnMut(u64, u64, XrefKind),
+    progress: &dyn Fn(usize) -> bool,
+) {
+    let mut pages = [None::<(u64, u64)>; 32];
+    for (i, chunk) in data.chunks_exact(4).enumerate() {
+        if i % (PROGRESS_INTERVAL / 4) == 0 && !progress(i * 4) {
+            return;
+        }
+        let pc = address.wrapping_add(i as u64 * 4);
+        let bytes = [chunk[0], chunk[1], chunk[2], chunk[3]];
+        let instruction = if is_little {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        };
+        let rd = (instruction & 0x1f) as usize;
+        let rn = ((instruction >> 5) & 0x1f) as usize;
+        let imm12 = u64::from((instruction >> 10) & 0xfff);
+        if instruction & 0x9f00_0000 == 0x9000_0000 {
+            // ADRP <Xd>, <label>
+            let imm = (((instruction >> 5) & 0x7ffff) << 2) | ((instruction >> 29) & 0x3);
+            let offset = sign_extend(u64::from(imm), 21) << 12;
+            pages[rd] = Some((pc, (pc & !0xfff).wrapping_add(offset)));
+        } else if instruction & 0xff80_0000 == 0x9100_0000 {
+            // ADD <Xd>, <Xn>, #<imm>{, <shift>}
+            if let Some((from, page)) = pages[rn] {
+                let shift = if (instruction >> 22) & 1 == 1 { 12 } else { 0 };
+                add(from, page.wrapping_add(imm12 << shift), XrefKind::Code);
+            }
+            pages[rd] = None;
+        } else if instruction & 0x3f00_0000 == 0x3900_0000 {
+            // LDR/STR <Rt>, [<Xn>, #<imm>]
+            if let Some((from, page)) = pages[rn] {
+                let scale = instruction >> 30;
+                add(from, page.wrapping_add(imm12 << scale), XrefKind::Code);
+            }
+        } else if instruction & 0xfc00_0000 == 0x9400_0000 {
+            // BL <label>
+            let offset = sign_extend(u64::from(instruction & 0x3ff_ffff), 26) << 2;
+            add(pc, pc.wrapping_add(offset), XrefKind::Call);
+        }
+    }
+}
+
+/// Sign-extends the value with the given number of bits.
+fn sign_extend(value: u64, bits: u32) -> u64 {
+    let shift = 64 - bits;
+    (((value << shift) as i64) >> shift) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_x86_references() {
+        let mut references = Vec::new();
+        // lea rdi, [rip + 0x100]; call 0x1000
+        let code = [
+            0x48, 0x8d, 0x3d, 0x00, 0x01, 0x00, 0x00, 0xe8, 0xf4, 0x0f, 0x00, 0x00,
+        ];
+        x86_references(
+            &code,
+            0x1000,
+            64,
+            false,
+            &mut |from, to, kind| references.push((from, to, kind)),
+            &|_| true,
+        );
+        assert_eq!(
+            vec![
+                (0x1000, 0x1107, XrefKind::Code),
+                (0x1007, 0x2000, XrefKind::Call),
+            ],
+            references
+        );
+    }
+
+    #[test]
+    fn test_aarch64_references() {
+        let mut references = Vec::new();
+        // nop; adrp x0, 0x31000; add x0, x0, #0x10; ldr x1, [x0, #8]; bl 0x10000
+        let code = [
+            0xd503_201f_u32,
+            0xb000_0100,
+            0x9100_4000,
+            0xf940_0401,
+            0x97ff_fffc,
+        ];
+        let data = code
+            .iter()
+            .flat_map(|instruction| instruction.to_le_bytes())
+            .collect::<Vec<_>>();
+        aarch64_references(
+            &data,
+            0x10000,
+            true,
+            &mut |from, to, kind| references.push((from, to, kind)),
+            &|_| true,
+        );
+        assert_eq!(
+            vec![
+                (0x10004, 0x31010, XrefKind::Code),
+                (0x10010, 0x10000, XrefKind::Call),
+            ],
+            references
+        );
+    }
+
+    #[test]
+    fn test_sign_extend() {
+        assert_eq!(u64::MAX, sign_extend(0x3ff_ffff, 26));
+        assert_eq!(0x10, sign_extend(0x10, 26));
+    }
+}
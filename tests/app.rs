@@ -1,48 +1,471 @@
-use binsider::{app::Analyzer, error::Result, file::FileInfo, prelude::Event};
-use std::{fs, path::PathBuf, sync::mpsc};
+use binsider::{
+    abi::Verdict,
+    app::Analyzer,
+    deps::{DependencyTree, Provider, RowKind},
+    diff::{Diff, DiffInfo},
+    error::Result,
+    file::{FileData, FileInfo},
+    insights::{capabilities::Capability, rules::RuleSet},
+    prelude::{Command, Event, ScrollType, State, Tab},
+    process::Process,
+    scan::{Relro, Scan},
+    size::{SizeInfo, ELF_HEADERS, UNACCOUNTED},
+    tui::ui::ELF_INFO_TABS,
+};
+use elf::{endian::AnyEndian, ElfBytes};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+};
 
 fn get_test_path() -> PathBuf {
     PathBuf::from(env!("CARGO_BIN_EXE_binsider"))
 }
 
-fn get_test_bytes() -> Result<Vec<u8>> {
+fn get_test_bytes() -> Result<Arc<FileData>> {
     let debug_binary = get_test_path();
-    Ok(fs::read(debug_binary)?)
+    Ok(Arc::new(FileData::open(&debug_binary)?))
 }
 
-#[test]
-fn test_init() -> Result<()> {
+fn get_test_analyzer() -> Result<Analyzer<'static>> {
     Analyzer::new(
         FileInfo::new(
             get_test_path().to_str().expect("failed to get test path"),
             None,
-            get_test_bytes()?.as_slice(),
+            get_test_bytes()?,
         )?,
         4,
         vec![],
+        None,
     )
-    .map(|_| ())
+}
+
+#[test]
+fn test_init() -> Result<()> {
+    let analyzer = get_test_analyzer()?;
+    // The hexdump must not write to the memory-mapped file.
+    assert!(matches!(*analyzer.file.bytes, FileData::Mapped(_)));
+    assert!(analyzer.file.is_read_only);
+    // The values for sorting must match the cells.
+    for info in ELF_INFO_TABS {
+        let property = analyzer.elf.info(info);
+        let (items, values) = (property.items(), property.values());
+        assert_eq!(items.len(), values.len());
+        assert!(items
+            .iter()
+            .zip(&values)
+            .all(|(items, values)| items.len() == values.len()));
+    }
+    Ok(())
 }
 
 #[test]
 fn test_extract_strings() -> Result<()> {
+    let mut analyzer = get_test_analyzer()?;
+    let (tx, rx) = mpsc::channel();
+    analyzer.extract_strings(tx);
+    if let Event::FileStrings(_, strings) = rx.recv()? {
+        let strings = strings?;
+        assert!(strings.iter().any(|v| v.value == ".debug_str"));
+        assert!(strings
+            .iter()
+            .filter(|v| v.section.as_deref() == Some(".rodata"))
+            .all(|v| v.address.is_some()));
+        let len = strings.len();
+        analyzer.set_strings(Some(strings));
+        assert_eq!(len, analyzer.strings_rows.rows.len());
+        assert!(!analyzer.strings_rows.search(".DEBUG_STR").is_empty());
+    } else {
+        panic!("strings did not succeed");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_diff() -> Result<()> {
+    let old = get_test_analyzer()?;
+    let new = get_test_analyzer()?;
+    let diff = Diff::new(&old, &new, 4)?;
+    assert!(!diff.rows(&DiffInfo::SectionHeaders).is_empty());
+    for info in [
+        DiffInfo::SectionHeaders,
+        DiffInfo::Symbols,
+        DiffInfo::Strings,
+    ] {
+        assert_eq!((0, 0, 0), diff.summary(&info));
+    }
+    assert_eq!(Verdict::Compatible, diff.abi.verdict());
+    Ok(())
+}
+
+#[test]
+fn test_size() -> Result<()> {
+    let analyzer = get_test_analyzer()?;
+    let sections = analyzer.size().entries(&SizeInfo::Sections);
+    assert!(sections.iter().any(|v| v.name == ".text"));
+    assert!(sections.iter().any(|v| v.name == ELF_HEADERS));
+    assert!(sections.iter().any(|v| v.name == UNACCOUNTED));
+    assert_eq!(
+        analyzer.file.bytes.len() as u64,
+        sections.iter().map(|v| v.file_size).sum::<u64>()
+    );
+    let symbols = analyzer.size().entries(&SizeInfo::Symbols);
+    assert!(symbols
+        .windows(2)
+        .all(|v| v[0].file_size.max(v[0].vm_size) >= v[1].file_size.max(v[1].vm_size)));
+    assert!(analyzer
+        .size()
+        .entries(&SizeInfo::Crates)
+        .iter()
+        .any(|v| v.name == "binsider"));
+    Ok(())
+}
+
+#[test]
+fn test_xrefs() -> Result<()> {
+    let mut analyzer = get_test_analyzer()?;
+    let (tx, rx) = mpsc::channel();
+    analyzer.extract_xrefs(tx);
+    if let Event::Xrefs(_, xrefs) = rx.recv()? {
+        let xrefs = xrefs?;
+        let (_, main) = analyzer
+            .elf
+            .symbols
+            .iter()
+            .find(|(name, _)| *name == "main")
+            .expect("main symbol does not exist");
+        assert!(xrefs
+            .references(main.st_value, main.st_size)
+            .iter()
+            .any(|xref| xrefs.describe(xref).starts_with("_start")));
+    } else {
+        panic!("xrefs did not succeed");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rules() -> Result<()> {
+    let mut analyzer = get_test_analyzer()?;
+    analyzer.rules = RuleSet::parse(
+        r#"
+        rule Elf { strings: $magic = { 7F 45 4C 46 } condition: $magic at 0 }
+        rule Binsider { strings: $a = "binsider" condition: Elf and #a > 1 and section(".text") }
+        rule Missing { condition: library("libmissing.so") }
+        "#,
+    )?;
+    let (tx, rx) = mpsc::channel();
+    analyzer.match_rules(tx);
+    if let Event::RuleMatches(_, matches) = rx.recv()? {
+        let matches = matches?;
+        assert_eq!(
+            vec!["Elf", "Binsider"],
+            matches.iter().map(|v| v.rule.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(0, matches[0].strings[0].offset);
+    } else {
+        panic!("rule matching did not succeed");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_hashes() -> Result<()> {
+    let mut analyzer = get_test_analyzer()?;
+    let (tx, rx) = mpsc::channel();
+    analyzer.extract_hashes(tx);
+    if let Event::FileHashes(_, hashes) = rx.recv()? {
+        let hashes = hashes?;
+        assert_eq!(32, hashes.md5.len());
+        assert_eq!(40, hashes.sha1.len());
+        assert_eq!(64, hashes.sha256.len());
+        assert!(hashes.import_hash.is_some());
+        assert!(hashes.symbol_hash.is_some());
+        assert!(hashes.tlsh.is_some_and(|v| v.starts_with("T1")));
+        assert!(!hashes.ssdeep.is_empty());
+    } else {
+        panic!("hashing did not succeed");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_capabilities() -> Result<()> {
+    let analyzer = get_test_analyzer()?;
+    let capabilities = analyzer.capabilities();
+    assert!(capabilities
+        .imports(Capability::FileIo)
+        .any(|import| import.name == "open64"));
+    assert!(capabilities
+        .imports(Capability::MemoryProtection)
+        .any(|import| import.name == "mmap64"));
+    assert!(capabilities
+        .summary()
+        .iter()
+        .any(|row| row[0] == Capability::FileIo.title()));
+    Ok(())
+}
+
+#[test]
+fn test_dependencies() -> Result<()> {
     let test_bytes = get_test_bytes()?;
     let test_path = get_test_path();
-    let mut analyzer = Analyzer::new(
+    let tree = DependencyTree::new(&test_path, &test_bytes, Path::new("/"))?;
+    assert_eq!(0, tree.missing().count());
+    let libc = tree
+        .libraries
+        .iter()
+        .find(|library| library.name == "libc.so.6")
+        .expect("libc is not found");
+    assert!(libc.path.as_ref().is_some_and(|path| path.exists()));
+    assert!(libc.source.is_some());
+    assert!(tree
+        .explain(libc)
+        .iter()
+        .any(|(name, _)| name == "Found via"));
+    let rows = tree.rows();
+    assert!(rows
+        .iter()
+        .any(|(kind, row)| *kind == RowKind::Found && row[0] == "libc.so.6"));
+    assert!(rows
+        .last()
+        .is_some_and(|(_, row)| Some(&row[0]) == tree.interpreter.as_ref().map(|v| &v.name)));
+    Ok(())
+}
+
+#[test]
+fn test_sysroot() -> Result<()> {
+    let test_bytes = get_test_bytes()?;
+    let test_path = get_test_path();
+    let host = DependencyTree::new(&test_path, &test_bytes, Path::new("/"))?;
+    let libc = host
+        .libraries
+        .iter()
+        .find(|library| library.name == "libc.so.6")
+        .and_then(|library| library.realpath.clone())
+        .expect("libc is not found");
+    let root = env::temp_dir().join(format!("binsider-test-sysroot-{}", std::process::id()));
+    fs::create_dir_all(root.join("usr/lib"))?;
+    fs::copy(&libc, root.join("usr/lib/libc.so.6"))?;
+    let tree = DependencyTree::new(&test_path, &test_bytes, &root);
+    fs::remove_dir_all(&root)?;
+    let tree = tree?;
+    let libc = tree
+        .libraries
+        .iter()
+        .find(|library| library.name == "libc.so.6")
+        .expect("libc is not found");
+    assert_eq!(Some(root.join("usr/lib/libc.so.6")), libc.realpath);
+    assert!(tree
+        .missing()
+        .flat_map(|library| &library.searched)
+        .all(|searched| searched.path.starts_with(&root)));
+    Ok(())
+}
+
+#[test]
+fn test_symbol_providers() -> Result<()> {
+    let analyzer = get_test_analyzer()?;
+    let providers = analyzer.symbol_providers();
+    assert!(!providers.contains(&Some(Provider::Missing)));
+    let (index, _) = analyzer
+        .elf
+        .dynamic_symbols
+        .iter()
+        .enumerate()
+        .find(|(_, (name, _))| *name == "malloc")
+        .expect("malloc is not imported");
+    assert_eq!(
+        "libc.so.6",
+        analyzer.dependencies().provider_name(providers[index])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_open_files() -> Result<()> {
+    let test_path = get_test_path();
+    let mut analyzer = get_test_analyzer()?;
+    analyzer.files = vec![test_path.clone()];
+    let libc_path = analyzer
+        .dependencies()
+        .libraries
+        .iter()
+        .find(|library| library.name == "libc.so.6")
+        .and_then(|library| library.display_path())
+        .map(PathBuf::from)
+        .expect("libc is not found");
+    let libc_bytes = Arc::new(FileData::open(&libc_path)?);
+    let libc_path_str = libc_path.to_string_lossy().to_string();
+    let mut state = State::new(analyzer, None)?;
+    state.view.tab = Tab::StaticAnalysis;
+    state.handle_tab()?;
+    state.view.list.state.select(Some(3));
+
+    state.open_file(Analyzer::new(
+        FileInfo::new(&libc_path_str, None, libc_bytes)?,
+        4,
+        vec![test_path.clone(), libc_path.clone()],
+        None,
+    )?)?;
+    assert_eq!(1, state.view.analyzer.id);
+    assert_eq!(Tab::General, state.view.tab);
+    assert_eq!(Some(1), state.find_file(&libc_path));
+    assert_eq!(Some(0), state.find_file(&test_path));
+
+    let (tx, _rx) = mpsc::channel();
+    state.run_command(Command::Previous(ScrollType::File, 1), tx.clone())?;
+    assert_eq!(0, state.view.analyzer.id);
+    assert_eq!(Tab::StaticAnalysis, state.view.tab);
+    assert_eq!(Some(3), state.view.list.state.selected());
+
+    state.run_command(Command::Next(ScrollType::File, 1), tx)?;
+    assert_eq!(1, state.view.analyzer.id);
+    assert!(state.open_files[0].is_some() && state.open_files[1].is_none());
+    Ok(())
+}
+
+#[test]
+fn test_jump_to_hexdump() -> Result<()> {
+    // Craft a section that ends beyond the address space.
+    let mut bytes = fs::read(get_test_path())?;
+    let elf = ElfBytes::<AnyEndian>::minimal_parse(&bytes)?;
+    let (section_headers, strtab) = elf.section_headers_with_strtab()?;
+    let index = section_headers
+        .zip(strtab)
+        .and_then(|(headers, strtab)| {
+            headers
+                .iter()
+                .position(|header| strtab.get(header.sh_name as usize).ok() == Some(".comment"))
+        })
+        .expect("section is not found");
+    let size_offset = elf.ehdr.e_shoff as usize + index * elf.ehdr.e_shentsize as usize + 0x20;
+    bytes[size_offset..size_offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    let path = env::temp_dir().join(format!("binsider-crafted-{}", std::process::id()));
+    fs::write(&path, bytes)?;
+
+    let analyzer = Analyzer::new(
         FileInfo::new(
-            test_path.to_str().expect("failed to get test path"),
+            path.to_str().expect("failed to get crafted path"),
             None,
-            test_bytes.as_slice(),
+            Arc::new(FileData::open(&path)?),
         )?,
         4,
         vec![],
+        None,
+    )?;
+    let mut state = State::new(analyzer, None)?;
+    state.view.tab = Tab::StaticAnalysis;
+    state.view.info_index = 1;
+    state.handle_tab()?;
+    state.view.list.state.select(Some(index));
+    assert_eq!(
+        Some(".comment"),
+        state.selected_row().map(|row| row[0].as_str())
+    );
+    let (tx, _rx) = mpsc::channel();
+    state.run_command(Command::JumpToHexdump, tx)?;
+    fs::remove_file(&path)?;
+    assert_eq!(Tab::Hexdump, state.view.tab);
+    assert!(state
+        .view
+        .analyzer
+        .heh
+        .labels
+        .notification
+        .contains("..0xffffffffffffffff"));
+    Ok(())
+}
+
+#[test]
+fn test_scan() -> Result<()> {
+    let dir = env::temp_dir().join(format!("binsider-test-scan-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("usr/bin"))?;
+    let bin_path = dir.join("usr/bin/binsider");
+    fs::copy(get_test_path(), &bin_path)?;
+    fs::write(dir.join("usr/README"), "not an ELF file")?;
+    std::os::unix::fs::symlink(&bin_path, dir.join("binsider"))?;
+
+    let scan = Scan::new(std::slice::from_ref(&dir))?;
+    assert_eq!(1, scan.entries.len());
+    assert_eq!(0, scan.skipped);
+    let entry = &scan.entries[0];
+    assert_eq!(bin_path, entry.path);
+    assert_eq!("executable", entry.kind);
+    assert!(!entry.stripped);
+    assert_eq!(Some(true), entry.nx);
+    assert_ne!(Some(Relro::None), entry.relro);
+    assert!(entry.dependencies > 0);
+    assert!(entry.glibc.is_some());
+    assert!(Scan::new(&[dir.join("usr/README")]).is_err());
+
+    let test_bytes = get_test_bytes()?;
+    let analyzer = Analyzer::new(
+        FileInfo::new(
+            bin_path.to_str().expect("failed to get test path"),
+            None,
+            Arc::clone(&test_bytes),
+        )?,
+        4,
+        vec![bin_path.clone()],
+        None,
     )?;
+    let mut state = State::new(analyzer, None)?;
+    state.scan = Some(scan);
+    state.view.tab = Tab::Scan;
+    state.handle_tab()?;
+    assert_eq!(1, state.view.list.items.len());
     let (tx, rx) = mpsc::channel();
-    analyzer.extract_strings(tx);
-    if let Event::FileStrings(strings) = rx.recv()? {
-        assert!(strings?.iter().map(|(s, _)| s).any(|v| v == ".debug_str"));
-    } else {
-        panic!("strings did not succeed");
+    state.run_command(Command::ShowDetails, tx)?;
+    assert!(matches!(rx.try_recv(), Ok(Event::OpenFile(path)) if path == bin_path));
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_process() -> Result<()> {
+    let mut child = std::process::Command::new("sleep").arg("30").spawn()?;
+    let mut process = Process::new(child.id())?;
+    // Wait for the dynamic loader to map the libraries.
+    for _ in 0..100 {
+        if !process.libraries().is_empty() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        process.refresh()?;
     }
+    assert!(process.running);
+    let exe_path = process.exe.to_string_lossy().to_string();
+    let exe_bytes = Arc::new(FileData::open(&process.exe)?);
+    let mut analyzer = Analyzer::new(
+        FileInfo::new(&exe_path, None, exe_bytes)?,
+        4,
+        vec![process.exe.clone()],
+        None,
+    )?;
+    assert!(process
+        .map_rows(&analyzer.elf)
+        .iter()
+        .any(|row| row[5].starts_with("LOAD") && row[6] == exe_path));
+    assert!(process
+        .library_rows(analyzer.dependencies())
+        .iter()
+        .any(|row| row[0] == "libc.so.6" && row[1] == "loaded"));
+    analyzer.process = Some(process);
+    let mut state = State::new(analyzer, None)?;
+    state.view.tab = Tab::Process;
+    state.handle_tab()?;
+    assert!(!state.view.list.items.is_empty());
+    child.kill()?;
+    child.wait()?;
+    state.handle_tab()?;
+    assert!(!state
+        .view
+        .analyzer
+        .process
+        .as_ref()
+        .is_some_and(|v| v.running));
     Ok(())
 }